@@ -0,0 +1,876 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+use spl_token::instruction as token_instruction;
+
+use crate::UnicornFactoryError;
+
+// Swap fee charged on the input side, in basis points
+pub const POOL_FEE_BPS: u16 = 30;
+
+// Constant-product pool account structure
+//
+// A pool is a PDA derived from [b"pool", project]. Project tokens sit in a token
+// account derived from [b"pool_token", pool] and the quote asset in a vault
+// derived from [b"pool_quote", pool], which is a lamport account for native SOL
+// projects and a token account for SPL quote mints. LP shares are minted from
+// [b"lp_mint", pool]; liquidity seeded at graduation is never minted and stays
+// locked in the pool for good.
+#[derive(Debug)]
+pub struct Pool {
+    pub project: Pubkey,
+    pub token_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub token_reserve: u64,
+    pub quote_reserve: u64,
+    pub lp_supply: u64,
+    pub locked_liquidity: u64,
+    pub fee_bps: u16,
+    pub bump: u8,
+    pub token_vault_bump: u8,
+    pub quote_vault_bump: u8,
+    pub lp_mint_bump: u8,
+}
+
+impl Pool {
+    pub const LEN: usize = 32 + // project
+        32 + // token_mint
+        32 + // quote_mint
+        8 + // token_reserve
+        8 + // quote_reserve
+        8 + // lp_supply
+        8 + // locked_liquidity
+        2 + // fee_bps
+        1 + // bump
+        1 + // token_vault_bump
+        1 + // quote_vault_bump
+        1; // lp_mint_bump
+
+    pub fn pack(&self, dst: &mut [u8]) {
+        let mut offset = 0;
+
+        // Pack project
+        dst[offset..offset + 32].copy_from_slice(&self.project.to_bytes());
+        offset += 32;
+
+        // Pack token_mint
+        dst[offset..offset + 32].copy_from_slice(&self.token_mint.to_bytes());
+        offset += 32;
+
+        // Pack quote_mint
+        dst[offset..offset + 32].copy_from_slice(&self.quote_mint.to_bytes());
+        offset += 32;
+
+        // Pack token_reserve
+        dst[offset..offset + 8].copy_from_slice(&self.token_reserve.to_le_bytes());
+        offset += 8;
+
+        // Pack quote_reserve
+        dst[offset..offset + 8].copy_from_slice(&self.quote_reserve.to_le_bytes());
+        offset += 8;
+
+        // Pack lp_supply
+        dst[offset..offset + 8].copy_from_slice(&self.lp_supply.to_le_bytes());
+        offset += 8;
+
+        // Pack locked_liquidity
+        dst[offset..offset + 8].copy_from_slice(&self.locked_liquidity.to_le_bytes());
+        offset += 8;
+
+        // Pack fee_bps
+        dst[offset..offset + 2].copy_from_slice(&self.fee_bps.to_le_bytes());
+        offset += 2;
+
+        // Pack bumps
+        dst[offset] = self.bump;
+        offset += 1;
+        dst[offset] = self.token_vault_bump;
+        offset += 1;
+        dst[offset] = self.quote_vault_bump;
+        offset += 1;
+        dst[offset] = self.lp_mint_bump;
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < Pool::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut offset = 0;
+
+        // Unpack project
+        let project = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack token_mint
+        let token_mint = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack quote_mint
+        let quote_mint = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack token_reserve
+        let token_reserve = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack quote_reserve
+        let quote_reserve = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack lp_supply
+        let lp_supply = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack locked_liquidity
+        let locked_liquidity = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack fee_bps
+        let fee_bps = u16::from_le_bytes(src[offset..offset + 2].try_into().unwrap());
+        offset += 2;
+
+        // Unpack bumps
+        let bump = src[offset];
+        offset += 1;
+        let token_vault_bump = src[offset];
+        offset += 1;
+        let quote_vault_bump = src[offset];
+        offset += 1;
+        let lp_mint_bump = src[offset];
+
+        Ok(Pool {
+            project,
+            token_mint,
+            quote_mint,
+            token_reserve,
+            quote_reserve,
+            lp_supply,
+            locked_liquidity,
+            fee_bps,
+            bump,
+            token_vault_bump,
+            quote_vault_bump,
+            lp_mint_bump,
+        })
+    }
+
+    pub fn is_native_quote(&self) -> bool {
+        self.quote_mint == Pubkey::default()
+    }
+}
+
+// Integer square root used to size the initial liquidity
+pub fn integer_sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+// Creates a program-derived account owned by `owner` and funded by `payer`
+pub fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+    owner: &Pubkey,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let rent = Rent::get()?;
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            rent.minimum_balance(space),
+            space as u64,
+            owner,
+        ),
+        &[payer.clone(), new_account.clone(), system_program.clone()],
+        &[seeds],
+    )
+}
+
+// Verifies a pool-derived account such as a vault or the LP mint
+pub fn verify_pool_pda(
+    program_id: &Pubkey,
+    pool_account: &AccountInfo,
+    seed: &[u8],
+    bump: u8,
+    account: &AccountInfo,
+) -> ProgramResult {
+    let expected =
+        Pubkey::create_program_address(&[seed, pool_account.key.as_ref(), &[bump]], program_id)
+            .map_err(|_| UnicornFactoryError::InvalidPoolAccount)?;
+
+    if expected != *account.key {
+        msg!(
+            "Invalid pool account for seed {:?}. Expected: {}, Got: {}",
+            seed,
+            expected,
+            account.key
+        );
+        return Err(UnicornFactoryError::InvalidPoolAccount.into());
+    }
+    Ok(())
+}
+
+// Creates a token account derived from `seeds` whose owner is the pool PDA
+pub fn create_pool_token_account<'a>(
+    payer: &AccountInfo<'a>,
+    token_account: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    pool_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    create_pda_account(
+        payer,
+        token_account,
+        system_program,
+        spl_token::state::Account::LEN,
+        &spl_token::id(),
+        seeds,
+    )?;
+
+    invoke(
+        &token_instruction::initialize_account3(
+            &spl_token::id(),
+            token_account.key,
+            mint.key,
+            pool_account.key,
+        )?,
+        &[token_account.clone(), mint.clone()],
+    )
+}
+
+// Moves `amount` of the pool's quote asset from the pool quote vault to `to`
+pub fn withdraw_pool_quote<'a>(
+    pool_account: &AccountInfo<'a>,
+    pool: &Pool,
+    quote_vault: &AccountInfo<'a>,
+    to: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    if pool.is_native_quote() {
+        let quote_vault_seeds = &[
+            b"pool_quote".as_ref(),
+            pool_account.key.as_ref(),
+            &[pool.quote_vault_bump],
+        ];
+
+        return invoke_signed(
+            &system_instruction::transfer(quote_vault.key, to.key, amount),
+            &[quote_vault.clone(), to.clone()],
+            &[quote_vault_seeds],
+        );
+    }
+
+    withdraw_pool_token(pool_account, pool, quote_vault, to, amount)
+}
+
+// Moves SPL tokens out of a token account owned by the pool PDA
+pub fn withdraw_pool_token<'a>(
+    pool_account: &AccountInfo<'a>,
+    pool: &Pool,
+    source: &AccountInfo<'a>,
+    to: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    let pool_seeds = &[b"pool".as_ref(), pool.project.as_ref(), &[pool.bump]];
+
+    invoke_signed(
+        &token_instruction::transfer(
+            &spl_token::id(),
+            source.key,
+            to.key,
+            pool_account.key,
+            &[],
+            amount,
+        )?,
+        &[source.clone(), to.clone(), pool_account.clone()],
+        &[pool_seeds],
+    )
+}
+
+// Moves quote asset from `user` into the pool quote vault. For SPL quote mints
+// `user_quote_account` is the user's token account; for native SOL it is the
+// user itself.
+fn deposit_pool_quote<'a>(
+    pool: &Pool,
+    user: &AccountInfo<'a>,
+    user_quote_account: &AccountInfo<'a>,
+    quote_vault: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    if pool.is_native_quote() {
+        return invoke(
+            &system_instruction::transfer(user.key, quote_vault.key, amount),
+            &[user.clone(), quote_vault.clone()],
+        );
+    }
+
+    deposit_pool_token(user, user_quote_account, quote_vault, amount)
+}
+
+// Moves SPL tokens from a user's token account into a pool token account
+fn deposit_pool_token<'a>(
+    user: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    invoke(
+        &token_instruction::transfer(
+            &spl_token::id(),
+            source.key,
+            destination.key,
+            user.key,
+            &[],
+            amount,
+        )?,
+        &[source.clone(), destination.clone(), user.clone()],
+    )
+}
+
+// Loads a pool and verifies its reserve vaults
+fn load_pool(
+    program_id: &Pubkey,
+    pool_account: &AccountInfo,
+    token_vault: &AccountInfo,
+    quote_vault: &AccountInfo,
+) -> Result<Pool, ProgramError> {
+    if pool_account.owner != program_id {
+        msg!("Pool account not owned by program");
+        return Err(UnicornFactoryError::InvalidPoolAccount.into());
+    }
+
+    let pool_data = pool_account.data.borrow();
+    let pool = Pool::unpack(&pool_data)?;
+    drop(pool_data);
+
+    let expected_pool =
+        Pubkey::create_program_address(&[b"pool", pool.project.as_ref(), &[pool.bump]], program_id)
+            .map_err(|_| UnicornFactoryError::InvalidPoolAccount)?;
+    if expected_pool != *pool_account.key {
+        msg!(
+            "Invalid pool account PDA. Expected: {}, Got: {}",
+            expected_pool,
+            pool_account.key
+        );
+        return Err(UnicornFactoryError::InvalidPoolAccount.into());
+    }
+
+    verify_pool_pda(
+        program_id,
+        pool_account,
+        b"pool_token",
+        pool.token_vault_bump,
+        token_vault,
+    )?;
+    verify_pool_pda(
+        program_id,
+        pool_account,
+        b"pool_quote",
+        pool.quote_vault_bump,
+        quote_vault,
+    )?;
+
+    Ok(pool)
+}
+
+// Constant-product output for `amount_in`, after the pool fee
+fn swap_output(pool: &Pool, amount_in: u64, reserve_in: u64, reserve_out: u64) -> u64 {
+    let amount_in_after_fee = amount_in as u128 * (10_000 - pool.fee_bps as u128) / 10_000;
+    let denominator = reserve_in as u128 + amount_in_after_fee;
+    if denominator == 0 {
+        return 0;
+    }
+    (reserve_out as u128 * amount_in_after_fee / denominator) as u64
+}
+
+// Swap instruction processor
+//
+// `quote_to_token` buys project tokens with the quote asset; otherwise project
+// tokens are sold into the pool for the quote asset.
+pub fn process_swap(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount_in: u64,
+    min_amount_out: u64,
+    quote_to_token: bool,
+) -> ProgramResult {
+    msg!("Starting pool swap");
+    let account_info_iter = &mut accounts.iter();
+
+    let pool_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Pool Account key: {}",
+        pool_account.key
+    );
+
+    let user_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: User Account key: {}",
+        user_account.key
+    );
+
+    let user_token_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: User Token Account key: {}",
+        user_token_account.key
+    );
+
+    let token_vault = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Pool Token Vault key: {}",
+        token_vault.key
+    );
+
+    let quote_vault = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Pool Quote Vault key: {}",
+        quote_vault.key
+    );
+
+    let token_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Token Program key: {}",
+        token_program.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 6: System Program key: {}",
+        system_program.key
+    );
+
+    // Verify user is signer
+    if !user_account.is_signer {
+        msg!("User is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify programs
+    if token_program.key != &spl_token::id()
+        || system_program.key != &solana_program::system_program::id()
+    {
+        msg!("Invalid token or system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if amount_in == 0 {
+        msg!("Swap amount must be greater than zero");
+        return Err(UnicornFactoryError::InvalidAmount.into());
+    }
+
+    let mut pool = load_pool(program_id, pool_account, token_vault, quote_vault)?;
+
+    // SPL quote pools settle through the user's quote token account
+    let user_quote_account = if pool.is_native_quote() {
+        user_account
+    } else {
+        let user_quote_account = next_account_info(account_info_iter)?;
+        msg!(
+            "Processing account 7: User Quote Account key: {}",
+            user_quote_account.key
+        );
+        user_quote_account
+    };
+
+    let (reserve_in, reserve_out) = if quote_to_token {
+        (pool.quote_reserve, pool.token_reserve)
+    } else {
+        (pool.token_reserve, pool.quote_reserve)
+    };
+
+    let amount_out = swap_output(&pool, amount_in, reserve_in, reserve_out);
+    if amount_out == 0 || amount_out >= reserve_out {
+        msg!(
+            "Insufficient pool liquidity. Out: {}, Reserve: {}",
+            amount_out,
+            reserve_out
+        );
+        return Err(UnicornFactoryError::InsufficientLiquidity.into());
+    }
+
+    if amount_out < min_amount_out {
+        msg!(
+            "Swap output below minimum. Out: {}, Minimum: {}",
+            amount_out,
+            min_amount_out
+        );
+        return Err(UnicornFactoryError::SlippageExceeded.into());
+    }
+
+    if quote_to_token {
+        deposit_pool_quote(
+            &pool,
+            user_account,
+            user_quote_account,
+            quote_vault,
+            amount_in,
+        )?;
+        withdraw_pool_token(
+            pool_account,
+            &pool,
+            token_vault,
+            user_token_account,
+            amount_out,
+        )?;
+
+        pool.quote_reserve = pool
+            .quote_reserve
+            .checked_add(amount_in)
+            .ok_or(UnicornFactoryError::Overflow)?;
+        pool.token_reserve -= amount_out;
+    } else {
+        deposit_pool_token(user_account, user_token_account, token_vault, amount_in)?;
+        withdraw_pool_quote(
+            pool_account,
+            &pool,
+            quote_vault,
+            user_quote_account,
+            amount_out,
+        )?;
+
+        pool.token_reserve = pool
+            .token_reserve
+            .checked_add(amount_in)
+            .ok_or(UnicornFactoryError::Overflow)?;
+        pool.quote_reserve -= amount_out;
+    }
+
+    let mut pool_data = pool_account.data.borrow_mut();
+    pool.pack(&mut pool_data);
+    drop(pool_data);
+
+    msg!(
+        "Swapped {} in for {} out. Reserves: {} tokens, {} quote",
+        amount_in,
+        amount_out,
+        pool.token_reserve,
+        pool.quote_reserve
+    );
+    Ok(())
+}
+
+// Accounts shared by add and remove liquidity
+struct LiquidityAccounts<'a, 'b> {
+    pool: &'a AccountInfo<'b>,
+    user: &'a AccountInfo<'b>,
+    user_token: &'a AccountInfo<'b>,
+    token_vault: &'a AccountInfo<'b>,
+    quote_vault: &'a AccountInfo<'b>,
+    lp_mint: &'a AccountInfo<'b>,
+    user_lp: &'a AccountInfo<'b>,
+    user_quote: &'a AccountInfo<'b>,
+}
+
+fn liquidity_accounts<'a, 'b>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<(LiquidityAccounts<'a, 'b>, Pool), ProgramError> {
+    let account_info_iter = &mut accounts.iter();
+
+    let pool_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Pool Account key: {}",
+        pool_account.key
+    );
+
+    let user_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: User Account key: {}",
+        user_account.key
+    );
+
+    let user_token_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: User Token Account key: {}",
+        user_token_account.key
+    );
+
+    let token_vault = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Pool Token Vault key: {}",
+        token_vault.key
+    );
+
+    let quote_vault = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Pool Quote Vault key: {}",
+        quote_vault.key
+    );
+
+    let lp_mint = next_account_info(account_info_iter)?;
+    msg!("Processing account 5: LP Mint key: {}", lp_mint.key);
+
+    let user_lp_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 6: User LP Account key: {}",
+        user_lp_account.key
+    );
+
+    let token_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 7: Token Program key: {}",
+        token_program.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 8: System Program key: {}",
+        system_program.key
+    );
+
+    // Verify user is signer
+    if !user_account.is_signer {
+        msg!("User is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify programs
+    if token_program.key != &spl_token::id()
+        || system_program.key != &solana_program::system_program::id()
+    {
+        msg!("Invalid token or system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let pool = load_pool(program_id, pool_account, token_vault, quote_vault)?;
+    verify_pool_pda(
+        program_id,
+        pool_account,
+        b"lp_mint",
+        pool.lp_mint_bump,
+        lp_mint,
+    )?;
+
+    let user_quote_account = if pool.is_native_quote() {
+        user_account
+    } else {
+        let user_quote_account = next_account_info(account_info_iter)?;
+        msg!(
+            "Processing account 9: User Quote Account key: {}",
+            user_quote_account.key
+        );
+        user_quote_account
+    };
+
+    Ok((
+        LiquidityAccounts {
+            pool: pool_account,
+            user: user_account,
+            user_token: user_token_account,
+            token_vault,
+            quote_vault,
+            lp_mint,
+            user_lp: user_lp_account,
+            user_quote: user_quote_account,
+        },
+        pool,
+    ))
+}
+
+// Add liquidity instruction processor
+//
+// The quote side is sized from the current pool ratio and may not exceed
+// `max_quote_amount`.
+pub fn process_add_liquidity(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    token_amount: u64,
+    max_quote_amount: u64,
+    min_lp_amount: u64,
+) -> ProgramResult {
+    msg!("Starting add liquidity");
+    let (accs, mut pool) = liquidity_accounts(program_id, accounts)?;
+
+    if token_amount == 0 {
+        msg!("Liquidity amount must be greater than zero");
+        return Err(UnicornFactoryError::InvalidAmount.into());
+    }
+
+    if pool.token_reserve == 0 || pool.lp_supply == 0 {
+        msg!("Pool has no liquidity to price against");
+        return Err(UnicornFactoryError::InsufficientLiquidity.into());
+    }
+
+    // Round the quote side up so existing providers are never diluted
+    let quote_amount = (token_amount as u128 * pool.quote_reserve as u128)
+        .div_ceil(pool.token_reserve as u128) as u64;
+    if quote_amount > max_quote_amount {
+        msg!(
+            "Required quote exceeds maximum. Required: {}, Maximum: {}",
+            quote_amount,
+            max_quote_amount
+        );
+        return Err(UnicornFactoryError::SlippageExceeded.into());
+    }
+
+    let lp_amount =
+        (token_amount as u128 * pool.lp_supply as u128 / pool.token_reserve as u128) as u64;
+    if lp_amount == 0 || lp_amount < min_lp_amount {
+        msg!(
+            "LP output below minimum. Out: {}, Minimum: {}",
+            lp_amount,
+            min_lp_amount
+        );
+        return Err(UnicornFactoryError::SlippageExceeded.into());
+    }
+
+    deposit_pool_token(accs.user, accs.user_token, accs.token_vault, token_amount)?;
+    deposit_pool_quote(
+        &pool,
+        accs.user,
+        accs.user_quote,
+        accs.quote_vault,
+        quote_amount,
+    )?;
+
+    // Mint LP shares to the provider
+    let pool_seeds = &[b"pool".as_ref(), pool.project.as_ref(), &[pool.bump]];
+    invoke_signed(
+        &token_instruction::mint_to(
+            &spl_token::id(),
+            accs.lp_mint.key,
+            accs.user_lp.key,
+            accs.pool.key,
+            &[],
+            lp_amount,
+        )?,
+        &[
+            accs.lp_mint.clone(),
+            accs.user_lp.clone(),
+            accs.pool.clone(),
+        ],
+        &[pool_seeds],
+    )?;
+
+    pool.token_reserve = pool
+        .token_reserve
+        .checked_add(token_amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    pool.quote_reserve = pool
+        .quote_reserve
+        .checked_add(quote_amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    pool.lp_supply = pool
+        .lp_supply
+        .checked_add(lp_amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+
+    let mut pool_data = accs.pool.data.borrow_mut();
+    pool.pack(&mut pool_data);
+    drop(pool_data);
+
+    msg!(
+        "Added {} tokens and {} quote for {} LP",
+        token_amount,
+        quote_amount,
+        lp_amount
+    );
+    Ok(())
+}
+
+// Remove liquidity instruction processor
+pub fn process_remove_liquidity(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    lp_amount: u64,
+    min_token_amount: u64,
+    min_quote_amount: u64,
+) -> ProgramResult {
+    msg!("Starting remove liquidity");
+    let (accs, mut pool) = liquidity_accounts(program_id, accounts)?;
+
+    // Locked graduation liquidity is never minted, so only circulating LP can burn
+    let circulating = pool.lp_supply - pool.locked_liquidity;
+    if lp_amount == 0 || lp_amount > circulating {
+        msg!(
+            "Invalid LP amount. Requested: {}, Circulating: {}",
+            lp_amount,
+            circulating
+        );
+        return Err(UnicornFactoryError::InvalidAmount.into());
+    }
+
+    let token_amount =
+        (lp_amount as u128 * pool.token_reserve as u128 / pool.lp_supply as u128) as u64;
+    let quote_amount =
+        (lp_amount as u128 * pool.quote_reserve as u128 / pool.lp_supply as u128) as u64;
+    if token_amount < min_token_amount || quote_amount < min_quote_amount {
+        msg!(
+            "Withdrawal below minimum. Tokens: {}, Quote: {}",
+            token_amount,
+            quote_amount
+        );
+        return Err(UnicornFactoryError::SlippageExceeded.into());
+    }
+
+    // Burn the provider's LP shares
+    invoke(
+        &token_instruction::burn(
+            &spl_token::id(),
+            accs.user_lp.key,
+            accs.lp_mint.key,
+            accs.user.key,
+            &[],
+            lp_amount,
+        )?,
+        &[
+            accs.user_lp.clone(),
+            accs.lp_mint.clone(),
+            accs.user.clone(),
+        ],
+    )?;
+
+    withdraw_pool_token(
+        accs.pool,
+        &pool,
+        accs.token_vault,
+        accs.user_token,
+        token_amount,
+    )?;
+    withdraw_pool_quote(
+        accs.pool,
+        &pool,
+        accs.quote_vault,
+        accs.user_quote,
+        quote_amount,
+    )?;
+
+    pool.token_reserve -= token_amount;
+    pool.quote_reserve -= quote_amount;
+    pool.lp_supply -= lp_amount;
+
+    let mut pool_data = accs.pool.data.borrow_mut();
+    pool.pack(&mut pool_data);
+    drop(pool_data);
+
+    msg!(
+        "Removed {} LP for {} tokens and {} quote",
+        lp_amount,
+        token_amount,
+        quote_amount
+    );
+    Ok(())
+}
@@ -4,6 +4,8 @@ use solana_program::{
     entrypoint::ProgramResult,
     msg,
     program::{invoke, invoke_signed},
+    keccak,
+    log::sol_log_data,
     program_error::ProgramError,
     program_pack::Pack,
     pubkey::Pubkey,
@@ -13,12 +15,79 @@ use solana_program::{
 use spl_token::instruction as token_instruction;
 use std::str::FromStr;
 
+pub mod amm;
+
+use amm::Pool;
+
 // Program entrypoint
 entrypoint!(process_instruction);
 
 // Program ID will be replaced after deployment
 pub const PROGRAM_ID: &str = "E95C9BgCrrt6Sy8MUbBPTVEEQJSR5Hyau2gAiuAdhb6Y";
 
+// Launch options chosen at project initialization
+#[derive(Debug)]
+pub struct ProjectConfig {
+    pub quote_mint: Pubkey,
+    pub total_supply: u64,
+    pub vesting_bps: u16,
+    pub lockup_enabled: bool,
+    pub raise_deadline: i64,
+    pub graduation_bps: u16,
+    pub graduation_token_reserve: u64,
+    pub min_contribution: u64,
+    pub max_contribution_per_wallet: u64,
+    pub launch_mode: LaunchMode,
+    pub auction_start_price: u64,
+    pub auction_floor_price: u64,
+    pub auction_start_time: i64,
+    pub auction_end_time: i64,
+    pub auction_supply: u64,
+    pub governance: GovernanceConfig,
+}
+
+// Timelock, veto, quorum, ballot and proposer rules applied to proposals. Zero
+// disables the delay, the expiry window, the veto, the quorum, secret ballots
+// and holder proposals; a default guardian disables cancelling.
+#[derive(Debug, Clone, Copy)]
+pub struct GovernanceConfig {
+    pub execution_delay: i64,
+    pub execution_window: i64,
+    pub guardian: Pubkey,
+    pub veto_threshold_bps: u16,
+    pub quorum_bps: u16,
+    pub reveal_period: i64,
+    pub proposal_threshold: u64,
+    pub proposal_deposit: u64,
+}
+
+impl GovernanceConfig {
+    // Secret-ballot proposals take reveals for `reveal_period` after voting ends
+    pub fn reveal_end(&self, voting_end: i64) -> i64 {
+        if self.reveal_period == 0 {
+            0
+        } else {
+            voting_end + self.reveal_period
+        }
+    }
+
+    // When a proposal whose voting ends at `voting_end` has its final result
+    pub fn tally_end(&self, voting_end: i64) -> i64 {
+        std::cmp::max(voting_end, self.reveal_end(voting_end))
+    }
+}
+
+// Terms of a funding round appended to a project
+#[derive(Debug)]
+pub struct FundingRoundParams {
+    pub goal: u64,
+    pub pricing_mode: PricingMode,
+    pub price: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub merkle_root: [u8; 32],
+}
+
 // Instruction enum
 #[derive(Debug)]
 pub enum UnicornFactoryInstruction {
@@ -26,9 +95,11 @@ pub enum UnicornFactoryInstruction {
         name: String,
         symbol: String,
         funding_goal: u64,
+        config: ProjectConfig,
     },
     Contribute {
         amount: u64,
+        proof: Vec<[u8; 32]>,
     },
     BuyTokens {
         amount: u64,
@@ -43,7 +114,7 @@ pub enum UnicornFactoryInstruction {
     },
     Vote {
         proposal_id: u64,
-        vote: bool,
+        choice: u8,
     },
     ReleaseFunds {
         proposal_id: u64,
@@ -52,10 +123,131 @@ pub enum UnicornFactoryInstruction {
         title: String,
         description: String,
         amount: u64,
+        deadline: i64,
+        tranche_count: u8,
+        tranche_interval: i64,
     },
     CompleteMilestone {
         milestone_id: u8,
     },
+    CreateVesting {
+        total_amount: u64,
+        start_time: i64,
+        cliff_time: i64,
+        end_time: i64,
+        revocable: bool,
+    },
+    ClaimVested,
+    RevokeVesting,
+    FinalizeRaise,
+    ClaimTokens,
+    Refund,
+    Graduate,
+    Swap {
+        amount_in: u64,
+        min_amount_out: u64,
+        quote_to_token: bool,
+    },
+    AddLiquidity {
+        token_amount: u64,
+        max_quote_amount: u64,
+        min_lp_amount: u64,
+    },
+    RemoveLiquidity {
+        lp_amount: u64,
+        min_token_amount: u64,
+        min_quote_amount: u64,
+    },
+    ConfigureSaleRound {
+        start_time: i64,
+        end_time: i64,
+        price: u64,
+        per_wallet_cap: u64,
+        merkle_root: [u8; 32],
+    },
+    AddFundingRound {
+        params: FundingRoundParams,
+    },
+    SettleBid,
+    Commit {
+        amount: u64,
+    },
+    Settle,
+    ClaimCommitment,
+    CreateCouncil {
+        threshold: u8,
+        members: Vec<Pubkey>,
+        treasury: Pubkey,
+    },
+    TransferAuthority {
+        new_authority: Pubkey,
+    },
+    AcceptAuthority,
+    CancelProposal {
+        proposal_id: u64,
+    },
+    VetoVote {
+        proposal_id: u64,
+    },
+    Delegate {
+        delegate: Pubkey,
+    },
+    Undelegate,
+    ChangeVote {
+        proposal_id: u64,
+        choice: u8,
+    },
+    WithdrawVote {
+        proposal_id: u64,
+    },
+    CreateChoiceProposal {
+        title: String,
+        description: String,
+        options: Vec<String>,
+    },
+    Execute {
+        proposal_id: u64,
+    },
+    CommitVote {
+        proposal_id: u64,
+        commitment: [u8; 32],
+    },
+    RevealVote {
+        proposal_id: u64,
+        choice: u8,
+        salt: [u8; 32],
+    },
+    CreateActionProposal {
+        title: String,
+        description: String,
+        action: ProposalAction,
+    },
+    SettleDeposit {
+        proposal_id: u64,
+    },
+    RedeemTreasury {
+        amount: u64,
+    },
+    CheckMilestoneDeadline {
+        milestone_id: u8,
+    },
+    SubmitMilestoneEvidence {
+        milestone_id: u8,
+        content_hash: [u8; 32],
+        uri: String,
+    },
+    MigrateProject,
+    UpdateCouncil {
+        threshold: u8,
+        members: Vec<Pubkey>,
+        treasury: Pubkey,
+    },
+    DepositEscrow {
+        amount: u64,
+    },
+    WithdrawEscrow {
+        amount: u64,
+    },
 }
 
 impl UnicornFactoryInstruction {
@@ -76,7 +268,9 @@ impl UnicornFactoryInstruction {
                 let name_len = u32::from_le_bytes(rest[0..4].try_into().unwrap()) as usize;
                 let symbol_len = u32::from_le_bytes(rest[4..8].try_into().unwrap()) as usize;
 
-                if rest.len() < 8 + name_len + symbol_len + 8 {
+                let config_len = 32 + 8 + 2 + 1 + 8 + 2 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8
+                    + 32 + 2 + 2 + 8 + 8 + 8;
+                if rest.len() < 8 + name_len + symbol_len + 8 + config_len {
                     return Err(ProgramError::InvalidInstructionData);
                 }
 
@@ -85,16 +279,107 @@ impl UnicornFactoryInstruction {
                 let symbol =
                     String::from_utf8(rest[8 + name_len..8 + name_len + symbol_len].to_vec())
                         .map_err(|_| ProgramError::InvalidInstructionData)?;
-                let funding_goal = u64::from_le_bytes(
-                    rest[8 + name_len + symbol_len..8 + name_len + symbol_len + 8]
-                        .try_into()
-                        .unwrap(),
-                );
+
+                let mut offset = 8 + name_len + symbol_len;
+                let funding_goal = u64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let quote_mint = Pubkey::try_from(&rest[offset..offset + 32])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                offset += 32;
+                let total_supply =
+                    u64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let vesting_bps = u16::from_le_bytes(rest[offset..offset + 2].try_into().unwrap());
+                offset += 2;
+                let lockup_enabled = rest[offset] != 0;
+                offset += 1;
+                let raise_deadline =
+                    i64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let graduation_bps =
+                    u16::from_le_bytes(rest[offset..offset + 2].try_into().unwrap());
+                offset += 2;
+                let graduation_token_reserve =
+                    u64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let min_contribution =
+                    u64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let max_contribution_per_wallet =
+                    u64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let launch_mode = LaunchMode::from_u8(rest[offset])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                offset += 1;
+                let auction_start_price =
+                    u64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let auction_floor_price =
+                    u64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let auction_start_time =
+                    i64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let auction_end_time =
+                    i64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let auction_supply =
+                    u64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let execution_delay =
+                    i64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let execution_window =
+                    i64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let guardian = Pubkey::try_from(&rest[offset..offset + 32])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                offset += 32;
+                let veto_threshold_bps =
+                    u16::from_le_bytes(rest[offset..offset + 2].try_into().unwrap());
+                offset += 2;
+                let quorum_bps = u16::from_le_bytes(rest[offset..offset + 2].try_into().unwrap());
+                offset += 2;
+                let reveal_period =
+                    i64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let proposal_threshold =
+                    u64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
+                offset += 8;
+                let proposal_deposit =
+                    u64::from_le_bytes(rest[offset..offset + 8].try_into().unwrap());
 
                 Ok(UnicornFactoryInstruction::InitializeProject {
                     name,
                     symbol,
                     funding_goal,
+                    config: ProjectConfig {
+                        quote_mint,
+                        total_supply,
+                        vesting_bps,
+                        lockup_enabled,
+                        raise_deadline,
+                        graduation_bps,
+                        graduation_token_reserve,
+                        min_contribution,
+                        max_contribution_per_wallet,
+                        launch_mode,
+                        auction_start_price,
+                        auction_floor_price,
+                        auction_start_time,
+                        auction_end_time,
+                        auction_supply,
+                        governance: GovernanceConfig {
+                            execution_delay,
+                            execution_window,
+                            guardian,
+                            veto_threshold_bps,
+                            quorum_bps,
+                            reveal_period,
+                            proposal_threshold,
+                            proposal_deposit,
+                        },
+                    },
                 })
             }
             1 => {
@@ -102,7 +387,19 @@ impl UnicornFactoryInstruction {
                     return Err(ProgramError::InvalidInstructionData);
                 }
                 let amount = u64::from_le_bytes(rest[0..8].try_into().unwrap());
-                Ok(UnicornFactoryInstruction::Contribute { amount })
+
+                // Allowlist proof for presale rounds, omitted for public contributions
+                let mut proof = Vec::new();
+                if rest.len() >= 12 {
+                    let proof_len = u32::from_le_bytes(rest[8..12].try_into().unwrap()) as usize;
+                    if rest.len() < 12 + proof_len * 32 {
+                        return Err(ProgramError::InvalidInstructionData);
+                    }
+                    for node in rest[12..12 + proof_len * 32].chunks_exact(32) {
+                        proof.push(node.try_into().unwrap());
+                    }
+                }
+                Ok(UnicornFactoryInstruction::Contribute { amount, proof })
             }
             2 => {
                 if rest.len() < 8 {
@@ -147,8 +444,11 @@ impl UnicornFactoryInstruction {
                     return Err(ProgramError::InvalidInstructionData);
                 }
                 let proposal_id = u64::from_le_bytes(rest[0..8].try_into().unwrap());
-                let vote = rest[8] != 0;
-                Ok(UnicornFactoryInstruction::Vote { proposal_id, vote })
+                let choice = rest[8];
+                Ok(UnicornFactoryInstruction::Vote {
+                    proposal_id,
+                    choice,
+                })
             }
             6 => {
                 if rest.len() < 8 {
@@ -179,10 +479,31 @@ impl UnicornFactoryInstruction {
                         .unwrap(),
                 );
 
+                // Optional due date, omitted for milestones without one
+                let deadline_offset = 32 + title_len + description_len + 8;
+                let deadline = match rest.get(deadline_offset..deadline_offset + 8) {
+                    Some(bytes) => i64::from_le_bytes(bytes.try_into().unwrap()),
+                    None => 0,
+                };
+
+                // Optional tranche schedule after the deadline, a single payout if omitted
+                let tranche_offset = deadline_offset + 8;
+                let (tranche_count, tranche_interval) =
+                    match rest.get(tranche_offset..tranche_offset + 9) {
+                        Some(bytes) => (
+                            bytes[0],
+                            i64::from_le_bytes(bytes[1..9].try_into().unwrap()),
+                        ),
+                        None => (1, 0),
+                    };
+
                 Ok(UnicornFactoryInstruction::AddMilestone {
                     title,
                     description,
                     amount,
+                    deadline,
+                    tranche_count,
+                    tranche_interval,
                 })
             }
             8 => {
@@ -192,11 +513,416 @@ impl UnicornFactoryInstruction {
                 let milestone_id = rest[0];
                 Ok(UnicornFactoryInstruction::CompleteMilestone { milestone_id })
             }
+            9 => {
+                if rest.len() < 33 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let total_amount = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                let start_time = i64::from_le_bytes(rest[8..16].try_into().unwrap());
+                let cliff_time = i64::from_le_bytes(rest[16..24].try_into().unwrap());
+                let end_time = i64::from_le_bytes(rest[24..32].try_into().unwrap());
+                let revocable = rest[32] != 0;
+                Ok(UnicornFactoryInstruction::CreateVesting {
+                    total_amount,
+                    start_time,
+                    cliff_time,
+                    end_time,
+                    revocable,
+                })
+            }
+            10 => Ok(UnicornFactoryInstruction::ClaimVested),
+            11 => Ok(UnicornFactoryInstruction::RevokeVesting),
+            12 => Ok(UnicornFactoryInstruction::FinalizeRaise),
+            13 => Ok(UnicornFactoryInstruction::ClaimTokens),
+            14 => Ok(UnicornFactoryInstruction::Refund),
+            15 => Ok(UnicornFactoryInstruction::Graduate),
+            16 => {
+                if rest.len() < 17 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let amount_in = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                let min_amount_out = u64::from_le_bytes(rest[8..16].try_into().unwrap());
+                let quote_to_token = rest[16] != 0;
+                Ok(UnicornFactoryInstruction::Swap {
+                    amount_in,
+                    min_amount_out,
+                    quote_to_token,
+                })
+            }
+            17 => {
+                if rest.len() < 24 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let token_amount = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                let max_quote_amount = u64::from_le_bytes(rest[8..16].try_into().unwrap());
+                let min_lp_amount = u64::from_le_bytes(rest[16..24].try_into().unwrap());
+                Ok(UnicornFactoryInstruction::AddLiquidity {
+                    token_amount,
+                    max_quote_amount,
+                    min_lp_amount,
+                })
+            }
+            18 => {
+                if rest.len() < 24 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let lp_amount = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                let min_token_amount = u64::from_le_bytes(rest[8..16].try_into().unwrap());
+                let min_quote_amount = u64::from_le_bytes(rest[16..24].try_into().unwrap());
+                Ok(UnicornFactoryInstruction::RemoveLiquidity {
+                    lp_amount,
+                    min_token_amount,
+                    min_quote_amount,
+                })
+            }
+            19 => {
+                if rest.len() < 64 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let start_time = i64::from_le_bytes(rest[0..8].try_into().unwrap());
+                let end_time = i64::from_le_bytes(rest[8..16].try_into().unwrap());
+                let price = u64::from_le_bytes(rest[16..24].try_into().unwrap());
+                let per_wallet_cap = u64::from_le_bytes(rest[24..32].try_into().unwrap());
+                let merkle_root: [u8; 32] = rest[32..64].try_into().unwrap();
+                Ok(UnicornFactoryInstruction::ConfigureSaleRound {
+                    start_time,
+                    end_time,
+                    price,
+                    per_wallet_cap,
+                    merkle_root,
+                })
+            }
+            20 => {
+                if rest.len() < 65 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let goal = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                let pricing_mode = PricingMode::from_u8(rest[8])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                let price = u64::from_le_bytes(rest[9..17].try_into().unwrap());
+                let start_time = i64::from_le_bytes(rest[17..25].try_into().unwrap());
+                let end_time = i64::from_le_bytes(rest[25..33].try_into().unwrap());
+                let merkle_root: [u8; 32] = rest[33..65].try_into().unwrap();
+                Ok(UnicornFactoryInstruction::AddFundingRound {
+                    params: FundingRoundParams {
+                        goal,
+                        pricing_mode,
+                        price,
+                        start_time,
+                        end_time,
+                        merkle_root,
+                    },
+                })
+            }
+            21 => Ok(UnicornFactoryInstruction::SettleBid),
+            22 => {
+                if rest.len() < 8 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let amount = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                Ok(UnicornFactoryInstruction::Commit { amount })
+            }
+            23 => Ok(UnicornFactoryInstruction::Settle),
+            24 => Ok(UnicornFactoryInstruction::ClaimCommitment),
+            25 | 44 => {
+                if rest.len() < 34 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let threshold = rest[0];
+                let member_count = rest[1] as usize;
+                if rest.len() < 34 + member_count * 32 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let treasury = Pubkey::try_from(&rest[2..34])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                let mut members = Vec::with_capacity(member_count);
+                for member in rest[34..34 + member_count * 32].chunks_exact(32) {
+                    members.push(
+                        Pubkey::try_from(member)
+                            .map_err(|_| ProgramError::InvalidInstructionData)?,
+                    );
+                }
+                if *tag == 44 {
+                    return Ok(UnicornFactoryInstruction::UpdateCouncil {
+                        threshold,
+                        members,
+                        treasury,
+                    });
+                }
+                Ok(UnicornFactoryInstruction::CreateCouncil {
+                    threshold,
+                    members,
+                    treasury,
+                })
+            }
+            26 => {
+                if rest.len() < 32 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let new_authority = Pubkey::try_from(&rest[0..32])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Ok(UnicornFactoryInstruction::TransferAuthority { new_authority })
+            }
+            27 => Ok(UnicornFactoryInstruction::AcceptAuthority),
+            28 => {
+                if rest.len() < 8 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let proposal_id = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                Ok(UnicornFactoryInstruction::CancelProposal { proposal_id })
+            }
+            29 => {
+                if rest.len() < 8 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let proposal_id = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                Ok(UnicornFactoryInstruction::VetoVote { proposal_id })
+            }
+            30 => {
+                if rest.len() < 32 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let delegate = Pubkey::try_from(&rest[0..32])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Ok(UnicornFactoryInstruction::Delegate { delegate })
+            }
+            31 => Ok(UnicornFactoryInstruction::Undelegate),
+            32 => {
+                if rest.len() < 9 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let proposal_id = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                let choice = rest[8];
+                Ok(UnicornFactoryInstruction::ChangeVote {
+                    proposal_id,
+                    choice,
+                })
+            }
+            33 => {
+                if rest.len() < 8 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let proposal_id = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                Ok(UnicornFactoryInstruction::WithdrawVote { proposal_id })
+            }
+            34 => {
+                if rest.len() < 8 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let title_len = u32::from_le_bytes(rest[0..4].try_into().unwrap()) as usize;
+                let description_len = u32::from_le_bytes(rest[4..8].try_into().unwrap()) as usize;
+
+                let mut offset = 8 + title_len + description_len;
+                if rest.len() < offset + 1 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                let title = String::from_utf8(rest[8..8 + title_len].to_vec())
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                let description =
+                    String::from_utf8(rest[8 + title_len..offset].to_vec())
+                        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                let option_count = rest[offset] as usize;
+                offset += 1;
+                let mut options = Vec::with_capacity(option_count);
+                for _ in 0..option_count {
+                    if rest.len() < offset + 4 {
+                        return Err(ProgramError::InvalidInstructionData);
+                    }
+                    let label_len =
+                        u32::from_le_bytes(rest[offset..offset + 4].try_into().unwrap()) as usize;
+                    offset += 4;
+                    if rest.len() < offset + label_len {
+                        return Err(ProgramError::InvalidInstructionData);
+                    }
+                    options.push(
+                        String::from_utf8(rest[offset..offset + label_len].to_vec())
+                            .map_err(|_| ProgramError::InvalidInstructionData)?,
+                    );
+                    offset += label_len;
+                }
+
+                Ok(UnicornFactoryInstruction::CreateChoiceProposal {
+                    title,
+                    description,
+                    options,
+                })
+            }
+            35 => {
+                if rest.len() < 8 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let proposal_id = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                Ok(UnicornFactoryInstruction::Execute { proposal_id })
+            }
+            36 => {
+                if rest.len() < 40 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let proposal_id = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                let commitment = rest[8..40].try_into().unwrap();
+                Ok(UnicornFactoryInstruction::CommitVote {
+                    proposal_id,
+                    commitment,
+                })
+            }
+            37 => {
+                if rest.len() < 41 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let proposal_id = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                let choice = rest[8];
+                let salt = rest[9..41].try_into().unwrap();
+                Ok(UnicornFactoryInstruction::RevealVote {
+                    proposal_id,
+                    choice,
+                    salt,
+                })
+            }
+            38 => {
+                if rest.len() < 8 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let title_len = u32::from_le_bytes(rest[0..4].try_into().unwrap()) as usize;
+                let description_len = u32::from_le_bytes(rest[4..8].try_into().unwrap()) as usize;
+
+                let offset = 8 + title_len + description_len;
+                if rest.len() < offset + ProposalAction::LEN {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                let title = String::from_utf8(rest[8..8 + title_len].to_vec())
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                let description =
+                    String::from_utf8(rest[8 + title_len..offset].to_vec())
+                        .map_err(|_| ProgramError::InvalidInstructionData)?;
+                let action = ProposalAction::unpack(&rest[offset..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(UnicornFactoryInstruction::CreateActionProposal {
+                    title,
+                    description,
+                    action,
+                })
+            }
+            39 => {
+                if rest.len() < 8 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let proposal_id = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                Ok(UnicornFactoryInstruction::SettleDeposit { proposal_id })
+            }
+            40 => {
+                if rest.len() < 8 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let amount = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                Ok(UnicornFactoryInstruction::RedeemTreasury { amount })
+            }
+            41 => {
+                let milestone_id = *rest.first().ok_or(ProgramError::InvalidInstructionData)?;
+                Ok(UnicornFactoryInstruction::CheckMilestoneDeadline { milestone_id })
+            }
+            42 => {
+                if rest.len() < 37 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let milestone_id = rest[0];
+                let content_hash = rest[1..33].try_into().unwrap();
+                let uri_len = u32::from_le_bytes(rest[33..37].try_into().unwrap()) as usize;
+                let uri = rest
+                    .get(37..37 + uri_len)
+                    .ok_or(ProgramError::InvalidInstructionData)?;
+                let uri = String::from_utf8(uri.to_vec())
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Ok(UnicornFactoryInstruction::SubmitMilestoneEvidence {
+                    milestone_id,
+                    content_hash,
+                    uri,
+                })
+            }
+            43 => Ok(UnicornFactoryInstruction::MigrateProject),
+            45 => {
+                if rest.len() < 8 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let amount = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                Ok(UnicornFactoryInstruction::DepositEscrow { amount })
+            }
+            46 => {
+                if rest.len() < 8 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                let amount = u64::from_le_bytes(rest[0..8].try_into().unwrap());
+                Ok(UnicornFactoryInstruction::WithdrawEscrow { amount })
+            }
             _ => Err(ProgramError::InvalidInstructionData),
         }
     }
 }
 
+// Outcome of a project's fundraise
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RaiseState {
+    Open,
+    Succeeded,
+    Failed,
+}
+
+impl RaiseState {
+    pub fn from_u8(value: u8) -> Result<Self, ProgramError> {
+        match value {
+            0 => Ok(RaiseState::Open),
+            1 => Ok(RaiseState::Succeeded),
+            2 => Ok(RaiseState::Failed),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+// How a project sells its tokens during the raise
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LaunchMode {
+    BondingCurve,
+    DutchAuction,
+    BatchAuction,
+}
+
+impl LaunchMode {
+    pub fn from_u8(value: u8) -> Result<Self, ProgramError> {
+        match value {
+            0 => Ok(LaunchMode::BondingCurve),
+            1 => Ok(LaunchMode::DutchAuction),
+            2 => Ok(LaunchMode::BatchAuction),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+// How a funding round prices its tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PricingMode {
+    Fixed,
+    Curve,
+}
+
+impl PricingMode {
+    pub fn from_u8(value: u8) -> Result<Self, ProgramError> {
+        match value {
+            0 => Ok(PricingMode::Fixed),
+            1 => Ok(PricingMode::Curve),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+// Version of the project account layout written by this program. Projects
+// created before the layout was versioned end after `proposal_count`, at
+// LEGACY_PROJECT_LEN bytes, and must go through MigrateProject before use.
+pub const PROJECT_LAYOUT_VERSION: u8 = 1;
+pub const LEGACY_PROJECT_LEN: usize = 132;
+
 // Project account structure
 #[derive(Debug)]
 pub struct Project {
@@ -211,6 +937,53 @@ pub struct Project {
     pub token_mint: Pubkey,
     pub milestone_count: u8,
     pub proposal_count: u8,
+    pub layout_version: u8,
+    pub total_allocated: u64,
+    pub total_released: u64,
+    pub vault_bump: u8,
+    pub quote_mint: Pubkey,
+    pub vesting_reserve: u64,
+    pub vesting_allocated: u64,
+    pub lockup_enabled: bool,
+    pub raise_deadline: i64,
+    pub raise_state: RaiseState,
+    pub graduation_bps: u16,
+    pub graduation_token_reserve: u64,
+    pub is_graduated: bool,
+    pub sale_round_end: i64,
+    pub min_contribution: u64,
+    pub max_contribution_per_wallet: u64,
+    pub round_count: u8,
+    pub current_round: u8,
+    pub launch_mode: LaunchMode,
+    pub auction_start_price: u64,
+    pub auction_floor_price: u64,
+    pub auction_start_time: i64,
+    pub auction_end_time: i64,
+    pub auction_supply: u64,
+    pub auction_tokens_sold: u64,
+    pub clearing_price: u64,
+    pub creator: Pubkey,
+    pub pending_authority: Pubkey,
+    pub governance: GovernanceConfig,
+    pub curve_base_price: u64,
+    pub curve_slope: u64,
+    pub is_paused: bool,
+    pub held_deposits: u64,
+    pub slashed_deposits: u64,
+    pub is_dissolved: bool,
+    pub total_supply: u64,
+    // Contributions recorded and not yet refunded; refunds are paid pro rata
+    // against it
+    pub total_contributed: u64,
+    // Token supply and treasury when the project was dissolved; redemptions
+    // pay out at this fixed rate
+    pub dissolved_supply: u64,
+    pub dissolved_treasury: u64,
+    // Tokens bought into escrow and not yet claimed or settled, and the
+    // auction deposits not yet settled
+    pub unclaimed_tokens: u64,
+    pub unsettled_deposits: u64,
 }
 
 impl Project {
@@ -224,7 +997,55 @@ impl Project {
         1 + // bump
         32 + // token_mint
         1 + // milestone_count
-        1; // proposal_count
+        1 + // proposal_count
+        1 + // layout_version
+        8 + // total_allocated
+        8 + // total_released
+        1 + // vault_bump
+        32 + // quote_mint
+        8 + // vesting_reserve
+        8 + // vesting_allocated
+        1 + // lockup_enabled
+        8 + // raise_deadline
+        1 + // raise_state
+        2 + // graduation_bps
+        8 + // graduation_token_reserve
+        1 + // is_graduated
+        8 + // sale_round_end
+        8 + // min_contribution
+        8 + // max_contribution_per_wallet
+        1 + // round_count
+        1 + // current_round
+        1 + // launch_mode
+        8 + // auction_start_price
+        8 + // auction_floor_price
+        8 + // auction_start_time
+        8 + // auction_end_time
+        8 + // auction_supply
+        8 + // auction_tokens_sold
+        8 + // clearing_price
+        32 + // creator
+        32 + // pending_authority
+        8 + // governance.execution_delay
+        8 + // governance.execution_window
+        32 + // governance.guardian
+        2 + // governance.veto_threshold_bps
+        2 + // governance.quorum_bps
+        8 + // governance.reveal_period
+        8 + // governance.proposal_threshold
+        8 + // governance.proposal_deposit
+        8 + // curve_base_price
+        8 + // curve_slope
+        1 + // is_paused
+        8 + // held_deposits
+        8 + // slashed_deposits
+        1 + // is_dissolved
+        8 + // total_supply
+        8 + // total_contributed
+        8 + // dissolved_supply
+        8 + // dissolved_treasury
+        8 + // unclaimed_tokens
+        8; // unsettled_deposits
 
     pub fn pack(&self, dst: &mut [u8]) {
         let mut offset = 0;
@@ -279,17 +1100,200 @@ impl Project {
 
         // Pack proposal_count
         dst[offset] = self.proposal_count;
-    }
+        offset += 1;
 
-    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
-        let mut offset = 0;
+        // Pack layout_version
+        dst[offset] = self.layout_version;
+        offset += 1;
 
-        // Unpack authority
-        let authority = Pubkey::try_from(&src[offset..offset + 32])
-            .map_err(|_| ProgramError::InvalidAccountData)?;
+        // Pack total_allocated
+        dst[offset..offset + 8].copy_from_slice(&self.total_allocated.to_le_bytes());
+        offset += 8;
+
+        // Pack total_released
+        dst[offset..offset + 8].copy_from_slice(&self.total_released.to_le_bytes());
+        offset += 8;
+
+        // Pack vault_bump
+        dst[offset] = self.vault_bump;
+        offset += 1;
+
+        // Pack quote_mint
+        dst[offset..offset + 32].copy_from_slice(&self.quote_mint.to_bytes());
         offset += 32;
 
-        // Unpack name
+        // Pack vesting_reserve
+        dst[offset..offset + 8].copy_from_slice(&self.vesting_reserve.to_le_bytes());
+        offset += 8;
+
+        // Pack vesting_allocated
+        dst[offset..offset + 8].copy_from_slice(&self.vesting_allocated.to_le_bytes());
+        offset += 8;
+
+        // Pack lockup_enabled
+        dst[offset] = self.lockup_enabled as u8;
+        offset += 1;
+
+        // Pack raise_deadline
+        dst[offset..offset + 8].copy_from_slice(&self.raise_deadline.to_le_bytes());
+        offset += 8;
+
+        // Pack raise_state
+        dst[offset] = self.raise_state as u8;
+        offset += 1;
+
+        // Pack graduation_bps
+        dst[offset..offset + 2].copy_from_slice(&self.graduation_bps.to_le_bytes());
+        offset += 2;
+
+        // Pack graduation_token_reserve
+        dst[offset..offset + 8].copy_from_slice(&self.graduation_token_reserve.to_le_bytes());
+        offset += 8;
+
+        // Pack is_graduated
+        dst[offset] = self.is_graduated as u8;
+        offset += 1;
+
+        // Pack sale_round_end
+        dst[offset..offset + 8].copy_from_slice(&self.sale_round_end.to_le_bytes());
+        offset += 8;
+
+        // Pack min_contribution
+        dst[offset..offset + 8].copy_from_slice(&self.min_contribution.to_le_bytes());
+        offset += 8;
+
+        // Pack max_contribution_per_wallet
+        dst[offset..offset + 8].copy_from_slice(&self.max_contribution_per_wallet.to_le_bytes());
+        offset += 8;
+
+        // Pack round_count
+        dst[offset] = self.round_count;
+        offset += 1;
+
+        // Pack current_round
+        dst[offset] = self.current_round;
+        offset += 1;
+
+        // Pack launch_mode
+        dst[offset] = self.launch_mode as u8;
+        offset += 1;
+
+        // Pack auction_start_price
+        dst[offset..offset + 8].copy_from_slice(&self.auction_start_price.to_le_bytes());
+        offset += 8;
+
+        // Pack auction_floor_price
+        dst[offset..offset + 8].copy_from_slice(&self.auction_floor_price.to_le_bytes());
+        offset += 8;
+
+        // Pack auction_start_time
+        dst[offset..offset + 8].copy_from_slice(&self.auction_start_time.to_le_bytes());
+        offset += 8;
+
+        // Pack auction_end_time
+        dst[offset..offset + 8].copy_from_slice(&self.auction_end_time.to_le_bytes());
+        offset += 8;
+
+        // Pack auction_supply
+        dst[offset..offset + 8].copy_from_slice(&self.auction_supply.to_le_bytes());
+        offset += 8;
+
+        // Pack auction_tokens_sold
+        dst[offset..offset + 8].copy_from_slice(&self.auction_tokens_sold.to_le_bytes());
+        offset += 8;
+
+        // Pack clearing_price
+        dst[offset..offset + 8].copy_from_slice(&self.clearing_price.to_le_bytes());
+        offset += 8;
+
+        // Pack creator
+        dst[offset..offset + 32].copy_from_slice(&self.creator.to_bytes());
+        offset += 32;
+
+        // Pack pending_authority
+        dst[offset..offset + 32].copy_from_slice(&self.pending_authority.to_bytes());
+        offset += 32;
+
+        // Pack governance
+        dst[offset..offset + 8].copy_from_slice(&self.governance.execution_delay.to_le_bytes());
+        offset += 8;
+        dst[offset..offset + 8].copy_from_slice(&self.governance.execution_window.to_le_bytes());
+        offset += 8;
+        dst[offset..offset + 32].copy_from_slice(&self.governance.guardian.to_bytes());
+        offset += 32;
+        dst[offset..offset + 2].copy_from_slice(&self.governance.veto_threshold_bps.to_le_bytes());
+        offset += 2;
+        dst[offset..offset + 2].copy_from_slice(&self.governance.quorum_bps.to_le_bytes());
+        offset += 2;
+        dst[offset..offset + 8].copy_from_slice(&self.governance.reveal_period.to_le_bytes());
+        offset += 8;
+        dst[offset..offset + 8].copy_from_slice(&self.governance.proposal_threshold.to_le_bytes());
+        offset += 8;
+        dst[offset..offset + 8].copy_from_slice(&self.governance.proposal_deposit.to_le_bytes());
+        offset += 8;
+
+        // Pack curve_base_price
+        dst[offset..offset + 8].copy_from_slice(&self.curve_base_price.to_le_bytes());
+        offset += 8;
+
+        // Pack curve_slope
+        dst[offset..offset + 8].copy_from_slice(&self.curve_slope.to_le_bytes());
+        offset += 8;
+
+        // Pack is_paused
+        dst[offset] = self.is_paused as u8;
+        offset += 1;
+
+        // Pack held_deposits
+        dst[offset..offset + 8].copy_from_slice(&self.held_deposits.to_le_bytes());
+        offset += 8;
+
+        // Pack slashed_deposits
+        dst[offset..offset + 8].copy_from_slice(&self.slashed_deposits.to_le_bytes());
+        offset += 8;
+
+        // Pack is_dissolved
+        dst[offset] = self.is_dissolved as u8;
+        offset += 1;
+
+        // Pack total_supply
+        dst[offset..offset + 8].copy_from_slice(&self.total_supply.to_le_bytes());
+        offset += 8;
+
+        // Pack total_contributed
+        dst[offset..offset + 8].copy_from_slice(&self.total_contributed.to_le_bytes());
+        offset += 8;
+
+        // Pack dissolved_supply
+        dst[offset..offset + 8].copy_from_slice(&self.dissolved_supply.to_le_bytes());
+        offset += 8;
+
+        // Pack dissolved_treasury
+        dst[offset..offset + 8].copy_from_slice(&self.dissolved_treasury.to_le_bytes());
+        offset += 8;
+
+        // Pack unclaimed_tokens
+        dst[offset..offset + 8].copy_from_slice(&self.unclaimed_tokens.to_le_bytes());
+        offset += 8;
+
+        // Pack unsettled_deposits
+        dst[offset..offset + 8].copy_from_slice(&self.unsettled_deposits.to_le_bytes());
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        // Accounts on an older layout are rejected until they are migrated
+        if src.len() < Project::LEN || src[LEGACY_PROJECT_LEN] != PROJECT_LAYOUT_VERSION {
+            return Err(UnicornFactoryError::OutdatedProjectLayout.into());
+        }
+
+        let mut offset = 0;
+
+        // Unpack authority
+        let authority = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack name
         let name = String::from_utf8(src[offset..offset + 32].to_vec())
             .map_err(|_| ProgramError::InvalidAccountData)?
             .trim_end_matches('\0')
@@ -334,6 +1338,190 @@ impl Project {
 
         // Unpack proposal_count
         let proposal_count = src[offset];
+        offset += 1;
+
+        // Unpack layout_version
+        let layout_version = src[offset];
+        offset += 1;
+
+        // Unpack total_allocated
+        let total_allocated = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack total_released
+        let total_released = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack vault_bump
+        let vault_bump = src[offset];
+        offset += 1;
+
+        // Unpack quote_mint
+        let quote_mint = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack vesting_reserve
+        let vesting_reserve = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack vesting_allocated
+        let vesting_allocated = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack lockup_enabled
+        let lockup_enabled = src[offset] != 0;
+        offset += 1;
+
+        // Unpack raise_deadline
+        let raise_deadline = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack raise_state
+        let raise_state = RaiseState::from_u8(src[offset])?;
+        offset += 1;
+
+        // Unpack graduation_bps
+        let graduation_bps = u16::from_le_bytes(src[offset..offset + 2].try_into().unwrap());
+        offset += 2;
+
+        // Unpack graduation_token_reserve
+        let graduation_token_reserve =
+            u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack is_graduated
+        let is_graduated = src[offset] != 0;
+        offset += 1;
+
+        // Unpack sale_round_end
+        let sale_round_end = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack min_contribution
+        let min_contribution = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack max_contribution_per_wallet
+        let max_contribution_per_wallet =
+            u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack round_count
+        let round_count = src[offset];
+        offset += 1;
+
+        // Unpack current_round
+        let current_round = src[offset];
+        offset += 1;
+
+        // Unpack launch_mode
+        let launch_mode = LaunchMode::from_u8(src[offset])?;
+        offset += 1;
+
+        // Unpack auction_start_price
+        let auction_start_price = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack auction_floor_price
+        let auction_floor_price = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack auction_start_time
+        let auction_start_time = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack auction_end_time
+        let auction_end_time = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack auction_supply
+        let auction_supply = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack auction_tokens_sold
+        let auction_tokens_sold = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack clearing_price
+        let clearing_price = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack creator
+        let creator = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack pending_authority
+        let pending_authority = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack governance
+        let execution_delay = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+        let execution_window = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+        let guardian = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+        let veto_threshold_bps = u16::from_le_bytes(src[offset..offset + 2].try_into().unwrap());
+        offset += 2;
+        let quorum_bps = u16::from_le_bytes(src[offset..offset + 2].try_into().unwrap());
+        offset += 2;
+        let reveal_period = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+        let proposal_threshold = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+        let proposal_deposit = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack curve_base_price
+        let curve_base_price = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack curve_slope
+        let curve_slope = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack is_paused
+        let is_paused = src[offset] != 0;
+        offset += 1;
+
+        // Unpack held_deposits
+        let held_deposits = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack slashed_deposits
+        let slashed_deposits = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack is_dissolved
+        let is_dissolved = src[offset] != 0;
+        offset += 1;
+
+        // Unpack total_supply
+        let total_supply = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack total_contributed
+        let total_contributed = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack dissolved_supply
+        let dissolved_supply = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack dissolved_treasury
+        let dissolved_treasury = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack unclaimed_tokens
+        let unclaimed_tokens = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack unsettled_deposits
+        let unsettled_deposits = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
 
         Ok(Project {
             authority,
@@ -347,11 +1535,266 @@ impl Project {
             token_mint,
             milestone_count,
             proposal_count,
+            layout_version,
+            total_allocated,
+            total_released,
+            vault_bump,
+            quote_mint,
+            vesting_reserve,
+            vesting_allocated,
+            lockup_enabled,
+            raise_deadline,
+            raise_state,
+            graduation_bps,
+            graduation_token_reserve,
+            is_graduated,
+            sale_round_end,
+            min_contribution,
+            max_contribution_per_wallet,
+            round_count,
+            current_round,
+            launch_mode,
+            auction_start_price,
+            auction_floor_price,
+            auction_start_time,
+            auction_end_time,
+            auction_supply,
+            auction_tokens_sold,
+            clearing_price,
+            creator,
+            pending_authority,
+            governance: GovernanceConfig {
+                execution_delay,
+                execution_window,
+                guardian,
+                veto_threshold_bps,
+                quorum_bps,
+                reveal_period,
+                proposal_threshold,
+                proposal_deposit,
+            },
+            curve_base_price,
+            curve_slope,
+            is_paused,
+            held_deposits,
+            slashed_deposits,
+            is_dissolved,
+            total_supply,
+            total_contributed,
+            dissolved_supply,
+            dissolved_treasury,
+            unclaimed_tokens,
+            unsettled_deposits,
         })
     }
+
+    // Projects raise native SOL unless a quote mint was chosen at initialization
+    pub fn is_native_quote(&self) -> bool {
+        self.quote_mint == Pubkey::default()
+    }
+
+    // Public bonding-curve trading waits until any presale round has ended
+    pub fn in_sale_round(&self, now: i64) -> bool {
+        self.sale_round_end != 0 && now <= self.sale_round_end
+    }
+
+    // Tokens are held back until the raise settles under lockup and in auctions
+    pub fn escrows_tokens(&self) -> bool {
+        self.lockup_enabled || self.launch_mode != LaunchMode::BondingCurve
+    }
+
+    // Dutch auction price at `now`, decaying linearly from the start price to
+    // the floor over the auction window
+    pub fn auction_price(&self, now: i64) -> u64 {
+        if now <= self.auction_start_time {
+            return self.auction_start_price;
+        }
+        if now >= self.auction_end_time {
+            return self.auction_floor_price;
+        }
+
+        let elapsed = (now - self.auction_start_time) as u128;
+        let duration = (self.auction_end_time - self.auction_start_time) as u128;
+        let decay = (self.auction_start_price - self.auction_floor_price) as u128;
+        self.auction_start_price - (decay * elapsed / duration) as u64
+    }
+
+    // Closes an auction at `clearing_price`. Every bidder pays the clearing
+    // price, so only the proceeds count towards the funding goal and anything
+    // above them is owed back to bidders. Rounding each bid down to whole
+    // tokens can leave the proceeds collected short of the tokens sold at the
+    // clearing price, so a successful auction raises only what each bidder
+    // is charged as they settle.
+    pub fn close_auction(&mut self, clearing_price: u64) -> ProgramResult {
+        self.clearing_price = clearing_price;
+        self.is_active = false;
+
+        let proceeds = self.auction_tokens_sold as u128 * clearing_price as u128;
+        if proceeds >= self.funding_goal as u128 {
+            self.total_raised = 0;
+            self.raise_state = RaiseState::Succeeded;
+        } else {
+            self.raise_state = RaiseState::Failed;
+        }
+        Ok(())
+    }
+
+    // Whether a Dutch auction's deposits are each filled at the clearing
+    // price, rather than at the quantity bid when that would oversell
+    pub fn fills_at_clearing_price(&self) -> bool {
+        self.total_contributed / self.clearing_price <= self.auction_supply
+    }
+
+    // Tokens bought and not yet delivered, and the quote owed back to their
+    // buyers beside them. A deposit filled at the clearing price counts what
+    // it holds below one token's price as a fraction of a token, which is
+    // how it is paid out once the project is dissolved.
+    pub fn undelivered_entitlements(&self) -> (u64, u64) {
+        match self.launch_mode {
+            LaunchMode::BondingCurve => (self.unclaimed_tokens, 0),
+            LaunchMode::DutchAuction if !self.fills_at_clearing_price() => {
+                let costs = self.unclaimed_tokens as u128 * self.clearing_price as u128;
+                let refunds = (self.unsettled_deposits as u128).saturating_sub(costs);
+                (self.unclaimed_tokens, refunds as u64)
+            }
+            _ => (
+                self.unsettled_deposits.div_ceil(self.clearing_price.max(1)),
+                0,
+            ),
+        }
+    }
+
+    // Quote a dissolved project pays for `tokens` of its supply
+    pub fn redemption_value(&self, tokens: u64) -> u64 {
+        self.deposit_redemption_value(tokens, 1)
+    }
+
+    // Quote a dissolved project pays for the tokens `deposit` buys at
+    // `price`, including the fraction of a token below one token's price
+    pub fn deposit_redemption_value(&self, deposit: u64, price: u64) -> u64 {
+        (deposit as u128 * self.dissolved_treasury as u128)
+            .checked_div(price as u128 * self.dissolved_supply as u128)
+            .unwrap_or(0) as u64
+    }
+
+    // Part of the raise set aside to seed the graduation pool
+    pub fn graduation_amount(&self, raised: u64) -> u64 {
+        (raised as u128 * self.graduation_bps as u128 / 10_000) as u64
+    }
+}
+
+// Action a yes/no proposal carries out through Execute once it passes.
+// Milestone release proposals carry `None` and are executed by ReleaseFunds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProposalAction {
+    None,
+    SetCurveParams { base_price: u64, slope: u64 },
+    SetFundingGoal { funding_goal: u64 },
+    SetAuthority { authority: Pubkey },
+    AddMilestone { amount: u64, deadline: i64 },
+    CancelMilestone { milestone_id: u8 },
+    TransferTreasury { recipient: Pubkey, amount: u64 },
+    SetPaused { paused: bool },
+    Dissolve,
+}
+
+impl ProposalAction {
+    pub const LEN: usize = 1 + // kind
+        32 + // key
+        8 + // first value
+        8; // second value
+
+    pub fn pack(&self, dst: &mut [u8]) {
+        let (kind, key, first, second) = match *self {
+            ProposalAction::None => (0, Pubkey::default(), 0, 0),
+            ProposalAction::SetCurveParams { base_price, slope } => {
+                (1, Pubkey::default(), base_price, slope)
+            }
+            ProposalAction::SetFundingGoal { funding_goal } => {
+                (2, Pubkey::default(), funding_goal, 0)
+            }
+            ProposalAction::SetAuthority { authority } => (3, authority, 0, 0),
+            ProposalAction::AddMilestone { amount, deadline } => {
+                (4, Pubkey::default(), amount, deadline as u64)
+            }
+            ProposalAction::CancelMilestone { milestone_id } => {
+                (5, Pubkey::default(), milestone_id as u64, 0)
+            }
+            ProposalAction::TransferTreasury { recipient, amount } => (6, recipient, amount, 0),
+            ProposalAction::SetPaused { paused } => (7, Pubkey::default(), paused as u64, 0),
+            ProposalAction::Dissolve => (8, Pubkey::default(), 0, 0),
+        };
+
+        dst[0] = kind;
+        dst[1..33].copy_from_slice(&key.to_bytes());
+        dst[33..41].copy_from_slice(&first.to_le_bytes());
+        dst[41..49].copy_from_slice(&second.to_le_bytes());
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < ProposalAction::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let key = Pubkey::try_from(&src[1..33]).map_err(|_| ProgramError::InvalidAccountData)?;
+        let first = u64::from_le_bytes(src[33..41].try_into().unwrap());
+        let second = u64::from_le_bytes(src[41..49].try_into().unwrap());
+
+        match src[0] {
+            0 => Ok(ProposalAction::None),
+            1 => Ok(ProposalAction::SetCurveParams {
+                base_price: first,
+                slope: second,
+            }),
+            2 => Ok(ProposalAction::SetFundingGoal {
+                funding_goal: first,
+            }),
+            3 => Ok(ProposalAction::SetAuthority { authority: key }),
+            4 => Ok(ProposalAction::AddMilestone {
+                amount: first,
+                deadline: second as i64,
+            }),
+            5 => Ok(ProposalAction::CancelMilestone {
+                milestone_id: u8::try_from(first).map_err(|_| ProgramError::InvalidAccountData)?,
+            }),
+            6 => Ok(ProposalAction::TransferTreasury {
+                recipient: key,
+                amount: first,
+            }),
+            7 => Ok(ProposalAction::SetPaused {
+                paused: first != 0,
+            }),
+            8 => Ok(ProposalAction::Dissolve),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
 }
 
+// Vote choices on yes/no proposals. Multi-choice proposals vote with the option
+// index instead; abstentions count toward quorum on both.
+pub const VOTE_NO: u8 = 0;
+pub const VOTE_YES: u8 = 1;
+pub const VOTE_ABSTAIN: u8 = u8::MAX;
+
+// Choice recorded for a committed secret ballot until it is revealed
+pub const VOTE_HIDDEN: u8 = u8::MAX - 1;
+
+// Maximum number of options on a multi-choice proposal
+pub const MAX_PROPOSAL_OPTIONS: usize = 8;
+
+// Proposal indices are a u8, so holders stop proposing this many slots before
+// they run out and the authority can still put milestones to a vote
+pub const AUTHORITY_PROPOSAL_SLOTS: u8 = 32;
+
 // Proposal account structure
+//
+// Proposals without option labels are yes/no votes on a milestone release.
+// `winning_option` is only meaningful once the proposal is executed. Secret
+// ballot proposals have a nonzero `reveal_end`: votes are committed until
+// `voting_end`, revealed until `reveal_end`, and weight that was committed but
+// never revealed stays in `unrevealed_votes` without counting toward the result.
+// Action proposals are yes/no votes on `action`, carried out by Execute.
+// Holder proposals escrow `deposit` in the vault until SettleDeposit.
+// Milestone proposals record the evidence version voters were shown.
 #[derive(Debug)]
 pub struct Proposal {
     pub creator: Pubkey,
@@ -363,6 +1806,19 @@ pub struct Proposal {
     pub is_executed: bool,
     pub created_at: i64,
     pub voting_end: i64,
+    pub is_cancelled: bool,
+    pub veto_votes: u64,
+    pub abstain_votes: u64,
+    pub quorum_votes: u64,
+    pub option_labels: Vec<String>,
+    pub option_votes: Vec<u64>,
+    pub winning_option: u8,
+    pub reveal_end: i64,
+    pub unrevealed_votes: u64,
+    pub action: ProposalAction,
+    pub deposit: u64,
+    pub evidence_version: u8,
+    pub evidence_hash: [u8; 32],
 }
 
 impl Proposal {
@@ -374,7 +1830,21 @@ impl Proposal {
         8 + // no_votes
         1 + // is_executed
         8 + // created_at
-        8; // voting_end
+        8 + // voting_end
+        1 + // is_cancelled
+        8 + // veto_votes
+        8 + // abstain_votes
+        8 + // quorum_votes
+        1 + // option count
+        32 * MAX_PROPOSAL_OPTIONS + // option_labels
+        8 * MAX_PROPOSAL_OPTIONS + // option_votes
+        1 + // winning_option
+        8 + // reveal_end
+        8 + // unrevealed_votes
+        ProposalAction::LEN + // action
+        8 + // deposit
+        1 + // evidence_version
+        32; // evidence_hash
 
     pub fn pack(&self, dst: &mut [u8]) {
         let mut offset = 0;
@@ -421,6 +1891,68 @@ impl Proposal {
 
         // Pack voting_end
         dst[offset..offset + 8].copy_from_slice(&self.voting_end.to_le_bytes());
+        offset += 8;
+
+        // Pack is_cancelled
+        dst[offset] = self.is_cancelled as u8;
+        offset += 1;
+
+        // Pack veto_votes
+        dst[offset..offset + 8].copy_from_slice(&self.veto_votes.to_le_bytes());
+        offset += 8;
+
+        // Pack abstain_votes
+        dst[offset..offset + 8].copy_from_slice(&self.abstain_votes.to_le_bytes());
+        offset += 8;
+
+        // Pack quorum_votes
+        dst[offset..offset + 8].copy_from_slice(&self.quorum_votes.to_le_bytes());
+        offset += 8;
+
+        // Pack options
+        dst[offset] = self.option_labels.len() as u8;
+        offset += 1;
+        for i in 0..MAX_PROPOSAL_OPTIONS {
+            let label_bytes = self.option_labels.get(i).map(|label| label.as_bytes());
+            let label_bytes = label_bytes.unwrap_or_default();
+            let mut label_buffer = [0u8; 32];
+            let len = std::cmp::min(label_bytes.len(), 32);
+            label_buffer[..len].copy_from_slice(&label_bytes[..len]);
+            dst[offset..offset + 32].copy_from_slice(&label_buffer);
+            offset += 32;
+        }
+        for i in 0..MAX_PROPOSAL_OPTIONS {
+            let votes = self.option_votes.get(i).copied().unwrap_or_default();
+            dst[offset..offset + 8].copy_from_slice(&votes.to_le_bytes());
+            offset += 8;
+        }
+
+        // Pack winning_option
+        dst[offset] = self.winning_option;
+        offset += 1;
+
+        // Pack reveal_end
+        dst[offset..offset + 8].copy_from_slice(&self.reveal_end.to_le_bytes());
+        offset += 8;
+
+        // Pack unrevealed_votes
+        dst[offset..offset + 8].copy_from_slice(&self.unrevealed_votes.to_le_bytes());
+        offset += 8;
+
+        // Pack action
+        self.action.pack(&mut dst[offset..offset + ProposalAction::LEN]);
+        offset += ProposalAction::LEN;
+
+        // Pack deposit
+        dst[offset..offset + 8].copy_from_slice(&self.deposit.to_le_bytes());
+        offset += 8;
+
+        // Pack evidence_version
+        dst[offset] = self.evidence_version;
+        offset += 1;
+
+        // Pack evidence_hash
+        dst[offset..offset + 32].copy_from_slice(&self.evidence_hash);
     }
 
     pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
@@ -467,9 +1999,79 @@ impl Proposal {
 
         // Unpack voting_end
         let voting_end = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
 
-        Ok(Proposal {
-            creator,
+        // Unpack is_cancelled
+        let is_cancelled = src[offset] != 0;
+        offset += 1;
+
+        // Unpack veto_votes
+        let veto_votes = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack abstain_votes
+        let abstain_votes = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack quorum_votes
+        let quorum_votes = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack options
+        let option_count = src[offset] as usize;
+        offset += 1;
+        if option_count > MAX_PROPOSAL_OPTIONS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut option_labels = Vec::with_capacity(option_count);
+        for i in 0..MAX_PROPOSAL_OPTIONS {
+            if i < option_count {
+                option_labels.push(
+                    String::from_utf8(src[offset..offset + 32].to_vec())
+                        .map_err(|_| ProgramError::InvalidAccountData)?
+                        .trim_end_matches('\0')
+                        .to_string(),
+                );
+            }
+            offset += 32;
+        }
+        let mut option_votes = Vec::with_capacity(option_count);
+        for i in 0..MAX_PROPOSAL_OPTIONS {
+            if i < option_count {
+                option_votes.push(u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap()));
+            }
+            offset += 8;
+        }
+
+        // Unpack winning_option
+        let winning_option = src[offset];
+        offset += 1;
+
+        // Unpack reveal_end
+        let reveal_end = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack unrevealed_votes
+        let unrevealed_votes = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack action
+        let action = ProposalAction::unpack(&src[offset..offset + ProposalAction::LEN])?;
+        offset += ProposalAction::LEN;
+
+        // Unpack deposit
+        let deposit = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack evidence_version
+        let evidence_version = src[offset];
+        offset += 1;
+
+        // Unpack evidence_hash
+        let evidence_hash = src[offset..offset + 32].try_into().unwrap();
+
+        Ok(Proposal {
+            creator,
             title,
             description,
             milestone_id,
@@ -478,27 +2080,505 @@ impl Proposal {
             is_executed,
             created_at,
             voting_end,
+            is_cancelled,
+            veto_votes,
+            abstain_votes,
+            quorum_votes,
+            option_labels,
+            option_votes,
+            winning_option,
+            reveal_end,
+            unrevealed_votes,
+            action,
+            deposit,
+            evidence_version,
+            evidence_hash,
+        })
+    }
+
+    // Passed proposals wait out the project's execution delay before funds move
+    pub fn executable_at(&self, governance: &GovernanceConfig) -> i64 {
+        self.tally_end() + governance.execution_delay
+    }
+
+    pub fn is_secret(&self) -> bool {
+        self.reveal_end != 0
+    }
+
+    // The result is final once voting and any reveal phase are over
+    pub fn tally_end(&self) -> i64 {
+        std::cmp::max(self.voting_end, self.reveal_end)
+    }
+
+    // Unexecuted proposals lapse once the execution window closes
+    pub fn is_expired(&self, governance: &GovernanceConfig, now: i64) -> bool {
+        governance.execution_window != 0
+            && now > self.executable_at(governance) + governance.execution_window
+    }
+
+    // Cancelled or vetoed, voted down, or lapsed, so it can no longer take
+    // effect
+    pub fn is_defunct(&self, governance: &GovernanceConfig, now: i64) -> bool {
+        self.is_cancelled
+            || (now > self.tally_end() && !self.passed())
+            || (!self.is_executed && self.is_expired(governance, now))
+    }
+
+    pub fn is_multi_choice(&self) -> bool {
+        !self.option_labels.is_empty()
+    }
+
+    // Tally a choice is counted in, or None if the proposal has no such choice
+    pub fn tally_mut(&mut self, choice: u8) -> Option<&mut u64> {
+        if choice == VOTE_ABSTAIN {
+            return Some(&mut self.abstain_votes);
+        }
+        if choice == VOTE_HIDDEN {
+            return Some(&mut self.unrevealed_votes);
+        }
+        if self.is_multi_choice() {
+            return self.option_votes.get_mut(choice as usize);
+        }
+        match choice {
+            VOTE_NO => Some(&mut self.no_votes),
+            VOTE_YES => Some(&mut self.yes_votes),
+            _ => None,
+        }
+    }
+
+    // Every counted vote, abstentions included, counts toward quorum
+    pub fn has_quorum(&self) -> bool {
+        let option_total: u128 = self.option_votes.iter().map(|&votes| votes as u128).sum();
+        let total = self.yes_votes as u128
+            + self.no_votes as u128
+            + self.abstain_votes as u128
+            + option_total;
+        total >= self.quorum_votes as u128
+    }
+
+    // Option with the most votes; a tie or an empty tally has no winner
+    pub fn leading_option(&self) -> Option<u8> {
+        let most = *self.option_votes.iter().max()?;
+        let mut leaders = self
+            .option_votes
+            .iter()
+            .enumerate()
+            .filter(|(_, &votes)| votes == most);
+        let (index, _) = leaders.next()?;
+        if most == 0 || leaders.next().is_some() {
+            return None;
+        }
+        Some(index as u8)
+    }
+
+    // Quorum plus a yes majority, or a single leading option. Abstentions
+    // never count toward approval.
+    pub fn passed(&self) -> bool {
+        if !self.has_quorum() {
+            return false;
+        }
+        if self.is_multi_choice() {
+            self.leading_option().is_some()
+        } else {
+            self.yes_votes > self.no_votes
+        }
+    }
+}
+
+// Veto record account structure
+//
+// Derived from [b"veto", proposal, voter] so each holder vetoes once.
+#[derive(Debug)]
+pub struct VetoRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub weight: u64,
+}
+
+impl VetoRecord {
+    pub const LEN: usize = 32 + // proposal
+        32 + // voter
+        8; // weight
+
+    pub fn pack(&self, dst: &mut [u8]) {
+        let mut offset = 0;
+
+        // Pack proposal
+        dst[offset..offset + 32].copy_from_slice(&self.proposal.to_bytes());
+        offset += 32;
+
+        // Pack voter
+        dst[offset..offset + 32].copy_from_slice(&self.voter.to_bytes());
+        offset += 32;
+
+        // Pack weight
+        dst[offset..offset + 8].copy_from_slice(&self.weight.to_le_bytes());
+    }
+}
+
+// Voter escrow account structure
+//
+// Derived from [b"escrow", project, holder]. Votes, vetoes and proposal
+// thresholds are weighted by the project tokens a holder has escrowed in the
+// project's escrow vault, a token account derived from [b"escrow_vault",
+// project]. Counting the escrow towards a proposal locks it until the
+// proposal's tally has closed, so the same tokens cannot be withdrawn and
+// counted again from another wallet.
+#[derive(Debug)]
+pub struct VoterEscrow {
+    pub project: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub locked_until: i64,
+    pub proposal_until: i64,
+}
+
+impl VoterEscrow {
+    pub const LEN: usize = 32 + // project
+        32 + // holder
+        8 + // amount
+        8 + // locked_until
+        8; // proposal_until
+
+    pub fn pack(&self, dst: &mut [u8]) {
+        let mut offset = 0;
+
+        // Pack project
+        dst[offset..offset + 32].copy_from_slice(&self.project.to_bytes());
+        offset += 32;
+
+        // Pack holder
+        dst[offset..offset + 32].copy_from_slice(&self.holder.to_bytes());
+        offset += 32;
+
+        // Pack amount
+        dst[offset..offset + 8].copy_from_slice(&self.amount.to_le_bytes());
+        offset += 8;
+
+        // Pack locked_until
+        dst[offset..offset + 8].copy_from_slice(&self.locked_until.to_le_bytes());
+        offset += 8;
+
+        // Pack proposal_until
+        dst[offset..offset + 8].copy_from_slice(&self.proposal_until.to_le_bytes());
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < VoterEscrow::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut offset = 0;
+
+        // Unpack project
+        let project = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack holder
+        let holder = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack amount
+        let amount = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack locked_until
+        let locked_until = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack proposal_until
+        let proposal_until = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+
+        Ok(VoterEscrow {
+            project,
+            holder,
+            amount,
+            locked_until,
+            proposal_until,
+        })
+    }
+}
+
+// Delegation record account structure
+//
+// Derived from [b"delegation", project, delegator]. While it exists the
+// delegator's escrowed tokens are counted with the delegate's votes, unless
+// the delegator votes on a proposal themselves.
+#[derive(Debug)]
+pub struct DelegationRecord {
+    pub project: Pubkey,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+}
+
+impl DelegationRecord {
+    pub const LEN: usize = 32 + // project
+        32 + // delegator
+        32; // delegate
+
+    pub fn pack(&self, dst: &mut [u8]) {
+        let mut offset = 0;
+
+        // Pack project
+        dst[offset..offset + 32].copy_from_slice(&self.project.to_bytes());
+        offset += 32;
+
+        // Pack delegator
+        dst[offset..offset + 32].copy_from_slice(&self.delegator.to_bytes());
+        offset += 32;
+
+        // Pack delegate
+        dst[offset..offset + 32].copy_from_slice(&self.delegate.to_bytes());
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < DelegationRecord::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut offset = 0;
+
+        // Unpack project
+        let project = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack delegator
+        let delegator = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack delegate
+        let delegate = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        Ok(DelegationRecord {
+            project,
+            delegator,
+            delegate,
+        })
+    }
+}
+
+// Vote record account structure
+//
+// Derived from [b"vote", proposal, voter] and created for every holder whose
+// weight is counted on a proposal. `cast_by` is the voter themselves for a
+// direct vote, or their delegate when the weight came in through delegation.
+// Secret ballots keep `choice` at VOTE_HIDDEN and store the commitment until
+// they are revealed.
+#[derive(Debug)]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub cast_by: Pubkey,
+    pub choice: u8,
+    pub weight: u64,
+    pub commitment: [u8; 32],
+}
+
+impl VoteRecord {
+    pub const LEN: usize = 32 + // proposal
+        32 + // voter
+        32 + // cast_by
+        1 + // choice
+        8 + // weight
+        32; // commitment
+
+    pub fn pack(&self, dst: &mut [u8]) {
+        let mut offset = 0;
+
+        // Pack proposal
+        dst[offset..offset + 32].copy_from_slice(&self.proposal.to_bytes());
+        offset += 32;
+
+        // Pack voter
+        dst[offset..offset + 32].copy_from_slice(&self.voter.to_bytes());
+        offset += 32;
+
+        // Pack cast_by
+        dst[offset..offset + 32].copy_from_slice(&self.cast_by.to_bytes());
+        offset += 32;
+
+        // Pack choice
+        dst[offset] = self.choice;
+        offset += 1;
+
+        // Pack weight
+        dst[offset..offset + 8].copy_from_slice(&self.weight.to_le_bytes());
+        offset += 8;
+
+        // Pack commitment
+        dst[offset..offset + 32].copy_from_slice(&self.commitment);
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < VoteRecord::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut offset = 0;
+
+        // Unpack proposal
+        let proposal = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack voter
+        let voter = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack cast_by
+        let cast_by = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack choice
+        let choice = src[offset];
+        offset += 1;
+
+        // Unpack weight
+        let weight = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack commitment
+        let commitment = src[offset..offset + 32].try_into().unwrap();
+
+        Ok(VoteRecord {
+            proposal,
+            voter,
+            cast_by,
+            choice,
+            weight,
+            commitment,
         })
     }
 }
 
+// Lifecycle of a milestone. A proposal moves it from Pending to Proposed; the
+// passed proposal approves it and ReleaseFunds pays it out. Open milestones
+// past their deadline become Missed and need a fresh proposal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MilestoneStatus {
+    Pending,
+    Proposed,
+    Approved,
+    Released,
+    Missed,
+    Cancelled,
+}
+
+impl MilestoneStatus {
+    pub fn from_u8(value: u8) -> Result<Self, ProgramError> {
+        match value {
+            0 => Ok(MilestoneStatus::Pending),
+            1 => Ok(MilestoneStatus::Proposed),
+            2 => Ok(MilestoneStatus::Approved),
+            3 => Ok(MilestoneStatus::Released),
+            4 => Ok(MilestoneStatus::Missed),
+            5 => Ok(MilestoneStatus::Cancelled),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    // Error for an instruction that does not accept a milestone in this state
+    pub fn error(self) -> UnicornFactoryError {
+        match self {
+            MilestoneStatus::Pending => UnicornFactoryError::ProposalNotFound,
+            MilestoneStatus::Proposed => UnicornFactoryError::MilestoneAlreadyHasProposal,
+            MilestoneStatus::Approved | MilestoneStatus::Released => {
+                UnicornFactoryError::MilestoneAlreadyCompleted
+            }
+            MilestoneStatus::Missed => UnicornFactoryError::MilestoneMissed,
+            MilestoneStatus::Cancelled => UnicornFactoryError::MilestoneCancelled,
+        }
+    }
+}
+
+pub const MAX_MILESTONE_TRANCHES: u8 = 12;
+
+// Milestones with a nonzero `deadline` are marked missed at `missed_at` by the
+// deadline crank if they are still open after it. That happens only once, so
+// the fresh vote a missed milestone needs is not held to the same deadline.
+// `proposal_id` is the latest proposal made for the milestone, the only one
+// that can approve or release it.
+// The amount is paid in `tranche_count` equal tranches, the first on approval
+// and each further one `tranche_interval` seconds after the previous.
+// `evidence_version` counts the evidence submitted so far, the latest of which
+// has `evidence_hash`.
 #[derive(Debug)]
 pub struct Milestone {
     pub title: String,
     pub description: String,
     pub amount: u64,
-    pub is_completed: bool,
-    pub completed_at: i64,
-    pub has_proposal: bool,
+    pub status: MilestoneStatus,
+    pub released_at: i64,
+    pub proposal_id: u8,
+    pub deadline: i64,
+    pub missed_at: i64,
+    pub tranche_count: u8,
+    pub tranche_interval: i64,
+    pub approved_at: i64,
+    pub released_amount: u64,
+    pub evidence_version: u8,
+    pub evidence_hash: [u8; 32],
 }
 
 impl Milestone {
     pub const LEN: usize = 32 + // title
         256 + // description
         8 + // amount
-        1 + // is_completed
-        8 + // completed_at
-        1; // has_proposal
+        1 + // status
+        8 + // released_at
+        1 + // proposal_id
+        8 + // deadline
+        8 + // missed_at
+        1 + // tranche_count
+        8 + // tranche_interval
+        8 + // approved_at
+        8 + // released_amount
+        1 + // evidence_version
+        32; // evidence_hash
+
+    pub fn new(
+        title: String,
+        description: String,
+        amount: u64,
+        deadline: i64,
+        tranche_count: u8,
+        tranche_interval: i64,
+    ) -> Self {
+        Milestone {
+            title,
+            description,
+            amount,
+            status: MilestoneStatus::Pending,
+            released_at: 0,
+            proposal_id: 0,
+            deadline,
+            missed_at: 0,
+            tranche_count,
+            tranche_interval,
+            approved_at: 0,
+            released_amount: 0,
+            evidence_version: 0,
+            evidence_hash: [0u8; 32],
+        }
+    }
+
+    // Total of the tranches unlocked by `now`, nothing before approval
+    pub fn unlocked_amount(&self, now: i64) -> u64 {
+        if self.approved_at == 0 || self.tranche_count == 0 {
+            return 0;
+        }
+        let unlocked = if self.tranche_interval > 0 {
+            let elapsed = now.saturating_sub(self.approved_at).max(0);
+            std::cmp::min(1 + elapsed / self.tranche_interval, self.tranche_count as i64)
+        } else {
+            self.tranche_count as i64
+        };
+        (self.amount as u128 * unlocked as u128 / self.tranche_count as u128) as u64
+    }
 
     pub fn pack(&self, dst: &mut [u8]) {
         let mut offset = 0;
@@ -523,16 +2603,48 @@ impl Milestone {
         dst[offset..offset + 8].copy_from_slice(&self.amount.to_le_bytes());
         offset += 8;
 
-        // Pack is_completed
-        dst[offset] = self.is_completed as u8;
+        // Pack status
+        dst[offset] = self.status as u8;
+        offset += 1;
+
+        // Pack released_at
+        dst[offset..offset + 8].copy_from_slice(&self.released_at.to_le_bytes());
+        offset += 8;
+
+        // Pack proposal_id
+        dst[offset] = self.proposal_id;
+        offset += 1;
+
+        // Pack deadline
+        dst[offset..offset + 8].copy_from_slice(&self.deadline.to_le_bytes());
+        offset += 8;
+
+        // Pack missed_at
+        dst[offset..offset + 8].copy_from_slice(&self.missed_at.to_le_bytes());
+        offset += 8;
+
+        // Pack tranche_count
+        dst[offset] = self.tranche_count;
         offset += 1;
 
-        // Pack completed_at
-        dst[offset..offset + 8].copy_from_slice(&self.completed_at.to_le_bytes());
+        // Pack tranche_interval
+        dst[offset..offset + 8].copy_from_slice(&self.tranche_interval.to_le_bytes());
+        offset += 8;
+
+        // Pack approved_at
+        dst[offset..offset + 8].copy_from_slice(&self.approved_at.to_le_bytes());
+        offset += 8;
+
+        // Pack released_amount
+        dst[offset..offset + 8].copy_from_slice(&self.released_amount.to_le_bytes());
         offset += 8;
 
-        // Pack has_proposal
-        dst[offset] = self.has_proposal as u8;
+        // Pack evidence_version
+        dst[offset] = self.evidence_version;
+        offset += 1;
+
+        // Pack evidence_hash
+        dst[offset..offset + 32].copy_from_slice(&self.evidence_hash);
     }
 
     pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
@@ -556,143 +2668,7885 @@ impl Milestone {
         let amount = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
         offset += 8;
 
-        // Unpack is_completed
-        let is_completed = src[offset] != 0;
+        // Unpack status
+        let status = MilestoneStatus::from_u8(src[offset])?;
+        offset += 1;
+
+        // Unpack released_at
+        let released_at = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack proposal_id
+        let proposal_id = src[offset];
+        offset += 1;
+
+        // Unpack deadline
+        let deadline = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack missed_at
+        let missed_at = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack tranche_count
+        let tranche_count = src[offset];
         offset += 1;
 
-        // Unpack completed_at
-        let completed_at = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        // Unpack tranche_interval
+        let tranche_interval = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack approved_at
+        let approved_at = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack released_amount
+        let released_amount = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
         offset += 8;
 
-        // Unpack has_proposal
-        let has_proposal = src[offset] != 0;
+        // Unpack evidence_version
+        let evidence_version = src[offset];
+        offset += 1;
+
+        // Unpack evidence_hash
+        let evidence_hash = src[offset..offset + 32].try_into().unwrap();
 
         Ok(Milestone {
             title,
             description,
             amount,
-            is_completed,
-            completed_at,
-            has_proposal,
+            status,
+            released_at,
+            proposal_id,
+            deadline,
+            missed_at,
+            tranche_count,
+            tranche_interval,
+            approved_at,
+            released_amount,
+            evidence_version,
+            evidence_hash,
         })
     }
 }
 
-// Program errors
+pub const MAX_EVIDENCE_URI_LEN: usize = 200;
+
+// Milestone evidence account structure
+//
+// Derived from [b"evidence", project, milestone_id, version]. Versions start
+// at 1 and each amendment adds the next one, leaving earlier versions intact.
 #[derive(Debug)]
-pub enum UnicornFactoryError {
-    ProjectNotActive,
-    FundingGoalReached,
-    Overflow,
-    InvalidAmount,
-    InvalidProjectAccount,
-    InvalidAuthority,
-    ProposalNotFound,
-    ProposalAlreadyExecuted,
-    VotingPeriodEnded,
-    AlreadyVoted,
-    InvalidMilestone,
-    MilestoneAlreadyCompleted,
-    VotingPeriodNotEnded,
-    ProposalDidNotPass,
-    MilestoneAlreadyHasProposal,
+pub struct MilestoneEvidence {
+    pub project: Pubkey,
+    pub milestone_id: u8,
+    pub version: u8,
+    pub uri: String,
+    pub content_hash: [u8; 32],
+    pub submitted_by: Pubkey,
+    pub submitted_at: i64,
+}
+
+impl MilestoneEvidence {
+    pub const LEN: usize = 32 + // project
+        1 + // milestone_id
+        1 + // version
+        MAX_EVIDENCE_URI_LEN + // uri
+        32 + // content_hash
+        32 + // submitted_by
+        8; // submitted_at
+
+    pub fn pack(&self, dst: &mut [u8]) {
+        let mut offset = 0;
+
+        // Pack project
+        dst[offset..offset + 32].copy_from_slice(&self.project.to_bytes());
+        offset += 32;
+
+        // Pack milestone_id
+        dst[offset] = self.milestone_id;
+        offset += 1;
+
+        // Pack version
+        dst[offset] = self.version;
+        offset += 1;
+
+        // Pack uri
+        let uri_bytes = self.uri.as_bytes();
+        let mut uri_buffer = [0u8; MAX_EVIDENCE_URI_LEN];
+        let len = std::cmp::min(uri_bytes.len(), MAX_EVIDENCE_URI_LEN);
+        uri_buffer[..len].copy_from_slice(&uri_bytes[..len]);
+        dst[offset..offset + MAX_EVIDENCE_URI_LEN].copy_from_slice(&uri_buffer);
+        offset += MAX_EVIDENCE_URI_LEN;
+
+        // Pack content_hash
+        dst[offset..offset + 32].copy_from_slice(&self.content_hash);
+        offset += 32;
+
+        // Pack submitted_by
+        dst[offset..offset + 32].copy_from_slice(&self.submitted_by.to_bytes());
+        offset += 32;
+
+        // Pack submitted_at
+        dst[offset..offset + 8].copy_from_slice(&self.submitted_at.to_le_bytes());
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < MilestoneEvidence::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut offset = 0;
+
+        // Unpack project
+        let project = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack milestone_id
+        let milestone_id = src[offset];
+        offset += 1;
+
+        // Unpack version
+        let version = src[offset];
+        offset += 1;
+
+        // Unpack uri
+        let uri = String::from_utf8(src[offset..offset + MAX_EVIDENCE_URI_LEN].to_vec())
+            .map_err(|_| ProgramError::InvalidAccountData)?
+            .trim_end_matches('\0')
+            .to_string();
+        offset += MAX_EVIDENCE_URI_LEN;
+
+        // Unpack content_hash
+        let content_hash = src[offset..offset + 32].try_into().unwrap();
+        offset += 32;
+
+        // Unpack submitted_by
+        let submitted_by = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack submitted_at
+        let submitted_at = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+
+        Ok(MilestoneEvidence {
+            project,
+            milestone_id,
+            version,
+            uri,
+            content_hash,
+            submitted_by,
+            submitted_at,
+        })
+    }
+}
+
+// Per-contributor record account structure
+#[derive(Debug)]
+pub struct ContributorRecord {
+    pub project: Pubkey,
+    pub contributor: Pubkey,
+    pub total_contributed: u64,
+    pub tokens_purchased: u64,
+    pub tokens_claimed: u64,
+    pub is_refunded: bool,
+    pub presale_contributed: u64,
+}
+
+impl ContributorRecord {
+    pub const LEN: usize = 32 + // project
+        32 + // contributor
+        8 + // total_contributed
+        8 + // tokens_purchased
+        8 + // tokens_claimed
+        1 + // is_refunded
+        8; // presale_contributed
+
+    pub fn pack(&self, dst: &mut [u8]) {
+        let mut offset = 0;
+
+        // Pack project
+        dst[offset..offset + 32].copy_from_slice(&self.project.to_bytes());
+        offset += 32;
+
+        // Pack contributor
+        dst[offset..offset + 32].copy_from_slice(&self.contributor.to_bytes());
+        offset += 32;
+
+        // Pack total_contributed
+        dst[offset..offset + 8].copy_from_slice(&self.total_contributed.to_le_bytes());
+        offset += 8;
+
+        // Pack tokens_purchased
+        dst[offset..offset + 8].copy_from_slice(&self.tokens_purchased.to_le_bytes());
+        offset += 8;
+
+        // Pack tokens_claimed
+        dst[offset..offset + 8].copy_from_slice(&self.tokens_claimed.to_le_bytes());
+        offset += 8;
+
+        // Pack is_refunded
+        dst[offset] = self.is_refunded as u8;
+        offset += 1;
+
+        // Pack presale_contributed
+        dst[offset..offset + 8].copy_from_slice(&self.presale_contributed.to_le_bytes());
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut offset = 0;
+
+        // Unpack project
+        let project = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack contributor
+        let contributor = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack total_contributed
+        let total_contributed = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack tokens_purchased
+        let tokens_purchased = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack tokens_claimed
+        let tokens_claimed = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack is_refunded
+        let is_refunded = src[offset] != 0;
+        offset += 1;
+
+        // Unpack presale_contributed
+        let presale_contributed = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+
+        Ok(ContributorRecord {
+            project,
+            contributor,
+            total_contributed,
+            tokens_purchased,
+            tokens_claimed,
+            is_refunded,
+            presale_contributed,
+        })
+    }
+}
+
+// Batch auction commitment account structure
+//
+// Derived from [b"commitment", project, user]. Commitments are collected during
+// the auction window and settle at one uniform price for every participant.
+#[derive(Debug)]
+pub struct Commitment {
+    pub project: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub is_claimed: bool,
+}
+
+impl Commitment {
+    pub const LEN: usize = 32 + // project
+        32 + // user
+        8 + // amount
+        1; // is_claimed
+
+    pub fn pack(&self, dst: &mut [u8]) {
+        let mut offset = 0;
+
+        // Pack project
+        dst[offset..offset + 32].copy_from_slice(&self.project.to_bytes());
+        offset += 32;
+
+        // Pack user
+        dst[offset..offset + 32].copy_from_slice(&self.user.to_bytes());
+        offset += 32;
+
+        // Pack amount
+        dst[offset..offset + 8].copy_from_slice(&self.amount.to_le_bytes());
+        offset += 8;
+
+        // Pack is_claimed
+        dst[offset] = self.is_claimed as u8;
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < Commitment::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut offset = 0;
+
+        // Unpack project
+        let project = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack user
+        let user = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack amount
+        let amount = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack is_claimed
+        let is_claimed = src[offset] != 0;
+
+        Ok(Commitment {
+            project,
+            user,
+            amount,
+            is_claimed,
+        })
+    }
+}
+
+// Maximum number of signers on a project council
+pub const MAX_COUNCIL_MEMBERS: usize = 10;
+
+// Project council account structure
+//
+// An M-of-N signer set derived from [b"council", project]. Once the council PDA
+// holds the project authority, authority instructions pass the council account
+// in the authority slot and need `threshold` member signatures. Released funds
+// go to `treasury`.
+#[derive(Debug)]
+pub struct ProjectCouncil {
+    pub project: Pubkey,
+    pub threshold: u8,
+    pub members: Vec<Pubkey>,
+    pub treasury: Pubkey,
+}
+
+impl ProjectCouncil {
+    pub const LEN: usize = 32 + // project
+        1 + // threshold
+        1 + // member count
+        32 * MAX_COUNCIL_MEMBERS + // members
+        32; // treasury
+
+    pub fn pack(&self, dst: &mut [u8]) {
+        let mut offset = 0;
+
+        // Pack project
+        dst[offset..offset + 32].copy_from_slice(&self.project.to_bytes());
+        offset += 32;
+
+        // Pack threshold
+        dst[offset] = self.threshold;
+        offset += 1;
+
+        // Pack members
+        dst[offset] = self.members.len() as u8;
+        offset += 1;
+        for i in 0..MAX_COUNCIL_MEMBERS {
+            let member = self.members.get(i).copied().unwrap_or_default();
+            dst[offset..offset + 32].copy_from_slice(&member.to_bytes());
+            offset += 32;
+        }
+
+        // Pack treasury
+        dst[offset..offset + 32].copy_from_slice(&self.treasury.to_bytes());
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < ProjectCouncil::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut offset = 0;
+
+        // Unpack project
+        let project = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack threshold
+        let threshold = src[offset];
+        offset += 1;
+
+        // Unpack members
+        let member_count = src[offset] as usize;
+        offset += 1;
+        if member_count > MAX_COUNCIL_MEMBERS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut members = Vec::with_capacity(member_count);
+        for i in 0..MAX_COUNCIL_MEMBERS {
+            if i < member_count {
+                members.push(
+                    Pubkey::try_from(&src[offset..offset + 32])
+                        .map_err(|_| ProgramError::InvalidAccountData)?,
+                );
+            }
+            offset += 32;
+        }
+
+        // Unpack treasury
+        let treasury = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        Ok(ProjectCouncil {
+            project,
+            threshold,
+            members,
+            treasury,
+        })
+    }
+}
+
+// Vesting schedule account structure
+#[derive(Debug)]
+pub struct VestingSchedule {
+    pub project: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+    pub revocable: bool,
+    pub is_revoked: bool,
+}
+
+impl VestingSchedule {
+    pub const LEN: usize = 32 + // project
+        32 + // beneficiary
+        8 + // total_amount
+        8 + // claimed_amount
+        8 + // start_time
+        8 + // cliff_time
+        8 + // end_time
+        1 + // revocable
+        1; // is_revoked
+
+    pub fn pack(&self, dst: &mut [u8]) {
+        let mut offset = 0;
+
+        // Pack project
+        dst[offset..offset + 32].copy_from_slice(&self.project.to_bytes());
+        offset += 32;
+
+        // Pack beneficiary
+        dst[offset..offset + 32].copy_from_slice(&self.beneficiary.to_bytes());
+        offset += 32;
+
+        // Pack total_amount
+        dst[offset..offset + 8].copy_from_slice(&self.total_amount.to_le_bytes());
+        offset += 8;
+
+        // Pack claimed_amount
+        dst[offset..offset + 8].copy_from_slice(&self.claimed_amount.to_le_bytes());
+        offset += 8;
+
+        // Pack start_time
+        dst[offset..offset + 8].copy_from_slice(&self.start_time.to_le_bytes());
+        offset += 8;
+
+        // Pack cliff_time
+        dst[offset..offset + 8].copy_from_slice(&self.cliff_time.to_le_bytes());
+        offset += 8;
+
+        // Pack end_time
+        dst[offset..offset + 8].copy_from_slice(&self.end_time.to_le_bytes());
+        offset += 8;
+
+        // Pack revocable
+        dst[offset] = self.revocable as u8;
+        offset += 1;
+
+        // Pack is_revoked
+        dst[offset] = self.is_revoked as u8;
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut offset = 0;
+
+        // Unpack project
+        let project = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack beneficiary
+        let beneficiary = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack total_amount
+        let total_amount = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack claimed_amount
+        let claimed_amount = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack start_time
+        let start_time = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack cliff_time
+        let cliff_time = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack end_time
+        let end_time = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack revocable
+        let revocable = src[offset] != 0;
+        offset += 1;
+
+        // Unpack is_revoked
+        let is_revoked = src[offset] != 0;
+
+        Ok(VestingSchedule {
+            project,
+            beneficiary,
+            total_amount,
+            claimed_amount,
+            start_time,
+            cliff_time,
+            end_time,
+            revocable,
+            is_revoked,
+        })
+    }
+
+    // Tokens unlocked at `now`: nothing before the cliff, then linear from
+    // start_time to end_time
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if now < self.cliff_time {
+            return 0;
+        }
+        if now >= self.end_time {
+            return self.total_amount;
+        }
+
+        let elapsed = (now - self.start_time) as u128;
+        let duration = (self.end_time - self.start_time) as u128;
+        (self.total_amount as u128 * elapsed / duration) as u64
+    }
+}
+
+// Presale round account structure
+//
+// Derived from [b"sale_round", project]. Only wallets in the Merkle tree under
+// `merkle_root` may contribute between start_time and end_time, at a fixed price
+// and up to `per_wallet_cap` each.
+#[derive(Debug)]
+pub struct SaleRound {
+    pub project: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub price: u64,
+    pub per_wallet_cap: u64,
+    pub merkle_root: [u8; 32],
+    pub total_sold: u64,
+}
+
+impl SaleRound {
+    pub const LEN: usize = 32 + // project
+        8 + // start_time
+        8 + // end_time
+        8 + // price
+        8 + // per_wallet_cap
+        32 + // merkle_root
+        8; // total_sold
+
+    pub fn pack(&self, dst: &mut [u8]) {
+        let mut offset = 0;
+
+        // Pack project
+        dst[offset..offset + 32].copy_from_slice(&self.project.to_bytes());
+        offset += 32;
+
+        // Pack start_time
+        dst[offset..offset + 8].copy_from_slice(&self.start_time.to_le_bytes());
+        offset += 8;
+
+        // Pack end_time
+        dst[offset..offset + 8].copy_from_slice(&self.end_time.to_le_bytes());
+        offset += 8;
+
+        // Pack price
+        dst[offset..offset + 8].copy_from_slice(&self.price.to_le_bytes());
+        offset += 8;
+
+        // Pack per_wallet_cap
+        dst[offset..offset + 8].copy_from_slice(&self.per_wallet_cap.to_le_bytes());
+        offset += 8;
+
+        // Pack merkle_root
+        dst[offset..offset + 32].copy_from_slice(&self.merkle_root);
+        offset += 32;
+
+        // Pack total_sold
+        dst[offset..offset + 8].copy_from_slice(&self.total_sold.to_le_bytes());
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < SaleRound::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut offset = 0;
+
+        // Unpack project
+        let project = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack start_time
+        let start_time = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack end_time
+        let end_time = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack price
+        let price = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack per_wallet_cap
+        let per_wallet_cap = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack merkle_root
+        let merkle_root: [u8; 32] = src[offset..offset + 32].try_into().unwrap();
+        offset += 32;
+
+        // Unpack total_sold
+        let total_sold = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+
+        Ok(SaleRound {
+            project,
+            start_time,
+            end_time,
+            price,
+            per_wallet_cap,
+            merkle_root,
+            total_sold,
+        })
+    }
+
+    pub fn is_allowlisted(&self, wallet: &Pubkey, proof: &[[u8; 32]]) -> bool {
+        verify_merkle_proof(&self.merkle_root, wallet, proof)
+    }
+}
+
+// Funding round account structure
+//
+// Rounds are derived from [b"round", project, index] and run in index order.
+// Each has its own goal, window and price; a zero merkle root opens the round
+// to everyone.
+#[derive(Debug)]
+pub struct FundingRound {
+    pub project: Pubkey,
+    pub index: u8,
+    pub goal: u64,
+    pub raised: u64,
+    pub pricing_mode: PricingMode,
+    pub price: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub merkle_root: [u8; 32],
+}
+
+impl FundingRound {
+    pub const LEN: usize = 32 + // project
+        1 + // index
+        8 + // goal
+        8 + // raised
+        1 + // pricing_mode
+        8 + // price
+        8 + // start_time
+        8 + // end_time
+        32; // merkle_root
+
+    pub fn pack(&self, dst: &mut [u8]) {
+        let mut offset = 0;
+
+        // Pack project
+        dst[offset..offset + 32].copy_from_slice(&self.project.to_bytes());
+        offset += 32;
+
+        // Pack index
+        dst[offset] = self.index;
+        offset += 1;
+
+        // Pack goal
+        dst[offset..offset + 8].copy_from_slice(&self.goal.to_le_bytes());
+        offset += 8;
+
+        // Pack raised
+        dst[offset..offset + 8].copy_from_slice(&self.raised.to_le_bytes());
+        offset += 8;
+
+        // Pack pricing_mode
+        dst[offset] = self.pricing_mode as u8;
+        offset += 1;
+
+        // Pack price
+        dst[offset..offset + 8].copy_from_slice(&self.price.to_le_bytes());
+        offset += 8;
+
+        // Pack start_time
+        dst[offset..offset + 8].copy_from_slice(&self.start_time.to_le_bytes());
+        offset += 8;
+
+        // Pack end_time
+        dst[offset..offset + 8].copy_from_slice(&self.end_time.to_le_bytes());
+        offset += 8;
+
+        // Pack merkle_root
+        dst[offset..offset + 32].copy_from_slice(&self.merkle_root);
+    }
+
+    pub fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < FundingRound::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut offset = 0;
+
+        // Unpack project
+        let project = Pubkey::try_from(&src[offset..offset + 32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        offset += 32;
+
+        // Unpack index
+        let index = src[offset];
+        offset += 1;
+
+        // Unpack goal
+        let goal = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack raised
+        let raised = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack pricing_mode
+        let pricing_mode = PricingMode::from_u8(src[offset])?;
+        offset += 1;
+
+        // Unpack price
+        let price = u64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack start_time
+        let start_time = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack end_time
+        let end_time = i64::from_le_bytes(src[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        // Unpack merkle_root
+        let merkle_root: [u8; 32] = src[offset..offset + 32].try_into().unwrap();
+
+        Ok(FundingRound {
+            project,
+            index,
+            goal,
+            raised,
+            pricing_mode,
+            price,
+            start_time,
+            end_time,
+            merkle_root,
+        })
+    }
+
+    // Fixed rounds sell at `price`; curve rounds climb linearly from `price` to
+    // twice `price` as the round fills
+    pub fn current_price(&self) -> u64 {
+        match self.pricing_mode {
+            PricingMode::Fixed => self.price,
+            PricingMode::Curve => {
+                let increase = self.price as u128 * self.raised as u128 / self.goal as u128;
+                self.price + increase as u64
+            }
+        }
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.merkle_root == [0u8; 32]
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.goal.saturating_sub(self.raised)
+    }
+}
+
+// Checks that `wallet` is a leaf of an allowlist tree. Leaves are keccak(wallet)
+// and each pair is hashed in sorted order.
+fn verify_merkle_proof(root: &[u8; 32], wallet: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let mut node = keccak::hashv(&[wallet.as_ref()]).to_bytes();
+    for sibling in proof {
+        node = if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+    node == *root
+}
+
+// Program errors
+#[derive(Debug)]
+pub enum UnicornFactoryError {
+    ProjectNotActive,
+    FundingGoalReached,
+    Overflow,
+    InvalidAmount,
+    InvalidProjectAccount,
+    InvalidAuthority,
+    ProposalNotFound,
+    ProposalAlreadyExecuted,
+    VotingPeriodEnded,
+    AlreadyVoted,
+    InvalidMilestone,
+    MilestoneAlreadyCompleted,
+    VotingPeriodNotEnded,
+    ProposalDidNotPass,
+    MilestoneAlreadyHasProposal,
+    MilestoneAllocationExceedsGoal,
+    InsufficientTreasury,
+    InvalidVaultAccount,
+    InvalidQuoteMint,
+    VestingReserveExceeded,
+    InvalidVestingSchedule,
+    NothingToClaim,
+    VestingNotRevocable,
+    VestingAlreadyRevoked,
+    RaiseClosed,
+    RaiseStillOpen,
+    RaiseNotSucceeded,
+    RaiseNotFailed,
+    AlreadyRefunded,
+    InvalidContributorRecord,
+    InvalidPoolAccount,
+    AlreadyGraduated,
+    InvalidGraduationConfig,
+    SlippageExceeded,
+    InsufficientLiquidity,
+    InvalidSaleRound,
+    SaleRoundNotStarted,
+    SaleRoundActive,
+    NotAllowlisted,
+    ContributionCapExceeded,
+    ContributionBelowMinimum,
+    InvalidFundingRound,
+    RoundNotStarted,
+    RoundAllocationExceeded,
+    InvalidLaunchMode,
+    InvalidAuctionConfig,
+    AuctionNotStarted,
+    AuctionSupplyExceeded,
+    InvalidCommitment,
+    InvalidCouncil,
+    InsufficientCouncilSignatures,
+    NoPendingAuthority,
+    ProposalCancelled,
+    TimelockActive,
+    ProposalExpired,
+    VetoWindowClosed,
+    InvalidGuardian,
+    InvalidDelegation,
+    VoteNotFound,
+    InvalidVoteChoice,
+    QuorumNotReached,
+    InvalidProposalType,
+    RevealPhaseClosed,
+    BallotMismatch,
+    ProjectPaused,
+    MilestoneCancelled,
+    ProposalThresholdNotMet,
+    ProjectDissolved,
+    ProjectNotDissolved,
+    MilestoneNotOverdue,
+    MilestoneMissed,
+    TrancheLocked,
+    InvalidEvidence,
+    OutdatedProjectLayout,
+    InvalidVoterEscrow,
+    EscrowLocked,
+    MilestoneNotCompleted,
+    TokenSupplyExceeded,
+    ProposalLimitReached,
 }
 
 impl From<UnicornFactoryError> for ProgramError {
     fn from(e: UnicornFactoryError) -> Self {
         ProgramError::Custom(e as u32)
     }
-}
+}
+
+// Helper functions for bonding curve calculations
+fn calculate_tokens(amount: u64, current_price: u64) -> u64 {
+    amount.checked_div(current_price).unwrap_or(0)
+}
+
+fn calculate_new_price(project: &Project) -> u64 {
+    let price_increase = project
+        .total_raised
+        .checked_mul(project.curve_slope)
+        .unwrap_or(0)
+        .checked_div(project.funding_goal)
+        .unwrap_or(0);
+    project.curve_base_price.saturating_add(price_increase)
+}
+
+// Quote asset that can be paid out of the vault without dipping into funds
+// still owed to holders selling back into the curve or proposal deposits held
+// in escrow. Slashed deposits are spendable like raised funds.
+fn releasable_amount(project: &Project, vault_balance: u64) -> u64 {
+    // A failed raise owes everything back to contributors, and a dissolved
+    // project owes what is left to its holders
+    if project.raise_state == RaiseState::Failed || project.is_dissolved {
+        return 0;
+    }
+
+    let owed_to_sellers = if project.is_active {
+        project.total_raised
+    } else {
+        0
+    };
+    let unreleased = project
+        .total_raised
+        .saturating_add(project.slashed_deposits)
+        .saturating_sub(project.total_released);
+
+    vault_balance
+        .saturating_sub(owed_to_sellers)
+        .saturating_sub(project.held_deposits)
+        .min(unreleased)
+}
+
+// Helper functions for contributor records
+//
+// Every contribution is tracked in a PDA derived from
+// [b"contributor", project, contributor] so refunds and locked-up tokens can be
+// settled once the raise closes.
+fn verify_contributor_record(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    contributor_account: &AccountInfo,
+    record_account: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (expected_record_pda, record_bump) = Pubkey::find_program_address(
+        &[
+            b"contributor",
+            project_account.key.as_ref(),
+            contributor_account.key.as_ref(),
+        ],
+        program_id,
+    );
+
+    if expected_record_pda != *record_account.key {
+        msg!(
+            "Invalid contributor record PDA. Expected: {}, Got: {}",
+            expected_record_pda,
+            record_account.key
+        );
+        return Err(UnicornFactoryError::InvalidContributorRecord.into());
+    }
+    Ok(record_bump)
+}
+
+// Loads the contributor record, creating it on the contributor's first contribution
+fn load_or_create_contributor_record<'a>(
+    program_id: &Pubkey,
+    project_account: &AccountInfo<'a>,
+    contributor_account: &AccountInfo<'a>,
+    record_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<ContributorRecord, ProgramError> {
+    let record_bump =
+        verify_contributor_record(program_id, project_account, contributor_account, record_account)?;
+
+    if !record_account.data_is_empty() {
+        let record_data = record_account.data.borrow();
+        return ContributorRecord::unpack(&record_data);
+    }
+
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let rent = Rent::get()?;
+    let space = ContributorRecord::LEN;
+    let lamports = rent.minimum_balance(space);
+
+    let record_seeds = &[
+        b"contributor".as_ref(),
+        project_account.key.as_ref(),
+        contributor_account.key.as_ref(),
+        &[record_bump],
+    ];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            contributor_account.key,
+            record_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        ),
+        &[
+            contributor_account.clone(),
+            record_account.clone(),
+            system_program.clone(),
+        ],
+        &[record_seeds],
+    )?;
+
+    Ok(ContributorRecord {
+        project: *project_account.key,
+        contributor: *contributor_account.key,
+        total_contributed: 0,
+        tokens_purchased: 0,
+        tokens_claimed: 0,
+        is_refunded: false,
+        presale_contributed: 0,
+    })
+}
+
+// Loads the project's presale round, verifying its PDA
+fn load_sale_round(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    sale_round_account: &AccountInfo,
+) -> Result<SaleRound, ProgramError> {
+    let (expected_sale_round_pda, _) = Pubkey::find_program_address(
+        &[b"sale_round", project_account.key.as_ref()],
+        program_id,
+    );
+
+    if expected_sale_round_pda != *sale_round_account.key
+        || sale_round_account.owner != program_id
+    {
+        msg!(
+            "Invalid sale round PDA. Expected: {}, Got: {}",
+            expected_sale_round_pda,
+            sale_round_account.key
+        );
+        return Err(UnicornFactoryError::InvalidSaleRound.into());
+    }
+
+    let sale_round_data = sale_round_account.data.borrow();
+    SaleRound::unpack(&sale_round_data)
+}
+
+// Verifies a funding round PDA for `index` and loads it
+fn load_funding_round(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    round_account: &AccountInfo,
+    index: u8,
+) -> Result<FundingRound, ProgramError> {
+    let (expected_round_pda, _) = Pubkey::find_program_address(
+        &[b"round", project_account.key.as_ref(), &[index]],
+        program_id,
+    );
+
+    if expected_round_pda != *round_account.key || round_account.owner != program_id {
+        msg!(
+            "Invalid funding round PDA. Expected: {}, Got: {}",
+            expected_round_pda,
+            round_account.key
+        );
+        return Err(UnicornFactoryError::InvalidFundingRound.into());
+    }
+
+    let round_data = round_account.data.borrow();
+    FundingRound::unpack(&round_data)
+}
+
+// Returns the project's current funding round, advancing past rounds whose
+// window has closed. Each skipped round requires the following round account.
+fn current_funding_round<'a, 'b, I>(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    project: &mut Project,
+    account_info_iter: &mut I,
+    now: i64,
+) -> Result<(&'a AccountInfo<'b>, FundingRound), ProgramError>
+where
+    I: Iterator<Item = &'a AccountInfo<'b>>,
+{
+    loop {
+        if project.current_round >= project.round_count {
+            msg!("All funding rounds have closed");
+            return Err(UnicornFactoryError::RaiseClosed.into());
+        }
+
+        let round_account = next_account_info(account_info_iter)?;
+        let round = load_funding_round(
+            program_id,
+            project_account,
+            round_account,
+            project.current_round,
+        )?;
+
+        if now <= round.end_time {
+            return Ok((round_account, round));
+        }
+
+        msg!("Round {} expired at {}", round.index, round.end_time);
+        project.current_round += 1;
+    }
+}
+
+// Verifies a batch auction commitment PDA, returning its bump
+fn verify_commitment(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    user_account: &AccountInfo,
+    commitment_account: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (expected_commitment_pda, commitment_bump) = Pubkey::find_program_address(
+        &[
+            b"commitment",
+            project_account.key.as_ref(),
+            user_account.key.as_ref(),
+        ],
+        program_id,
+    );
+
+    if expected_commitment_pda != *commitment_account.key {
+        msg!(
+            "Invalid commitment PDA. Expected: {}, Got: {}",
+            expected_commitment_pda,
+            commitment_account.key
+        );
+        return Err(UnicornFactoryError::InvalidCommitment.into());
+    }
+    Ok(commitment_bump)
+}
+
+// A council needs distinct members, a threshold they can reach and a treasury
+fn verify_council_members(threshold: u8, members: &[Pubkey], treasury: &Pubkey) -> ProgramResult {
+    let mut unique_members = members.to_vec();
+    unique_members.sort();
+    unique_members.dedup();
+    if members.is_empty()
+        || members.len() > MAX_COUNCIL_MEMBERS
+        || unique_members.len() != members.len()
+        || threshold == 0
+        || threshold as usize > members.len()
+        || *treasury == Pubkey::default()
+    {
+        msg!(
+            "Invalid council. Members: {}, Threshold: {}",
+            members.len(),
+            threshold
+        );
+        return Err(UnicornFactoryError::InvalidCouncil.into());
+    }
+    Ok(())
+}
+
+// Helper functions for project authority
+//
+// The project authority is either a wallet that signs directly or a council
+// PDA, in which case a threshold of council members must sign anywhere in the
+// instruction's accounts.
+fn load_council(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    council_account: &AccountInfo,
+) -> Result<ProjectCouncil, ProgramError> {
+    let (expected_council_pda, _) =
+        Pubkey::find_program_address(&[b"council", project_account.key.as_ref()], program_id);
+
+    if expected_council_pda != *council_account.key || council_account.owner != program_id {
+        msg!(
+            "Invalid council PDA. Expected: {}, Got: {}",
+            expected_council_pda,
+            council_account.key
+        );
+        return Err(UnicornFactoryError::InvalidCouncil.into());
+    }
+
+    let council_data = council_account.data.borrow();
+    ProjectCouncil::unpack(&council_data)
+}
+
+// Verifies `authority_account` may act as `expected`, returning a signer that
+// can pay for any accounts the instruction creates
+fn authorize_as<'a, 'b>(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    expected: &Pubkey,
+    authority_account: &'a AccountInfo<'b>,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<&'a AccountInfo<'b>, ProgramError> {
+    if authority_account.key != expected {
+        msg!("Invalid authority. Expected: {}", expected);
+        return Err(UnicornFactoryError::InvalidAuthority.into());
+    }
+
+    if authority_account.is_signer {
+        return Ok(authority_account);
+    }
+
+    let council = load_council(program_id, project_account, authority_account)?;
+    let mut signers: Vec<&AccountInfo> = Vec::new();
+    for account in accounts {
+        if account.is_signer
+            && council.members.contains(account.key)
+            && !signers.iter().any(|signer| signer.key == account.key)
+        {
+            signers.push(account);
+        }
+    }
+
+    if signers.len() < council.threshold as usize {
+        msg!(
+            "Council approval missing. Signatures: {}, Threshold: {}",
+            signers.len(),
+            council.threshold
+        );
+        return Err(UnicornFactoryError::InsufficientCouncilSignatures.into());
+    }
+
+    Ok(signers[0])
+}
+
+fn authorize<'a, 'b>(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    project: &Project,
+    authority_account: &'a AccountInfo<'b>,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<&'a AccountInfo<'b>, ProgramError> {
+    authorize_as(
+        program_id,
+        project_account,
+        &project.authority,
+        authority_account,
+        accounts,
+    )
+}
+
+// Proposals come from the project authority, or from any holder with at least
+// `proposal_threshold` escrowed project tokens who puts `proposal_deposit` in
+// the vault. Holders pass their voter escrow and the vault after the proposal
+// accounts, plus their quote token account for SPL quote mints. A holder has
+// one live proposal at a time: their escrow stays locked, and cannot propose
+// again, until the new proposal's tally closes at `tally_end`.
+// Returns the payer and the deposit taken.
+fn authorize_proposer<'a, 'b>(
+    program_id: &Pubkey,
+    project_account: &'a AccountInfo<'b>,
+    project: &mut Project,
+    proposer_account: &'a AccountInfo<'b>,
+    accounts: &'a [AccountInfo<'b>],
+    account_info_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    tally_end: i64,
+) -> Result<(&'a AccountInfo<'b>, u64), ProgramError> {
+    if *proposer_account.key == project.authority {
+        let payer_account =
+            authorize(program_id, project_account, project, proposer_account, accounts)?;
+        return Ok((payer_account, 0));
+    }
+
+    if project.governance.proposal_threshold == 0 {
+        msg!("Holder proposals are disabled for this project");
+        return Err(UnicornFactoryError::InvalidAuthority.into());
+    }
+
+    if !proposer_account.is_signer {
+        msg!("Proposer is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let proposer_escrow_account = next_account_info(account_info_iter)?;
+    msg!("Proposer Escrow key: {}", proposer_escrow_account.key);
+
+    let vault_account = next_account_info(account_info_iter)?;
+    msg!("Vault Account key: {}", vault_account.key);
+
+    let proposer_quote_account = if project.is_native_quote() {
+        proposer_account
+    } else {
+        let quote_account = next_account_info(account_info_iter)?;
+        msg!("Proposer Quote Account key: {}", quote_account.key);
+        quote_account
+    };
+
+    // The last proposal slots are kept for the authority
+    if project.proposal_count >= u8::MAX - AUTHORITY_PROPOSAL_SLOTS {
+        msg!("Holder proposals are exhausted for this project");
+        return Err(UnicornFactoryError::ProposalLimitReached.into());
+    }
+
+    let balance = voting_weight(
+        program_id,
+        project_account,
+        proposer_escrow_account,
+        proposer_account.key,
+        tally_end,
+    )?;
+    if balance < project.governance.proposal_threshold {
+        msg!(
+            "Proposer holds {} tokens, {} needed to propose",
+            balance,
+            project.governance.proposal_threshold
+        );
+        return Err(UnicornFactoryError::ProposalThresholdNotMet.into());
+    }
+
+    let mut escrow_data = proposer_escrow_account.try_borrow_mut_data()?;
+    let mut escrow = VoterEscrow::unpack(&escrow_data)?;
+    let now = Clock::get()?.unix_timestamp;
+    if now <= escrow.proposal_until {
+        msg!(
+            "Proposer already has a live proposal until {}",
+            escrow.proposal_until
+        );
+        return Err(UnicornFactoryError::ProposalLimitReached.into());
+    }
+    escrow.proposal_until = tally_end;
+    escrow.pack(&mut escrow_data);
+    drop(escrow_data);
+
+    let deposit = project.governance.proposal_deposit;
+    if deposit > 0 {
+        verify_vault(program_id, project_account, project, vault_account)?;
+        deposit_to_vault(
+            project,
+            proposer_account,
+            proposer_quote_account,
+            vault_account,
+            deposit,
+        )?;
+        project.held_deposits = project
+            .held_deposits
+            .checked_add(deposit)
+            .ok_or(UnicornFactoryError::Overflow)?;
+    }
+
+    Ok((proposer_account, deposit))
+}
+
+// Loads the project held by an account owned by this program
+fn load_project(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+) -> Result<Project, ProgramError> {
+    if project_account.owner != program_id {
+        msg!(
+            "Project account {} is not owned by this program",
+            project_account.key
+        );
+        return Err(UnicornFactoryError::InvalidProjectAccount.into());
+    }
+
+    let project_data = project_account.try_borrow_data()?;
+    Project::unpack(&project_data)
+}
+
+// Verifies a proposal PDA for `proposal_id` and loads it
+fn load_proposal(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    proposal_account: &AccountInfo,
+    proposal_id: u64,
+) -> Result<Proposal, ProgramError> {
+    let (expected_proposal_pda, _bump) = Pubkey::find_program_address(
+        &[
+            b"proposal",
+            project_account.key.as_ref(),
+            &[proposal_id as u8],
+        ],
+        program_id,
+    );
+
+    if expected_proposal_pda != *proposal_account.key {
+        msg!(
+            "Invalid proposal account PDA. Expected: {}, Got: {}",
+            expected_proposal_pda,
+            proposal_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let proposal_data = proposal_account.data.borrow();
+    Proposal::unpack(&proposal_data)
+}
+
+// Creates the PDA for the project's next milestone and books its allocation
+fn create_milestone_account<'a>(
+    program_id: &Pubkey,
+    project_account: &AccountInfo<'a>,
+    project: &mut Project,
+    milestone_account: &AccountInfo<'a>,
+    payer_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    milestone: &Milestone,
+) -> ProgramResult {
+    let milestone_index = project.milestone_count;
+
+    if milestone.amount == 0 {
+        msg!("Milestone amount must be greater than zero");
+        return Err(UnicornFactoryError::InvalidAmount.into());
+    }
+
+    if milestone.deadline != 0 && milestone.deadline <= Clock::get()?.unix_timestamp {
+        msg!("Milestone deadline {} has already passed", milestone.deadline);
+        return Err(UnicornFactoryError::InvalidMilestone.into());
+    }
+
+    // Several tranches need an interval between their unlock times
+    if milestone.tranche_count == 0
+        || milestone.tranche_count > MAX_MILESTONE_TRANCHES
+        || milestone.tranche_interval < 0
+        || (milestone.tranche_count > 1 && milestone.tranche_interval == 0)
+    {
+        msg!(
+            "Invalid tranche schedule: {} tranches every {} seconds",
+            milestone.tranche_count,
+            milestone.tranche_interval
+        );
+        return Err(UnicornFactoryError::InvalidMilestone.into());
+    }
+
+    // Verify milestone allocations stay within the funding goal, less the share
+    // reserved for the graduation pool
+    let total_allocated = project
+        .total_allocated
+        .checked_add(milestone.amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    let allocatable = project.funding_goal - project.graduation_amount(project.funding_goal);
+    if total_allocated > allocatable {
+        msg!(
+            "Milestone allocations exceed funding goal. Allocated: {}, Allocatable: {}",
+            total_allocated,
+            allocatable
+        );
+        return Err(UnicornFactoryError::MilestoneAllocationExceedsGoal.into());
+    }
+
+    // Verify milestone PDA
+    let (expected_milestone_pda, milestone_bump) = Pubkey::find_program_address(
+        &[
+            b"milestone",
+            project_account.key.as_ref(),
+            &[milestone_index],
+        ],
+        program_id,
+    );
+
+    if expected_milestone_pda != *milestone_account.key {
+        msg!(
+            "Invalid milestone account PDA. Expected: {}, Got: {}",
+            expected_milestone_pda,
+            milestone_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Verify milestone account is not already initialized
+    if milestone_account.data.borrow().iter().any(|&x| x != 0) {
+        msg!("Milestone account already initialized");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // Calculate rent
+    let rent = Rent::get()?;
+    let space = Milestone::LEN;
+    let lamports = rent.minimum_balance(space);
+
+    // Create milestone account using invoke_signed
+    let milestone_seeds = &[
+        b"milestone".as_ref(),
+        project_account.key.as_ref(),
+        &[milestone_index],
+        &[milestone_bump],
+    ];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer_account.key,
+            milestone_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        ),
+        &[
+            payer_account.clone(),
+            milestone_account.clone(),
+            system_program.clone(),
+        ],
+        &[milestone_seeds],
+    )?;
+
+    {
+        let mut milestone_data_buffer = milestone_account.data.borrow_mut();
+        milestone.pack(&mut milestone_data_buffer);
+    }
+
+    // Update project milestone count
+    project.milestone_count += 1;
+    project.total_allocated = total_allocated;
+    Ok(())
+}
+
+// Verifies a milestone PDA for `milestone_id`
+fn verify_milestone(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    milestone_account: &AccountInfo,
+    milestone_id: u8,
+) -> ProgramResult {
+    let (expected_milestone_pda, _bump) = Pubkey::find_program_address(
+        &[b"milestone", project_account.key.as_ref(), &[milestone_id]],
+        program_id,
+    );
+
+    if expected_milestone_pda != *milestone_account.key {
+        msg!(
+            "Invalid milestone account PDA. Expected: {}, Got: {}",
+            expected_milestone_pda,
+            milestone_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+// Checks a milestone's current proposal has passed and can take effect now.
+// Shared by CompleteMilestone and ReleaseFunds.
+fn verify_milestone_proposal(
+    project: &Project,
+    proposal: &Proposal,
+    proposal_id: u64,
+    milestone: &Milestone,
+    now: i64,
+) -> ProgramResult {
+    if !matches!(
+        milestone.status,
+        MilestoneStatus::Proposed | MilestoneStatus::Approved
+    ) {
+        msg!(
+            "Milestone {} has status {:?}",
+            proposal.milestone_id,
+            milestone.status
+        );
+        return Err(milestone.status.error().into());
+    }
+
+    // Proposals from before a missed deadline are superseded by the fresh one
+    if proposal_id != milestone.proposal_id as u64 {
+        msg!(
+            "Proposal {} is not the current proposal {} of milestone {}",
+            proposal_id,
+            milestone.proposal_id,
+            proposal.milestone_id
+        );
+        return Err(UnicornFactoryError::MilestoneMissed.into());
+    }
+
+    if proposal.is_executed {
+        msg!("Proposal {} is already executed", proposal_id);
+        return Err(UnicornFactoryError::ProposalAlreadyExecuted.into());
+    }
+
+    // Check if voting period has ended
+    if now <= proposal.tally_end() {
+        msg!(
+            "Voting period for proposal {} has not ended yet",
+            proposal_id
+        );
+        return Err(UnicornFactoryError::VotingPeriodNotEnded.into());
+    }
+
+    if proposal.is_multi_choice() || proposal.action != ProposalAction::None {
+        msg!("Proposal {} is executed with Execute", proposal_id);
+        return Err(UnicornFactoryError::InvalidProposalType.into());
+    }
+
+    if !proposal.has_quorum() {
+        msg!(
+            "Proposal {} did not reach quorum of {}",
+            proposal_id,
+            proposal.quorum_votes
+        );
+        return Err(UnicornFactoryError::QuorumNotReached.into());
+    }
+
+    // Check if proposal has won (yes votes > no votes)
+    if !proposal.passed() {
+        msg!("Proposal {} did not win the vote", proposal_id);
+        return Err(UnicornFactoryError::ProposalDidNotPass.into());
+    }
+
+    if proposal.is_cancelled {
+        msg!("Proposal {} was cancelled", proposal_id);
+        return Err(UnicornFactoryError::ProposalCancelled.into());
+    }
+
+    // An approved milestone pays its remaining tranches on schedule
+    if milestone.approved_at != 0 {
+        return Ok(());
+    }
+
+    // Enforce the timelock and the execution window
+    let executable_at = proposal.executable_at(&project.governance);
+    if now <= executable_at {
+        msg!(
+            "Proposal {} is timelocked until {}",
+            proposal_id,
+            executable_at
+        );
+        return Err(UnicornFactoryError::TimelockActive.into());
+    }
+
+    if proposal.is_expired(&project.governance, now) {
+        msg!("Proposal {} expired before execution", proposal_id);
+        return Err(UnicornFactoryError::ProposalExpired.into());
+    }
+    Ok(())
+}
+
+// Milestone events for off-chain indexers, emitted as `Program data:` logs of
+// the event name, project, milestone id and a timestamp
+fn emit_milestone_event(event: &[u8], project: &Pubkey, milestone_id: u8, timestamp: i64) {
+    sol_log_data(&[
+        event,
+        project.as_ref(),
+        &[milestone_id],
+        &timestamp.to_le_bytes(),
+    ]);
+}
+
+// Evidence has to point at content-addressed or web storage that fits the account
+fn is_evidence_uri(uri: &str) -> bool {
+    uri.len() <= MAX_EVIDENCE_URI_LEN
+        && ["ipfs://", "ar://", "https://"]
+            .iter()
+            .any(|scheme| uri.len() > scheme.len() && uri.starts_with(scheme))
+}
+
+// Votes a proposal needs for quorum, fixed from the token supply at creation
+fn quorum_votes(project: &Project, project_token: &AccountInfo) -> Result<u64, ProgramError> {
+    if project.token_mint != *project_token.key {
+        msg!(
+            "Project token mint mismatch. Project mint: {}, Provided mint: {}",
+            project.token_mint,
+            project_token.key
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let supply = {
+        let mint_data = project_token.try_borrow_data()?;
+        spl_token::state::Mint::unpack(&mint_data)?.supply
+    };
+    Ok((supply as u128 * project.governance.quorum_bps as u128 / 10_000) as u64)
+}
+
+// Creates the PDA for the project's next proposal and bumps proposal_count
+fn create_proposal_account<'a>(
+    program_id: &Pubkey,
+    project_account: &AccountInfo<'a>,
+    project: &mut Project,
+    proposal_account: &AccountInfo<'a>,
+    payer_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    proposal: &Proposal,
+) -> ProgramResult {
+    // Determine the index for the new proposal
+    let proposal_index = project.proposal_count;
+    msg!("New proposal index: {}", proposal_index);
+    let proposal_count = proposal_index
+        .checked_add(1)
+        .ok_or(UnicornFactoryError::ProposalLimitReached)?;
+
+    // Find Proposal PDA and bump
+    let (expected_proposal_pda, proposal_bump) = Pubkey::find_program_address(
+        &[b"proposal", project_account.key.as_ref(), &[proposal_index]],
+        program_id,
+    );
+
+    // Verify the provided proposal account is the expected PDA
+    if expected_proposal_pda != *proposal_account.key {
+        msg!(
+            "Invalid proposal account PDA. Expected: {}, Got: {}",
+            expected_proposal_pda,
+            proposal_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Verify proposal account is not already initialized
+    if proposal_account.data.borrow().iter().any(|&x| x != 0) {
+        msg!("Proposal account already initialized");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // Calculate account size and rent
+    let rent = Rent::get()?;
+    let space = Proposal::LEN;
+    let lamports = rent.minimum_balance(space);
+
+    msg!("Proposal account space: {}, Lamports: {}", space, lamports);
+
+    // Create proposal account using invoke_signed
+    let proposal_seeds = &[
+        b"proposal".as_ref(),
+        project_account.key.as_ref(),
+        &[proposal_index],
+        &[proposal_bump],
+    ];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer_account.key,
+            proposal_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        ),
+        &[
+            payer_account.clone(),
+            proposal_account.clone(),
+            system_program.clone(),
+        ],
+        &[proposal_seeds],
+    )?;
+
+    msg!("Proposal account created successfully");
+
+    // Pack proposal data into the new account
+    let mut proposal_data_buffer = proposal_account.data.borrow_mut();
+    proposal.pack(&mut proposal_data_buffer);
+    drop(proposal_data_buffer);
+
+    // Increment proposal count in project account
+    project.proposal_count = proposal_count;
+    Ok(())
+}
+
+// Helper functions for token-weighted voting
+//
+// Votes are weighted by the holder's escrowed project tokens. Every holder
+// whose weight lands on a proposal gets a vote record, so an escrow is only
+// counted once per proposal whether it was cast directly or through a
+// delegate, and it stays locked until `lock_until` so it cannot move to
+// another wallet and be counted again. Holders without an escrow weigh nothing.
+fn voting_weight(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    escrow_account: &AccountInfo,
+    holder: &Pubkey,
+    lock_until: i64,
+) -> Result<u64, ProgramError> {
+    verify_voter_escrow(program_id, project_account, holder, escrow_account)?;
+    if escrow_account.data_is_empty() {
+        return Ok(0);
+    }
+
+    let mut escrow_data = escrow_account.try_borrow_mut_data()?;
+    let mut escrow = VoterEscrow::unpack(&escrow_data)?;
+    if lock_until > escrow.locked_until {
+        escrow.locked_until = lock_until;
+        escrow.pack(&mut escrow_data);
+    }
+    Ok(escrow.amount)
+}
+
+// Verifies the project's escrow vault PDA, returning its bump
+fn verify_escrow_vault(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    escrow_vault: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (expected_vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"escrow_vault", project_account.key.as_ref()],
+        program_id,
+    );
+
+    if expected_vault_pda != *escrow_vault.key {
+        msg!(
+            "Invalid escrow vault PDA. Expected: {}, Got: {}",
+            expected_vault_pda,
+            escrow_vault.key
+        );
+        return Err(UnicornFactoryError::InvalidVoterEscrow.into());
+    }
+    Ok(vault_bump)
+}
+
+// Verifies a voter escrow PDA, returning its bump
+fn verify_voter_escrow(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    holder: &Pubkey,
+    escrow_account: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (expected_escrow_pda, escrow_bump) = Pubkey::find_program_address(
+        &[b"escrow", project_account.key.as_ref(), holder.as_ref()],
+        program_id,
+    );
+
+    if expected_escrow_pda != *escrow_account.key
+        || (!escrow_account.data_is_empty() && escrow_account.owner != program_id)
+    {
+        msg!(
+            "Invalid voter escrow PDA. Expected: {}, Got: {}",
+            expected_escrow_pda,
+            escrow_account.key
+        );
+        return Err(UnicornFactoryError::InvalidVoterEscrow.into());
+    }
+    Ok(escrow_bump)
+}
+
+fn verify_vote_record(
+    program_id: &Pubkey,
+    proposal_account: &AccountInfo,
+    voter: &Pubkey,
+    vote_record_account: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (expected_vote_pda, vote_bump) = Pubkey::find_program_address(
+        &[b"vote", proposal_account.key.as_ref(), voter.as_ref()],
+        program_id,
+    );
+
+    if expected_vote_pda != *vote_record_account.key {
+        msg!(
+            "Invalid vote record PDA. Expected: {}, Got: {}",
+            expected_vote_pda,
+            vote_record_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(vote_bump)
+}
+
+fn create_vote_record<'a>(
+    program_id: &Pubkey,
+    vote_record_account: &AccountInfo<'a>,
+    vote_bump: u8,
+    payer_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    record: &VoteRecord,
+) -> ProgramResult {
+    let rent = Rent::get()?;
+    let space = VoteRecord::LEN;
+    let lamports = rent.minimum_balance(space);
+
+    let vote_seeds = &[
+        b"vote".as_ref(),
+        record.proposal.as_ref(),
+        record.voter.as_ref(),
+        &[vote_bump],
+    ];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer_account.key,
+            vote_record_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        ),
+        &[
+            payer_account.clone(),
+            vote_record_account.clone(),
+            system_program.clone(),
+        ],
+        &[vote_seeds],
+    )?;
+
+    let mut vote_data = vote_record_account.data.borrow_mut();
+    record.pack(&mut vote_data);
+    Ok(())
+}
+
+fn add_vote_weight(proposal: &mut Proposal, choice: u8, weight: u64) -> ProgramResult {
+    let tally = proposal
+        .tally_mut(choice)
+        .ok_or(UnicornFactoryError::InvalidVoteChoice)?;
+    *tally = tally
+        .checked_add(weight)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    Ok(())
+}
+
+fn remove_vote_weight(proposal: &mut Proposal, choice: u8, weight: u64) {
+    if let Some(tally) = proposal.tally_mut(choice) {
+        *tally = tally.saturating_sub(weight);
+    }
+}
+
+// Commitment a secret ballot is revealed against. The voter key is hashed in
+// so one holder cannot copy another's commitment and reveal it themselves.
+fn ballot_commitment(choice: u8, salt: &[u8; 32], voter: &Pubkey) -> [u8; 32] {
+    keccak::hashv(&[&[choice], salt, voter.as_ref()]).0
+}
+
+// Votes can only be cast or revised on live proposals before voting_end
+fn verify_voting_open(proposal: &Proposal) -> ProgramResult {
+    if proposal.is_executed {
+        msg!("Proposal is already executed");
+        return Err(UnicornFactoryError::ProposalAlreadyExecuted.into());
+    }
+
+    if proposal.is_cancelled {
+        msg!("Proposal was cancelled");
+        return Err(UnicornFactoryError::ProposalCancelled.into());
+    }
+
+    // Check if voting period has ended
+    let clock = Clock::get()?;
+    if clock.unix_timestamp > proposal.voting_end {
+        msg!("Voting period has ended");
+        return Err(UnicornFactoryError::VotingPeriodEnded.into());
+    }
+
+    Ok(())
+}
+
+// Closes a program-owned record and returns its rent to `recipient`
+fn close_record_account(account: &AccountInfo, recipient: &AccountInfo) -> ProgramResult {
+    let recipient_lamports = recipient
+        .lamports()
+        .checked_add(account.lamports())
+        .ok_or(UnicornFactoryError::Overflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+    **recipient.try_borrow_mut_lamports()? = recipient_lamports;
+    account.try_borrow_mut_data()?.fill(0);
+    Ok(())
+}
+
+fn verify_delegation_record(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    delegator: &Pubkey,
+    delegation_account: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (expected_delegation_pda, delegation_bump) = Pubkey::find_program_address(
+        &[
+            b"delegation",
+            project_account.key.as_ref(),
+            delegator.as_ref(),
+        ],
+        program_id,
+    );
+
+    if expected_delegation_pda != *delegation_account.key {
+        msg!(
+            "Invalid delegation record PDA. Expected: {}, Got: {}",
+            expected_delegation_pda,
+            delegation_account.key
+        );
+        return Err(UnicornFactoryError::InvalidDelegation.into());
+    }
+
+    Ok(delegation_bump)
+}
+
+// Helper functions for the project vault
+//
+// The vault is a PDA derived from [b"vault", project] that holds every unit of
+// quote asset raised by the project. For native SOL projects it is a system-owned
+// lamport account that never drops below its rent-exempt minimum; for projects
+// raising in an SPL token it is a token account for the quote mint owned by the
+// project PDA. The project state account only keeps its own rent.
+fn verify_vault(
+    program_id: &Pubkey,
+    project_account: &AccountInfo,
+    project: &Project,
+    vault_account: &AccountInfo,
+) -> ProgramResult {
+    let expected_vault = Pubkey::create_program_address(
+        &[
+            b"vault",
+            project_account.key.as_ref(),
+            &[project.vault_bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| UnicornFactoryError::InvalidVaultAccount)?;
+
+    if expected_vault != *vault_account.key {
+        msg!(
+            "Invalid vault account. Expected: {}, Got: {}",
+            expected_vault,
+            vault_account.key
+        );
+        return Err(UnicornFactoryError::InvalidVaultAccount.into());
+    }
+    Ok(())
+}
+
+// Quote asset held by the vault, excluding the rent-exempt minimum of a SOL vault
+fn vault_balance(project: &Project, vault_account: &AccountInfo) -> Result<u64, ProgramError> {
+    if project.is_native_quote() {
+        let rent = Rent::get()?;
+        return Ok(vault_account
+            .lamports()
+            .saturating_sub(rent.minimum_balance(0)));
+    }
+
+    let vault_data = vault_account.try_borrow_data()?;
+    let vault_info = spl_token::state::Account::unpack(&vault_data)?;
+    Ok(vault_info.amount)
+}
+
+// Moves quote asset from `payer` into the vault. For SPL quote mints
+// `payer_quote_account` is the payer's token account; for native SOL it is the
+// payer itself.
+fn deposit_to_vault<'a>(
+    project: &Project,
+    payer: &AccountInfo<'a>,
+    payer_quote_account: &AccountInfo<'a>,
+    vault_account: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    if project.is_native_quote() {
+        return invoke(
+            &system_instruction::transfer(payer.key, vault_account.key, amount),
+            &[payer.clone(), vault_account.clone()],
+        );
+    }
+
+    invoke(
+        &token_instruction::transfer(
+            &spl_token::id(),
+            payer_quote_account.key,
+            vault_account.key,
+            payer.key,
+            &[],
+            amount,
+        )?,
+        &[
+            payer_quote_account.clone(),
+            vault_account.clone(),
+            payer.clone(),
+        ],
+    )
+}
+
+// Pays quote asset out of the vault. For SPL quote mints `to` is the recipient's
+// token account; for native SOL it is the recipient wallet.
+fn withdraw_from_vault<'a>(
+    project_account: &AccountInfo<'a>,
+    project: &Project,
+    vault_account: &AccountInfo<'a>,
+    to: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    let available = vault_balance(project, vault_account)?;
+    if amount > available {
+        msg!(
+            "Vault insufficient balance. Available: {}, Requested: {}",
+            available,
+            amount
+        );
+        return Err(UnicornFactoryError::InsufficientTreasury.into());
+    }
+
+    if project.is_native_quote() {
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            project_account.key.as_ref(),
+            &[project.vault_bump],
+        ];
+
+        return invoke_signed(
+            &system_instruction::transfer(vault_account.key, to.key, amount),
+            &[vault_account.clone(), to.clone()],
+            &[vault_seeds],
+        );
+    }
+
+    let project_seeds = &[
+        b"project".as_ref(),
+        project.creator.as_ref(),
+        &[project.bump],
+    ];
+
+    invoke_signed(
+        &token_instruction::transfer(
+            &spl_token::id(),
+            vault_account.key,
+            to.key,
+            project_account.key,
+            &[],
+            amount,
+        )?,
+        &[vault_account.clone(), to.clone(), project_account.clone()],
+        &[project_seeds],
+    )
+}
+
+// Main program logic
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = UnicornFactoryInstruction::unpack(instruction_data)?;
+
+    match instruction {
+        UnicornFactoryInstruction::InitializeProject {
+            name,
+            symbol,
+            funding_goal,
+            config,
+        } => {
+            msg!("Instruction: Initialize Project");
+            process_initialize_project(program_id, accounts, name, symbol, funding_goal, config)
+        }
+        UnicornFactoryInstruction::Contribute { amount, proof } => {
+            msg!("Instruction: Contribute");
+            process_contribute(program_id, accounts, amount, proof)
+        }
+        UnicornFactoryInstruction::BuyTokens { amount } => {
+            msg!("Instruction: Buy Tokens");
+            process_buy_tokens(program_id, accounts, amount)
+        }
+        UnicornFactoryInstruction::SellTokens { amount } => {
+            msg!("Instruction: Sell Tokens");
+            process_sell_tokens(program_id, accounts, amount)
+        }
+        UnicornFactoryInstruction::CreateProposal {
+            title,
+            description,
+            milestone_id,
+        } => {
+            msg!("Instruction: Create Proposal");
+            process_create_proposal(
+                program_id,
+                accounts,
+                title,
+                description,
+                milestone_id,
+            )
+        }
+        UnicornFactoryInstruction::Vote {
+            proposal_id,
+            choice,
+        } => {
+            msg!("Instruction: Vote");
+            process_vote(program_id, accounts, proposal_id, choice, [0; 32])
+        }
+        UnicornFactoryInstruction::ReleaseFunds { proposal_id } => {
+            msg!("Instruction: Release Funds");
+            process_release_funds(program_id, accounts, proposal_id)
+        }
+        UnicornFactoryInstruction::AddMilestone {
+            title,
+            description,
+            amount,
+            deadline,
+            tranche_count,
+            tranche_interval,
+        } => {
+            msg!("Instruction: Add Milestone");
+            let milestone = Milestone::new(
+                title,
+                description,
+                amount,
+                deadline,
+                tranche_count,
+                tranche_interval,
+            );
+            process_add_milestone(program_id, accounts, milestone)
+        }
+        UnicornFactoryInstruction::CompleteMilestone { milestone_id } => {
+            msg!("Instruction: Complete Milestone");
+            process_complete_milestone(program_id, accounts, milestone_id)
+        }
+        UnicornFactoryInstruction::CreateVesting {
+            total_amount,
+            start_time,
+            cliff_time,
+            end_time,
+            revocable,
+        } => {
+            msg!("Instruction: Create Vesting");
+            process_create_vesting(
+                program_id,
+                accounts,
+                total_amount,
+                start_time,
+                cliff_time,
+                end_time,
+                revocable,
+            )
+        }
+        UnicornFactoryInstruction::ClaimVested => {
+            msg!("Instruction: Claim Vested");
+            process_claim_vested(program_id, accounts)
+        }
+        UnicornFactoryInstruction::RevokeVesting => {
+            msg!("Instruction: Revoke Vesting");
+            process_revoke_vesting(program_id, accounts)
+        }
+        UnicornFactoryInstruction::FinalizeRaise => {
+            msg!("Instruction: Finalize Raise");
+            process_finalize_raise(program_id, accounts)
+        }
+        UnicornFactoryInstruction::ClaimTokens => {
+            msg!("Instruction: Claim Tokens");
+            process_claim_tokens(program_id, accounts)
+        }
+        UnicornFactoryInstruction::Refund => {
+            msg!("Instruction: Refund");
+            process_refund(program_id, accounts)
+        }
+        UnicornFactoryInstruction::Graduate => {
+            msg!("Instruction: Graduate");
+            process_graduate(program_id, accounts)
+        }
+        UnicornFactoryInstruction::Swap {
+            amount_in,
+            min_amount_out,
+            quote_to_token,
+        } => {
+            msg!("Instruction: Swap");
+            amm::process_swap(program_id, accounts, amount_in, min_amount_out, quote_to_token)
+        }
+        UnicornFactoryInstruction::AddLiquidity {
+            token_amount,
+            max_quote_amount,
+            min_lp_amount,
+        } => {
+            msg!("Instruction: Add Liquidity");
+            amm::process_add_liquidity(
+                program_id,
+                accounts,
+                token_amount,
+                max_quote_amount,
+                min_lp_amount,
+            )
+        }
+        UnicornFactoryInstruction::RemoveLiquidity {
+            lp_amount,
+            min_token_amount,
+            min_quote_amount,
+        } => {
+            msg!("Instruction: Remove Liquidity");
+            amm::process_remove_liquidity(
+                program_id,
+                accounts,
+                lp_amount,
+                min_token_amount,
+                min_quote_amount,
+            )
+        }
+        UnicornFactoryInstruction::ConfigureSaleRound {
+            start_time,
+            end_time,
+            price,
+            per_wallet_cap,
+            merkle_root,
+        } => {
+            msg!("Instruction: Configure Sale Round");
+            process_configure_sale_round(
+                program_id,
+                accounts,
+                start_time,
+                end_time,
+                price,
+                per_wallet_cap,
+                merkle_root,
+            )
+        }
+        UnicornFactoryInstruction::AddFundingRound { params } => {
+            msg!("Instruction: Add Funding Round");
+            process_add_funding_round(program_id, accounts, params)
+        }
+        UnicornFactoryInstruction::SettleBid => {
+            msg!("Instruction: Settle Bid");
+            process_settle_bid(program_id, accounts)
+        }
+        UnicornFactoryInstruction::Commit { amount } => {
+            msg!("Instruction: Commit");
+            process_commit(program_id, accounts, amount)
+        }
+        UnicornFactoryInstruction::Settle => {
+            msg!("Instruction: Settle");
+            process_settle(program_id, accounts)
+        }
+        UnicornFactoryInstruction::ClaimCommitment => {
+            msg!("Instruction: Claim Commitment");
+            process_claim_commitment(program_id, accounts)
+        }
+        UnicornFactoryInstruction::CreateCouncil {
+            threshold,
+            members,
+            treasury,
+        } => {
+            msg!("Instruction: Create Council");
+            process_create_council(program_id, accounts, threshold, members, treasury)
+        }
+        UnicornFactoryInstruction::TransferAuthority { new_authority } => {
+            msg!("Instruction: Transfer Authority");
+            process_transfer_authority(program_id, accounts, new_authority)
+        }
+        UnicornFactoryInstruction::AcceptAuthority => {
+            msg!("Instruction: Accept Authority");
+            process_accept_authority(program_id, accounts)
+        }
+        UnicornFactoryInstruction::CancelProposal { proposal_id } => {
+            msg!("Instruction: Cancel Proposal");
+            process_cancel_proposal(program_id, accounts, proposal_id)
+        }
+        UnicornFactoryInstruction::VetoVote { proposal_id } => {
+            msg!("Instruction: Veto Vote");
+            process_veto_vote(program_id, accounts, proposal_id)
+        }
+        UnicornFactoryInstruction::Delegate { delegate } => {
+            msg!("Instruction: Delegate");
+            process_delegate(program_id, accounts, delegate)
+        }
+        UnicornFactoryInstruction::Undelegate => {
+            msg!("Instruction: Undelegate");
+            process_undelegate(program_id, accounts)
+        }
+        UnicornFactoryInstruction::ChangeVote {
+            proposal_id,
+            choice,
+        } => {
+            msg!("Instruction: Change Vote");
+            process_revise_vote(program_id, accounts, proposal_id, Some(choice))
+        }
+        UnicornFactoryInstruction::WithdrawVote { proposal_id } => {
+            msg!("Instruction: Withdraw Vote");
+            process_revise_vote(program_id, accounts, proposal_id, None)
+        }
+        UnicornFactoryInstruction::CreateChoiceProposal {
+            title,
+            description,
+            options,
+        } => {
+            msg!("Instruction: Create Choice Proposal");
+            process_create_choice_proposal(program_id, accounts, title, description, options)
+        }
+        UnicornFactoryInstruction::Execute { proposal_id } => {
+            msg!("Instruction: Execute");
+            process_execute(program_id, accounts, proposal_id)
+        }
+        UnicornFactoryInstruction::CommitVote {
+            proposal_id,
+            commitment,
+        } => {
+            msg!("Instruction: Commit Vote");
+            process_vote(program_id, accounts, proposal_id, VOTE_HIDDEN, commitment)
+        }
+        UnicornFactoryInstruction::RevealVote {
+            proposal_id,
+            choice,
+            salt,
+        } => {
+            msg!("Instruction: Reveal Vote");
+            process_reveal_vote(program_id, accounts, proposal_id, choice, salt)
+        }
+        UnicornFactoryInstruction::CreateActionProposal {
+            title,
+            description,
+            action,
+        } => {
+            msg!("Instruction: Create Action Proposal");
+            process_create_action_proposal(program_id, accounts, title, description, action)
+        }
+        UnicornFactoryInstruction::SettleDeposit { proposal_id } => {
+            msg!("Instruction: Settle Deposit");
+            process_settle_deposit(program_id, accounts, proposal_id)
+        }
+        UnicornFactoryInstruction::RedeemTreasury { amount } => {
+            msg!("Instruction: Redeem Treasury");
+            process_redeem_treasury(program_id, accounts, amount)
+        }
+        UnicornFactoryInstruction::CheckMilestoneDeadline { milestone_id } => {
+            msg!("Instruction: Check Milestone Deadline");
+            process_check_milestone_deadline(program_id, accounts, milestone_id)
+        }
+        UnicornFactoryInstruction::SubmitMilestoneEvidence {
+            milestone_id,
+            content_hash,
+            uri,
+        } => {
+            msg!("Instruction: Submit Milestone Evidence");
+            process_submit_milestone_evidence(program_id, accounts, milestone_id, content_hash, uri)
+        }
+        UnicornFactoryInstruction::MigrateProject => {
+            msg!("Instruction: Migrate Project");
+            process_migrate_project(program_id, accounts)
+        }
+        UnicornFactoryInstruction::UpdateCouncil {
+            threshold,
+            members,
+            treasury,
+        } => {
+            msg!("Instruction: Update Council");
+            process_update_council(program_id, accounts, threshold, members, treasury)
+        }
+        UnicornFactoryInstruction::DepositEscrow { amount } => {
+            msg!("Instruction: Deposit Escrow");
+            process_deposit_escrow(program_id, accounts, amount)
+        }
+        UnicornFactoryInstruction::WithdrawEscrow { amount } => {
+            msg!("Instruction: Withdraw Escrow");
+            process_withdraw_escrow(program_id, accounts, amount)
+        }
+    }
+}
+
+// Initialize project instruction processor
+fn process_initialize_project(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+    symbol: String,
+    funding_goal: u64,
+    config: ProjectConfig,
+) -> ProgramResult {
+    msg!("Starting project initialization");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let authority_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Authority Account key: {}",
+        authority_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: System Program key: {}",
+        system_program.key
+    );
+
+    let token_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Token Program key: {}",
+        token_program.key
+    );
+
+    let token_mint_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Token Mint Account key: {}",
+        token_mint_account.key
+    );
+
+    let vault_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Vault Account key: {}",
+        vault_account.key
+    );
+
+    // Verify authority is signer
+    if !authority_account.is_signer {
+        msg!("Authority is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Verify token program
+    if token_program.key != &spl_token::id() {
+        msg!("Invalid token program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Find PDA bump
+    let (pda, bump) =
+        Pubkey::find_program_address(&[b"project", authority_account.key.as_ref()], program_id);
+
+    msg!("Generated PDA: {}", pda);
+    msg!("Bump: {}", bump);
+
+    // Verify PDA matches
+    if pda != *project_account.key {
+        msg!(
+            "Invalid project account. Expected: {}, Got: {}",
+            pda,
+            project_account.key
+        );
+        return Err(UnicornFactoryError::InvalidProjectAccount.into());
+    }
+
+    // Verify project account is not already initialized
+    if project_account.data.borrow().iter().any(|&x| x != 0) {
+        msg!("Project account already initialized");
+        return Err(UnicornFactoryError::InvalidProjectAccount.into());
+    }
+
+    // Find vault PDA bump
+    let (vault_pda, vault_bump) =
+        Pubkey::find_program_address(&[b"vault", project_account.key.as_ref()], program_id);
+
+    if vault_pda != *vault_account.key {
+        msg!(
+            "Invalid vault account. Expected: {}, Got: {}",
+            vault_pda,
+            vault_account.key
+        );
+        return Err(UnicornFactoryError::InvalidVaultAccount.into());
+    }
+
+    if config.graduation_bps > 10_000 {
+        msg!("Graduation share cannot exceed 100%");
+        return Err(UnicornFactoryError::InvalidGraduationConfig.into());
+    }
+
+    // Pool tokens are minted on top of the sale, so they need a supply to fit in
+    if config.graduation_token_reserve > 0 && config.total_supply == 0 {
+        msg!("A graduation token reserve needs a total supply");
+        return Err(UnicornFactoryError::InvalidGraduationConfig.into());
+    }
+
+    // The vesting reserve is a share of the project's total token supply, and
+    // the supply must cover it alongside the auction and graduation pool tokens
+    if config.vesting_bps > 10_000 || (config.vesting_bps > 0 && config.total_supply == 0) {
+        msg!(
+            "Invalid vesting share {} of total supply {}",
+            config.vesting_bps,
+            config.total_supply
+        );
+        return Err(UnicornFactoryError::VestingReserveExceeded.into());
+    }
+    let vesting_reserve =
+        (config.total_supply as u128 * config.vesting_bps as u128 / 10_000) as u64;
+    let allocated_supply = vesting_reserve as u128
+        + config.auction_supply as u128
+        + config.graduation_token_reserve as u128;
+    if config.total_supply != 0 && allocated_supply > config.total_supply as u128 {
+        msg!(
+            "Total supply {} cannot cover vesting {}, auction {} and graduation {} tokens",
+            config.total_supply,
+            vesting_reserve,
+            config.auction_supply,
+            config.graduation_token_reserve
+        );
+        return Err(UnicornFactoryError::VestingReserveExceeded.into());
+    }
+
+    if config.governance.execution_delay < 0
+        || config.governance.execution_window < 0
+        || config.governance.veto_threshold_bps > 10_000
+        || config.governance.quorum_bps > 10_000
+        || config.governance.reveal_period < 0
+    {
+        msg!(
+            "Invalid governance config. delay={}, window={}, veto_bps={}, quorum_bps={}, reveal={}",
+            config.governance.execution_delay,
+            config.governance.execution_window,
+            config.governance.veto_threshold_bps,
+            config.governance.quorum_bps,
+            config.governance.reveal_period
+        );
+        return Err(UnicornFactoryError::InvalidAmount.into());
+    }
+
+    if config.max_contribution_per_wallet != 0
+        && config.min_contribution > config.max_contribution_per_wallet
+    {
+        msg!(
+            "Minimum contribution {} exceeds the per-wallet cap {}",
+            config.min_contribution,
+            config.max_contribution_per_wallet
+        );
+        return Err(UnicornFactoryError::InvalidAmount.into());
+    }
+
+    let invalid_auction = match config.launch_mode {
+        LaunchMode::BondingCurve => false,
+        LaunchMode::DutchAuction => {
+            config.auction_floor_price == 0
+                || config.auction_start_time >= config.auction_end_time
+                || config.auction_supply == 0
+        }
+        LaunchMode::BatchAuction => {
+            config.auction_start_time >= config.auction_end_time || config.auction_supply == 0
+        }
+    };
+    // The price decays from the start price down to the floor
+    if config.launch_mode == LaunchMode::DutchAuction
+        && config.auction_floor_price > config.auction_start_price
+    {
+        msg!(
+            "Auction floor price {} is above the start price {}",
+            config.auction_floor_price,
+            config.auction_start_price
+        );
+        return Err(UnicornFactoryError::InvalidAuctionConfig.into());
+    }
+
+    if invalid_auction {
+        msg!(
+            "Invalid auction. start_price={}, floor_price={}, start={}, end={}, supply={}",
+            config.auction_start_price,
+            config.auction_floor_price,
+            config.auction_start_time,
+            config.auction_end_time,
+            config.auction_supply
+        );
+        return Err(UnicornFactoryError::InvalidAuctionConfig.into());
+    }
+
+    // Verify quote mint for projects raising in an SPL token
+    let quote_mint = config.quote_mint;
+    let quote_mint_account = if quote_mint != Pubkey::default() {
+        let quote_mint_account = next_account_info(account_info_iter)?;
+        msg!(
+            "Processing account 6: Quote Mint Account key: {}",
+            quote_mint_account.key
+        );
+
+        if *quote_mint_account.key != quote_mint
+            || quote_mint_account.owner != &spl_token::id()
+        {
+            msg!("Invalid quote mint account");
+            return Err(UnicornFactoryError::InvalidQuoteMint.into());
+        }
+        Some(quote_mint_account)
+    } else {
+        None
+    };
+
+    // Create project account
+    let project = Project {
+        authority: *authority_account.key,
+        name: name.clone(),
+        symbol: symbol.clone(),
+        funding_goal,
+        total_raised: 0,
+        token_price: 1,
+        is_active: true,
+        bump,
+        token_mint: *token_mint_account.key,
+        milestone_count: 0,
+        proposal_count: 0,
+        layout_version: PROJECT_LAYOUT_VERSION,
+        total_allocated: 0,
+        total_released: 0,
+        vault_bump,
+        quote_mint,
+        vesting_reserve,
+        vesting_allocated: 0,
+        lockup_enabled: config.lockup_enabled,
+        raise_deadline: config.raise_deadline,
+        raise_state: RaiseState::Open,
+        graduation_bps: config.graduation_bps,
+        graduation_token_reserve: config.graduation_token_reserve,
+        is_graduated: false,
+        sale_round_end: 0,
+        min_contribution: config.min_contribution,
+        max_contribution_per_wallet: config.max_contribution_per_wallet,
+        round_count: 0,
+        current_round: 0,
+        launch_mode: config.launch_mode,
+        auction_start_price: config.auction_start_price,
+        auction_floor_price: config.auction_floor_price,
+        auction_start_time: config.auction_start_time,
+        auction_end_time: config.auction_end_time,
+        auction_supply: config.auction_supply,
+        auction_tokens_sold: 0,
+        clearing_price: 0,
+        creator: *authority_account.key,
+        pending_authority: Pubkey::default(),
+        governance: config.governance,
+        curve_base_price: 1,
+        curve_slope: 100,
+        is_paused: false,
+        held_deposits: 0,
+        slashed_deposits: 0,
+        is_dissolved: false,
+        total_supply: config.total_supply,
+        total_contributed: 0,
+        dissolved_supply: 0,
+        dissolved_treasury: 0,
+        unclaimed_tokens: 0,
+        unsettled_deposits: 0,
+    };
+
+    msg!(
+        "Project data prepared: name={}, symbol={}, funding_goal={}, token_mint={}, quote_mint={}, vesting_reserve={}, lockup_enabled={}, raise_deadline={}",
+        name,
+        symbol,
+        funding_goal,
+        project.token_mint,
+        project.quote_mint,
+        project.vesting_reserve,
+        project.lockup_enabled,
+        project.raise_deadline
+    );
+
+    // Calculate account size and rent
+    let rent = Rent::get()?;
+    let space = Project::LEN;
+    let lamports = rent.minimum_balance(space);
+
+    msg!("Account space: {}, Lamports: {}", space, lamports);
+
+    // Create account with PDA as signer
+    let seeds = &[b"project".as_ref(), authority_account.key.as_ref(), &[bump]];
+
+    msg!("Creating project account with seeds: {:?}", seeds);
+
+    // Create the account using invoke_signed
+    invoke_signed(
+        &system_instruction::create_account(
+            authority_account.key,
+            project_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        ),
+        &[
+            authority_account.clone(),
+            project_account.clone(),
+            system_program.clone(),
+        ],
+        &[seeds],
+    )?;
+
+    msg!("Account created successfully");
+
+    if let Some(quote_mint_account) = quote_mint_account {
+        // Create the vault as a quote mint token account owned by the project PDA
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            project_account.key.as_ref(),
+            &[vault_bump],
+        ];
+
+        invoke_signed(
+            &system_instruction::create_account(
+                authority_account.key,
+                vault_account.key,
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            &[
+                authority_account.clone(),
+                vault_account.clone(),
+                system_program.clone(),
+            ],
+            &[vault_seeds],
+        )?;
+
+        invoke(
+            &token_instruction::initialize_account3(
+                &spl_token::id(),
+                vault_account.key,
+                quote_mint_account.key,
+                project_account.key,
+            )?,
+            &[vault_account.clone(), quote_mint_account.clone()],
+        )?;
+
+        msg!("Quote token vault created successfully");
+    } else {
+        // Fund the vault with its rent-exempt minimum so raised SOL never sits in a
+        // rent-paying account
+        let vault_lamports = rent
+            .minimum_balance(0)
+            .saturating_sub(vault_account.lamports());
+        if vault_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    authority_account.key,
+                    vault_account.key,
+                    vault_lamports,
+                ),
+                &[authority_account.clone(), vault_account.clone()],
+            )?;
+        }
+
+        msg!("Vault funded successfully");
+    }
+
+    // Pack project data
+    let mut project_data = vec![0; Project::LEN];
+    project.pack(&mut project_data);
+    project_account
+        .data
+        .borrow_mut()
+        .copy_from_slice(&project_data);
+
+    msg!("Project initialized successfully");
+    Ok(())
+}
+
+// Contribute instruction processor
+fn process_contribute(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let project_account = next_account_info(account_info_iter)?;
+    let contributor_account = next_account_info(account_info_iter)?;
+    let contributor_token_account = next_account_info(account_info_iter)?;
+    let project_token = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let record_account = next_account_info(account_info_iter)?;
+
+    // Verify contributor is signer
+    if !contributor_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    if project.is_dissolved {
+        msg!("Project is dissolved");
+        return Err(UnicornFactoryError::ProjectDissolved.into());
+    }
+
+    if !project.is_active {
+        return Err(UnicornFactoryError::ProjectNotActive.into());
+    }
+
+    if project.is_paused {
+        msg!("Project is paused");
+        return Err(UnicornFactoryError::ProjectPaused.into());
+    }
+
+    // Batch auctions collect commitments instead
+    if project.launch_mode == LaunchMode::BatchAuction {
+        msg!("Batch auction projects take commitments");
+        return Err(UnicornFactoryError::InvalidLaunchMode.into());
+    }
+
+    // Auctions close on sell-out or at the end of their window instead
+    let is_auction = project.launch_mode == LaunchMode::DutchAuction;
+    if !is_auction && project.total_raised >= project.funding_goal {
+        return Err(UnicornFactoryError::FundingGoalReached.into());
+    }
+
+    let clock = Clock::get()?;
+    if project.raise_deadline != 0 && clock.unix_timestamp > project.raise_deadline {
+        msg!("Raise closed at {}", project.raise_deadline);
+        return Err(UnicornFactoryError::RaiseClosed.into());
+    }
+
+    if is_auction {
+        if clock.unix_timestamp < project.auction_start_time {
+            msg!("Auction opens at {}", project.auction_start_time);
+            return Err(UnicornFactoryError::AuctionNotStarted.into());
+        }
+        if clock.unix_timestamp > project.auction_end_time {
+            msg!("Auction closed at {}", project.auction_end_time);
+            return Err(UnicornFactoryError::RaiseClosed.into());
+        }
+    }
+
+    verify_vault(program_id, project_account, &project, vault_account)?;
+
+    let mut record = load_or_create_contributor_record(
+        program_id,
+        project_account,
+        contributor_account,
+        record_account,
+        system_program,
+    )?;
+
+    // Enforce project-wide contribution limits; zero disables either bound
+    if amount < project.min_contribution {
+        msg!(
+            "Contribution below minimum. Amount: {}, Minimum: {}",
+            amount,
+            project.min_contribution
+        );
+        return Err(UnicornFactoryError::ContributionBelowMinimum.into());
+    }
+
+    let total_contributed = record
+        .total_contributed
+        .checked_add(amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    if project.max_contribution_per_wallet != 0
+        && total_contributed > project.max_contribution_per_wallet
+    {
+        msg!(
+            "Wallet contribution cap exceeded. Contributed: {}, Cap: {}",
+            total_contributed,
+            project.max_contribution_per_wallet
+        );
+        return Err(UnicornFactoryError::ContributionCapExceeded.into());
+    }
+
+    // SPL quote projects are paid from the contributor's quote token account
+    let contributor_quote_account = if project.is_native_quote() {
+        contributor_account
+    } else {
+        next_account_info(account_info_iter)?
+    };
+
+    // Auction bids lock in their token amount at the current decayed price;
+    // while a presale round is running only allowlisted wallets contribute, at
+    // the round's fixed price and within the per-wallet cap
+    let price = if is_auction {
+        project.auction_price(clock.unix_timestamp)
+    } else if project.in_sale_round(clock.unix_timestamp) {
+        let sale_round_account = next_account_info(account_info_iter)?;
+        let mut sale_round = load_sale_round(program_id, project_account, sale_round_account)?;
+
+        if clock.unix_timestamp < sale_round.start_time {
+            msg!("Sale round opens at {}", sale_round.start_time);
+            return Err(UnicornFactoryError::SaleRoundNotStarted.into());
+        }
+
+        if !sale_round.is_allowlisted(contributor_account.key, &proof) {
+            msg!("Contributor {} is not allowlisted", contributor_account.key);
+            return Err(UnicornFactoryError::NotAllowlisted.into());
+        }
+
+        let presale_contributed = record
+            .presale_contributed
+            .checked_add(amount)
+            .ok_or(UnicornFactoryError::Overflow)?;
+        if sale_round.per_wallet_cap != 0 && presale_contributed > sale_round.per_wallet_cap {
+            msg!(
+                "Presale cap exceeded. Contributed: {}, Cap: {}",
+                presale_contributed,
+                sale_round.per_wallet_cap
+            );
+            return Err(UnicornFactoryError::ContributionCapExceeded.into());
+        }
+        record.presale_contributed = presale_contributed;
+
+        sale_round.total_sold = sale_round
+            .total_sold
+            .checked_add(amount)
+            .ok_or(UnicornFactoryError::Overflow)?;
+        let mut sale_round_data = sale_round_account.data.borrow_mut();
+        sale_round.pack(&mut sale_round_data);
+        drop(sale_round_data);
+
+        sale_round.price
+    } else if project.round_count > 0 {
+        // Multi-round raises route to the current round, skipping any whose
+        // window has already closed
+        let (round_account, mut round) = current_funding_round(
+            program_id,
+            project_account,
+            &mut project,
+            account_info_iter,
+            clock.unix_timestamp,
+        )?;
+
+        if clock.unix_timestamp < round.start_time {
+            msg!("Round {} opens at {}", round.index, round.start_time);
+            return Err(UnicornFactoryError::RoundNotStarted.into());
+        }
+
+        if !round.is_public()
+            && !verify_merkle_proof(&round.merkle_root, contributor_account.key, &proof)
+        {
+            msg!("Contributor {} is not allowlisted", contributor_account.key);
+            return Err(UnicornFactoryError::NotAllowlisted.into());
+        }
+
+        if amount > round.remaining() {
+            msg!(
+                "Round {} allocation exceeded. Amount: {}, Remaining: {}",
+                round.index,
+                amount,
+                round.remaining()
+            );
+            return Err(UnicornFactoryError::RoundAllocationExceeded.into());
+        }
+
+        let round_price = round.current_price();
+        round.raised += amount;
+
+        // A filled round hands over to the next one
+        if round.remaining() == 0 {
+            project.current_round += 1;
+            msg!("Round {} filled", round.index);
+        }
+
+        let mut round_data = round_account.data.borrow_mut();
+        round.pack(&mut round_data);
+        drop(round_data);
+
+        round_price
+    } else {
+        project.token_price
+    };
+
+    // Calculate tokens to mint
+    let tokens_to_mint = calculate_tokens(amount, price);
+
+    // A bid has to buy at least one token, or there would be nothing to settle it by
+    if is_auction && tokens_to_mint == 0 {
+        msg!("Bid below the current price. Amount: {}, Price: {}", amount, price);
+        return Err(UnicornFactoryError::InvalidAmount.into());
+    }
+
+    let auction_tokens_sold = project
+        .auction_tokens_sold
+        .checked_add(tokens_to_mint)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    if is_auction && auction_tokens_sold > project.auction_supply {
+        msg!(
+            "Auction supply exceeded. Requested: {}, Remaining: {}",
+            tokens_to_mint,
+            project.auction_supply - project.auction_tokens_sold
+        );
+        return Err(UnicornFactoryError::AuctionSupplyExceeded.into());
+    }
+
+    // Transfer quote asset from contributor to the project vault
+    deposit_to_vault(
+        &project,
+        contributor_account,
+        contributor_quote_account,
+        vault_account,
+        amount,
+    )?;
+
+    // With lockup enabled or in an auction tokens stay escrowed in the
+    // contributor record until the raise settles; otherwise they are minted to
+    // the contributor now
+    if !project.escrows_tokens() {
+        let seeds = &[
+            b"project".as_ref(),
+            project.creator.as_ref(),
+            &[project.bump],
+        ];
+
+        invoke_signed(
+            &token_instruction::mint_to(
+                token_program.key,
+                project_token.key,
+                contributor_token_account.key,
+                project_account.key,
+                &[],
+                tokens_to_mint,
+            )?,
+            &[
+                project_token.clone(),
+                contributor_token_account.clone(),
+                project_account.clone(),
+            ],
+            &[seeds],
+        )?;
+
+        record.tokens_claimed = record
+            .tokens_claimed
+            .checked_add(tokens_to_mint)
+            .ok_or(UnicornFactoryError::Overflow)?;
+    }
+
+    // Update contributor record
+    record.total_contributed = total_contributed;
+    record.tokens_purchased = record
+        .tokens_purchased
+        .checked_add(tokens_to_mint)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    let mut record_data = record_account.data.borrow_mut();
+    record.pack(&mut record_data);
+    drop(record_data);
+
+    // Update project state
+    project.total_raised = project
+        .total_raised
+        .checked_add(amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    project.total_contributed = project
+        .total_contributed
+        .checked_add(amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    if project.escrows_tokens() {
+        project.unclaimed_tokens = project
+            .unclaimed_tokens
+            .checked_add(tokens_to_mint)
+            .ok_or(UnicornFactoryError::Overflow)?;
+    }
+
+    if is_auction {
+        project.unsettled_deposits = project
+            .unsettled_deposits
+            .checked_add(amount)
+            .ok_or(UnicornFactoryError::Overflow)?;
+
+        // Prices only fall, so the latest bid sets the clearing price and a
+        // sell-out closes the auction there
+        project.auction_tokens_sold = auction_tokens_sold;
+        project.clearing_price = price;
+        if auction_tokens_sold == project.auction_supply {
+            project.close_auction(price)?;
+        }
+    } else {
+        project.token_price = calculate_new_price(&project);
+
+        // Check if funding goal is reached
+        if project.total_raised >= project.funding_goal {
+            project.is_active = false;
+            project.raise_state = RaiseState::Succeeded;
+        }
+    }
+
+    // Pack updated project data
+    let mut project_data = vec![0; Project::LEN];
+    project.pack(&mut project_data);
+    project_account
+        .data
+        .borrow_mut()
+        .copy_from_slice(&project_data);
+
+    Ok(())
+}
+
+// Buy tokens instruction processor
+//
+// Public bonding-curve buys go through the contribute path without an
+// allowlist proof, so they are recorded against the buyer's contributor record
+// and held to the same wallet limits, funding rounds and goal. Accounts match
+// Contribute.
+fn process_buy_tokens(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let project_account = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // Load and verify project
+    let project = load_project(program_id, project_account)?;
+
+    if project.launch_mode != LaunchMode::BondingCurve {
+        msg!("Bonding-curve buys are disabled for {:?} launches", project.launch_mode);
+        return Err(UnicornFactoryError::InvalidLaunchMode.into());
+    }
+
+    let clock = Clock::get()?;
+    if project.in_sale_round(clock.unix_timestamp) {
+        msg!(
+            "Public trading opens after the sale round ends at {}",
+            project.sale_round_end
+        );
+        return Err(UnicornFactoryError::SaleRoundActive.into());
+    }
+
+    process_contribute(program_id, accounts, amount, Vec::new())
+}
+
+// Sell tokens instruction processor
+fn process_sell_tokens(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    msg!("=== SELL TOKENS START ===");
+    msg!("Amount to sell: {}", amount);
+
+    // Log all received accounts first
+    msg!("Total accounts received: {}", accounts.len());
+    for (i, account) in accounts.iter().enumerate() {
+        msg!("Account {}: key={}, owner={}, lamports={}, data_len={}, executable={}, is_signer={}, is_writable={}", 
+             i, account.key, account.owner, account.lamports(), 
+             account.data_len(), account.executable, account.is_signer, account.is_writable);
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let project_account = next_account_info(account_info_iter)?;
+    msg!("✓ Project account loaded: {}", project_account.key);
+
+    let seller_account = next_account_info(account_info_iter)?;
+    msg!("✓ Seller account loaded: {}", seller_account.key);
+
+    let seller_token_account = next_account_info(account_info_iter)?;
+    msg!(
+        "✓ Seller token account loaded: {}",
+        seller_token_account.key
+    );
+
+    let project_token = next_account_info(account_info_iter)?;
+    msg!("✓ Project token loaded: {}", project_token.key);
+
+    let token_program = next_account_info(account_info_iter)?;
+    msg!("✓ Token program loaded: {}", token_program.key);
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!("✓ System program loaded: {}", system_program.key);
+
+    let vault_account = next_account_info(account_info_iter)?;
+    msg!("✓ Vault account loaded: {}", vault_account.key);
+
+    let record_account = next_account_info(account_info_iter)?;
+    msg!("✓ Seller record loaded: {}", record_account.key);
+
+    // Comprehensive account validations
+    msg!("=== VALIDATION PHASE ===");
+
+    // Verify seller is signer
+    if !seller_account.is_signer {
+        msg!("❌ ERROR: Seller is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    msg!("✓ Seller is signer");
+
+    // Verify token program ID
+    if token_program.key != &spl_token::id() {
+        msg!(
+            "❌ ERROR: Invalid token program. Expected: {}, Got: {}",
+            spl_token::id(),
+            token_program.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    msg!("✓ Token program ID correct");
+
+    // Verify system program ID
+    if system_program.key != &system_program::id() {
+        msg!(
+            "❌ ERROR: Invalid system program. Expected: {}, Got: {}",
+            system_program::id(),
+            system_program.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    msg!("✓ System program ID correct");
+
+    // Load and verify project
+    msg!("=== PROJECT LOADING ===");
+    let mut project = load_project(program_id, project_account)?;
+    msg!(
+        "✓ Project loaded: name={}, authority={}, total_raised={}, token_price={}, token_mint={}",
+        project.name,
+        project.authority,
+        project.total_raised,
+        project.token_price,
+        project.token_mint
+    );
+
+    // A failed raise is wound down through refunds, not the curve
+    if !project.is_active {
+        msg!("❌ ERROR: Project is not active");
+        return Err(UnicornFactoryError::ProjectNotActive.into());
+    }
+
+    if project.is_paused {
+        msg!("Project is paused");
+        return Err(UnicornFactoryError::ProjectPaused.into());
+    }
+
+    if project.is_dissolved {
+        msg!("Project is dissolved");
+        return Err(UnicornFactoryError::ProjectDissolved.into());
+    }
+    msg!("✓ Project is active");
+
+    // Verify project token mint matches
+    if project.token_mint != *project_token.key {
+        msg!(
+            "❌ ERROR: Project token mint mismatch. Project mint: {}, Provided mint: {}",
+            project.token_mint,
+            project_token.key
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+    msg!("✓ Project token mint matches");
+
+    // Verify vault belongs to this project
+    verify_vault(program_id, project_account, &project, vault_account)?;
+    msg!("✓ Vault account matches");
+
+    // SPL quote projects pay out to the seller's quote token account
+    let seller_quote_account = if project.is_native_quote() {
+        seller_account
+    } else {
+        let seller_quote_account = next_account_info(account_info_iter)?;
+        msg!("✓ Seller quote account loaded: {}", seller_quote_account.key);
+        seller_quote_account
+    };
+
+    // Check seller token account
+    msg!("=== SELLER TOKEN ACCOUNT VALIDATION ===");
+    if seller_token_account.data_is_empty() {
+        msg!("❌ ERROR: Seller token account is empty/doesn't exist");
+        return Err(ProgramError::UninitializedAccount);
+    }
+    msg!("✓ Seller token account exists");
+
+    if seller_token_account.owner != &spl_token::id() {
+        msg!(
+            "❌ ERROR: Seller token account owner mismatch. Expected: {}, Got: {}",
+            spl_token::id(),
+            seller_token_account.owner
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    msg!("✓ Seller token account owned by token program");
+
+    // Parse seller token account data
+    let seller_token_data = seller_token_account.try_borrow_data()?;
+    if seller_token_data.len() != spl_token::state::Account::LEN {
+        msg!(
+            "❌ ERROR: Invalid token account data length. Expected: {}, Got: {}",
+            spl_token::state::Account::LEN,
+            seller_token_data.len()
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+    msg!("✓ Seller token account data length correct");
+
+    let seller_token_info = spl_token::state::Account::unpack(&seller_token_data)?;
+    msg!("✓ Seller token account parsed successfully");
+    msg!("  - Balance: {}", seller_token_info.amount);
+    msg!("  - Mint: {}", seller_token_info.mint);
+    msg!("  - Owner: {}", seller_token_info.owner);
+    msg!("  - Delegate: {:?}", seller_token_info.delegate);
+    msg!("  - State: {:?}", seller_token_info.state);
+
+    // Validate token account details
+    if seller_token_info.amount < amount {
+        msg!(
+            "❌ ERROR: Insufficient token balance. Has: {}, Needs: {}",
+            seller_token_info.amount,
+            amount
+        );
+        return Err(UnicornFactoryError::InvalidAmount.into());
+    }
+    msg!("✓ Sufficient token balance");
+
+    if seller_token_info.mint != *project_token.key {
+        msg!(
+            "❌ ERROR: Token account mint mismatch. Expected: {}, Got: {}",
+            project_token.key,
+            seller_token_info.mint
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+    msg!("✓ Token account mint correct");
+
+    if seller_token_info.owner != *seller_account.key {
+        msg!(
+            "❌ ERROR: Token account owner mismatch. Expected: {}, Got: {}",
+            seller_account.key,
+            seller_token_info.owner
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+    msg!("✓ Token account owner correct");
+
+    drop(seller_token_data);
+
+    // Calculate quote amount to return
+    let quote_to_return = amount
+        .checked_mul(project.token_price)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    msg!("✓ Quote amount to return calculated: {}", quote_to_return);
+
+    // Check vault balance
+    let available = vault_balance(&project, vault_account)?;
+    msg!(
+        "Vault balance: {}, Need to pay: {}",
+        available,
+        quote_to_return
+    );
+
+    if available < quote_to_return {
+        msg!("❌ ERROR: Vault insufficient balance to pay back seller");
+        return Err(UnicornFactoryError::InsufficientTreasury.into());
+    }
+    msg!("✓ Vault has sufficient balance");
+
+    // Prepare burn instruction
+    msg!("=== PREPARING BURN INSTRUCTION ===");
+    let burn_instruction = token_instruction::burn(
+        token_program.key,
+        seller_token_account.key,
+        project_token.key,
+        seller_account.key,
+        &[],
+        amount,
+    )?;
+
+    msg!("Burn instruction created:");
+    msg!("  - Program ID: {}", burn_instruction.program_id);
+    msg!("  - Accounts count: {}", burn_instruction.accounts.len());
+    for (i, acc) in burn_instruction.accounts.iter().enumerate() {
+        msg!(
+            "    Account {}: pubkey={}, is_signer={}, is_writable={}",
+            i,
+            acc.pubkey,
+            acc.is_signer,
+            acc.is_writable
+        );
+    }
+    msg!("  - Data length: {}", burn_instruction.data.len());
+
+    msg!("=== EXECUTING BURN ===");
+    invoke(
+        &burn_instruction,
+        &[
+            seller_token_account.clone(),
+            project_token.clone(),
+            seller_account.clone(),
+        ],
+    )?;
+    msg!("✓ Tokens burned successfully");
+
+    // Pay the seller out of the vault
+    msg!("=== EXECUTING VAULT TRANSFER ===");
+    msg!(
+        "Transferring {} from vault to seller",
+        quote_to_return
+    );
+    withdraw_from_vault(
+        project_account,
+        &project,
+        vault_account,
+        seller_quote_account,
+        quote_to_return,
+    )?;
+    msg!("✓ Transferred {} from vault", quote_to_return);
+
+    // Whatever the sale paid back is no longer owed to the seller as a
+    // contribution. Holders who never contributed have no record.
+    verify_contributor_record(program_id, project_account, seller_account, record_account)?;
+    if !record_account.data_is_empty() {
+        let mut record_data = record_account.data.borrow_mut();
+        let mut record = ContributorRecord::unpack(&record_data)?;
+        let returned = quote_to_return.min(record.total_contributed);
+        record.total_contributed -= returned;
+        let sold = amount.min(record.tokens_claimed);
+        record.tokens_claimed -= sold;
+        record.tokens_purchased -= sold;
+        record.pack(&mut record_data);
+        drop(record_data);
+
+        project.total_contributed = project.total_contributed.saturating_sub(returned);
+        msg!("✓ Seller record reduced by {}", returned);
+    }
+
+    // Update project state
+    msg!("=== UPDATING PROJECT STATE ===");
+    project.total_raised = project
+        .total_raised
+        .checked_sub(quote_to_return)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    project.token_price = calculate_new_price(&project);
+    msg!(
+        "Updated project state: total_raised={}, token_price={}",
+        project.total_raised,
+        project.token_price
+    );
+
+    if project.total_raised >= project.funding_goal {
+        project.is_active = false;
+        msg!("Project funding goal reached, marking as inactive");
+    }
+
+    // Pack updated project data
+    let mut project_data = vec![0; Project::LEN];
+    project.pack(&mut project_data);
+    project_account
+        .data
+        .borrow_mut()
+        .copy_from_slice(&project_data);
+
+    msg!("✓ Project data updated");
+    msg!("=== SELL TOKENS SUCCESSFUL ===");
+    Ok(())
+}
+
+// Create proposal instruction processor
+fn process_create_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    description: String,
+    milestone_id: u8,
+) -> ProgramResult {
+    msg!("Starting proposal creation");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let proposal_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Proposal Account key: {}",
+        proposal_account.key
+    );
+
+    let authority_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Proposer Account key: {}",
+        authority_account.key
+    );
+
+    let milestone_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Milestone Account key: {}",
+        milestone_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: System Program key: {}",
+        system_program.key
+    );
+
+    let project_token = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Project Token key: {}",
+        project_token.key
+    );
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    // The authority proposes freely; holders above the threshold post a deposit
+    let clock = Clock::get()?;
+    let voting_end = clock.unix_timestamp + 180;
+    let tally_end = project.governance.tally_end(voting_end);
+    let (payer_account, deposit) = authorize_proposer(
+        program_id,
+        project_account,
+        &mut project,
+        authority_account,
+        accounts,
+        account_info_iter,
+        tally_end,
+    )?;
+
+    // Load and verify milestone
+    let mut milestone_data = milestone_account.data.borrow_mut();
+    let mut milestone = Milestone::unpack(&milestone_data)?;
+
+    // Verify milestone PDA
+    let (expected_milestone_pda, _milestone_bump) = Pubkey::find_program_address(
+        &[b"milestone", project_account.key.as_ref(), &[milestone_id]],
+        program_id,
+    );
+
+    if expected_milestone_pda != *milestone_account.key {
+        msg!(
+            "Invalid milestone account PDA. Expected: {}, Got: {}",
+            expected_milestone_pda,
+            milestone_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // A proposed milestone whose proposal can no longer pass or take effect
+    // is proposed afresh; that proposal follows the proposer's accounts
+    let is_stranded = if milestone.status == MilestoneStatus::Proposed {
+        let current_proposal_account = next_account_info(account_info_iter)?;
+        msg!(
+            "Processing current proposal account key: {}",
+            current_proposal_account.key
+        );
+        let current_proposal = load_proposal(
+            program_id,
+            project_account,
+            current_proposal_account,
+            milestone.proposal_id as u64,
+        )?;
+        current_proposal.is_defunct(&project.governance, clock.unix_timestamp)
+    } else {
+        false
+    };
+
+    // Only pending milestones, missed ones needing a fresh vote or stranded
+    // ones can be proposed
+    if !is_stranded
+        && !matches!(
+            milestone.status,
+            MilestoneStatus::Pending | MilestoneStatus::Missed
+        )
+    {
+        msg!(
+            "Milestone {} cannot be proposed in status {:?}",
+            milestone_id,
+            milestone.status
+        );
+        return Err(milestone.status.error().into());
+    }
+
+    // Create proposal data
+    let proposal = Proposal {
+        creator: *authority_account.key,
+        title: title.clone(),
+        description: description.clone(),
+        milestone_id,
+        yes_votes: 0,
+        no_votes: 0,
+        is_executed: false,
+        created_at: clock.unix_timestamp,
+        voting_end, // 24 hours from now
+        is_cancelled: false,
+        veto_votes: 0,
+        abstain_votes: 0,
+        quorum_votes: quorum_votes(&project, project_token)?,
+        option_labels: Vec::new(),
+        option_votes: Vec::new(),
+        winning_option: 0,
+        reveal_end: project.governance.reveal_end(voting_end),
+        unrevealed_votes: 0,
+        action: ProposalAction::None,
+        deposit,
+        evidence_version: milestone.evidence_version,
+        evidence_hash: milestone.evidence_hash,
+    };
+
+    create_proposal_account(
+        program_id,
+        project_account,
+        &mut project,
+        proposal_account,
+        payer_account,
+        system_program,
+        &proposal,
+    )?;
+
+    // Point the milestone at its new proposal
+    milestone.status = MilestoneStatus::Proposed;
+    milestone.proposal_id = project.proposal_count - 1;
+    milestone.pack(&mut milestone_data);
+    drop(milestone_data);
+
+    // Pack updated project data
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!("Proposal added and project count updated successfully");
+    Ok(())
+}
+
+// Vote and commit vote instruction processor
+//
+// Accounts after the system program are the voter's escrow and vote record,
+// followed by (delegation record, delegator escrow, delegator vote record)
+// triples for each holder delegating to the voter.
+// Secret ballots are cast with VOTE_HIDDEN and the voter's commitment.
+fn process_vote(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proposal_id: u64,
+    choice: u8,
+    commitment: [u8; 32],
+) -> ProgramResult {
+    msg!("Starting vote processing");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let proposal_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Proposal Account key: {}",
+        proposal_account.key
+    );
+
+    let voter_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Voter Account key: {}",
+        voter_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: System Program key: {}",
+        system_program.key
+    );
+
+    let voter_escrow_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Voter Escrow key: {}",
+        voter_escrow_account.key
+    );
+
+    let vote_record_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Vote Record key: {}",
+        vote_record_account.key
+    );
+
+    // Verify voter is signer
+    if !voter_account.is_signer {
+        msg!("Voter is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Verify the project account holds a project
+    load_project(program_id, project_account)?;
+
+    let mut proposal = load_proposal(program_id, project_account, proposal_account, proposal_id)?;
+    verify_voting_open(&proposal)?;
+
+    if proposal.is_secret() != (choice == VOTE_HIDDEN) {
+        msg!(
+            "Proposal {} takes {} ballots",
+            proposal_id,
+            if proposal.is_secret() { "committed" } else { "public" }
+        );
+        return Err(UnicornFactoryError::InvalidVoteChoice.into());
+    }
+
+    if proposal.tally_mut(choice).is_none() {
+        msg!("Proposal {} has no choice {}", proposal_id, choice);
+        return Err(UnicornFactoryError::InvalidVoteChoice.into());
+    }
+
+    // Weight the voter's own vote by their escrow, locked until the tally closes
+    let own_weight = voting_weight(
+        program_id,
+        project_account,
+        voter_escrow_account,
+        voter_account.key,
+        proposal.tally_end(),
+    )?;
+    let own_record = VoteRecord {
+        proposal: *proposal_account.key,
+        voter: *voter_account.key,
+        cast_by: *voter_account.key,
+        choice,
+        weight: own_weight,
+        commitment,
+    };
+
+    let vote_bump = verify_vote_record(
+        program_id,
+        proposal_account,
+        voter_account.key,
+        vote_record_account,
+    )?;
+
+    if vote_record_account.data_is_empty() {
+        create_vote_record(
+            program_id,
+            vote_record_account,
+            vote_bump,
+            voter_account,
+            system_program,
+            &own_record,
+        )?;
+    } else {
+        let previous = {
+            let vote_data = vote_record_account.data.borrow();
+            VoteRecord::unpack(&vote_data)?
+        };
+        if previous.cast_by == *voter_account.key {
+            msg!("Voter already voted on proposal {}", proposal_id);
+            return Err(UnicornFactoryError::AlreadyVoted.into());
+        }
+
+        // A direct vote overrides the one a delegate cast for this voter
+        msg!(
+            "Overriding delegated vote of {} cast by {}",
+            previous.weight,
+            previous.cast_by
+        );
+        remove_vote_weight(&mut proposal, previous.choice, previous.weight);
+        let mut vote_data = vote_record_account.data.borrow_mut();
+        own_record.pack(&mut vote_data);
+    }
+
+    // Count the balances delegated to the voter
+    let mut delegated_weight: u64 = 0;
+    for delegation_accounts in account_info_iter.as_slice().chunks(3) {
+        let [delegation_account, delegator_escrow_account, delegator_vote_account] =
+            delegation_accounts
+        else {
+            msg!("Delegated votes need a delegation record, voter escrow and vote record");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if delegation_account.owner != program_id {
+            msg!("Delegation record {} is not active", delegation_account.key);
+            return Err(UnicornFactoryError::InvalidDelegation.into());
+        }
+        let delegation = {
+            let delegation_data = delegation_account.data.borrow();
+            DelegationRecord::unpack(&delegation_data)?
+        };
+        verify_delegation_record(
+            program_id,
+            project_account,
+            &delegation.delegator,
+            delegation_account,
+        )?;
+        if delegation.delegate != *voter_account.key {
+            msg!(
+                "{} delegates to {}, not the voter",
+                delegation.delegator,
+                delegation.delegate
+            );
+            return Err(UnicornFactoryError::InvalidDelegation.into());
+        }
+
+        let delegator_bump = verify_vote_record(
+            program_id,
+            proposal_account,
+            &delegation.delegator,
+            delegator_vote_account,
+        )?;
+
+        // Delegators who already voted themselves keep their own vote
+        if !delegator_vote_account.data_is_empty() {
+            msg!("{} already voted on this proposal", delegation.delegator);
+            continue;
+        }
+
+        let weight = voting_weight(
+            program_id,
+            project_account,
+            delegator_escrow_account,
+            &delegation.delegator,
+            proposal.tally_end(),
+        )?;
+        create_vote_record(
+            program_id,
+            delegator_vote_account,
+            delegator_bump,
+            voter_account,
+            system_program,
+            &VoteRecord {
+                proposal: *proposal_account.key,
+                voter: delegation.delegator,
+                cast_by: *voter_account.key,
+                choice,
+                weight,
+                commitment,
+            },
+        )?;
+
+        delegated_weight = delegated_weight
+            .checked_add(weight)
+            .ok_or(UnicornFactoryError::Overflow)?;
+    }
+
+    let total_weight = own_weight
+        .checked_add(delegated_weight)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    if total_weight == 0 {
+        msg!("Voter holds no project tokens and has no delegated weight");
+        return Err(UnicornFactoryError::InvalidAmount.into());
+    }
+
+    // Update vote count
+    add_vote_weight(&mut proposal, choice, total_weight)?;
+
+    // Pack updated proposal data
+    let mut proposal_data = proposal_account.try_borrow_mut_data()?;
+    proposal.pack(&mut proposal_data);
+    drop(proposal_data);
+
+    msg!(
+        "Vote processed successfully: {} own and {} delegated weight",
+        own_weight,
+        delegated_weight
+    );
+    Ok(())
+}
+
+// Release funds instruction processor
+fn process_release_funds(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proposal_id: u64,
+) -> ProgramResult {
+    msg!("Starting funds release for proposal {}", proposal_id);
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let proposal_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Proposal Account key: {}",
+        proposal_account.key
+    );
+
+    let authority_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Authority Account key: {}",
+        authority_account.key
+    );
+
+    let milestone_account = next_account_info(account_info_iter)?;
+     msg!(
+        "Processing account 3: Milestone Account key: {}",
+        milestone_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: System Program key: {}",
+        system_program.key
+    );
+
+    let vault_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Vault Account key: {}",
+        vault_account.key
+    );
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    // Verify authority: the authority key itself or a quorum of its council
+    authorize(program_id, project_account, &project, authority_account, accounts)?;
+
+    verify_vault(program_id, project_account, &project, vault_account)?;
+
+    if project.is_dissolved {
+        msg!("Project is dissolved");
+        return Err(UnicornFactoryError::ProjectDissolved.into());
+    }
+
+    // A council releases to its treasury; a single authority to itself
+    let council = if authority_account.is_signer {
+        None
+    } else {
+        Some(load_council(program_id, project_account, authority_account)?)
+    };
+
+    // SPL quote projects release to the recipient's quote token account
+    let authority_quote_account = if project.is_native_quote() {
+        match &council {
+            Some(council) => {
+                let treasury_account = next_account_info(account_info_iter)?;
+                msg!(
+                    "Processing account 6: Council Treasury key: {}",
+                    treasury_account.key
+                );
+                if *treasury_account.key != council.treasury {
+                    msg!("Release recipient is not the council treasury");
+                    return Err(UnicornFactoryError::InvalidCouncil.into());
+                }
+                treasury_account
+            }
+            None => authority_account,
+        }
+    } else {
+        let authority_quote_account = next_account_info(account_info_iter)?;
+        msg!(
+            "Processing account 6: Authority Quote Account key: {}",
+            authority_quote_account.key
+        );
+
+        let token_program = next_account_info(account_info_iter)?;
+        if token_program.key != &spl_token::id() {
+            msg!("Invalid token program");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if let Some(council) = &council {
+            let quote_data = authority_quote_account.try_borrow_data()?;
+            let quote_account = spl_token::state::Account::unpack(&quote_data)?;
+            if quote_account.owner != council.treasury {
+                msg!("Release recipient is not owned by the council treasury");
+                return Err(UnicornFactoryError::InvalidCouncil.into());
+            }
+        }
+        authority_quote_account
+    };
+
+    // Find expected Proposal PDA (using single byte like create_proposal)
+    let (expected_proposal_pda, _bump) = Pubkey::find_program_address(
+        &[
+            b"proposal",
+            project_account.key.as_ref(),
+            &[proposal_id as u8],
+        ],
+        program_id,
+    );
+
+    // Verify the provided proposal account is the expected PDA
+    if expected_proposal_pda != *proposal_account.key {
+        msg!(
+            "Invalid proposal account PDA. Expected: {}, Got: {}",
+            expected_proposal_pda,
+            proposal_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify proposal
+    let mut proposal_data = proposal_account.data.borrow_mut();
+    let mut proposal = Proposal::unpack(&proposal_data)?;
+
+    // Load and verify milestone account
+    let mut milestone_data = milestone_account.data.borrow_mut();
+    let mut milestone = Milestone::unpack(&milestone_data)?;
+
+    // Verify milestone PDA using the milestone_id from the proposal
+    let (expected_milestone_pda, _milestone_bump) = Pubkey::find_program_address(
+        &[b"milestone", project_account.key.as_ref(), &[proposal.milestone_id]],
+        program_id,
+    );
+
+    if expected_milestone_pda != *milestone_account.key {
+        msg!(
+            "Invalid milestone account PDA for release. Expected: {}, Got: {}",
+            expected_milestone_pda,
+            milestone_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Funds only move once CompleteMilestone has approved the milestone
+    if milestone.status == MilestoneStatus::Proposed {
+        msg!(
+            "Milestone {} must be completed before funds are released",
+            proposal.milestone_id
+        );
+        return Err(UnicornFactoryError::MilestoneNotCompleted.into());
+    }
+
+    let clock = Clock::get()?;
+    verify_milestone_proposal(
+        &project,
+        &proposal,
+        proposal_id,
+        &milestone,
+        clock.unix_timestamp,
+    )?;
+
+    // Release the tranches unlocked since the last release from the project vault
+    let amount_to_release = milestone
+        .unlocked_amount(clock.unix_timestamp)
+        .saturating_sub(milestone.released_amount);
+    if amount_to_release == 0 {
+        msg!(
+            "No tranche of milestone {} is unlocked yet",
+            proposal.milestone_id
+        );
+        return Err(UnicornFactoryError::TrancheLocked.into());
+    }
+    msg!(
+        "Releasing {} for proposal {}",
+        amount_to_release,
+        proposal_id
+    );
+
+    // Check the vault can cover the release without touching its rent-exempt
+    // reserve or the funds owed to sellers
+    let releasable = releasable_amount(&project, vault_balance(&project, vault_account)?);
+    if amount_to_release > releasable {
+        msg!(
+            "Insufficient treasury for release. Releasable: {}, Requested: {}",
+            releasable,
+            amount_to_release
+        );
+        return Err(UnicornFactoryError::InsufficientTreasury.into());
+    }
+
+    withdraw_from_vault(
+        project_account,
+        &project,
+        vault_account,
+        authority_quote_account,
+        amount_to_release,
+    )?;
+
+    // Track released total on the project
+    project.total_released = project
+        .total_released
+        .checked_add(amount_to_release)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    // Record the payout on the milestone together with the transfer
+    milestone.released_amount += amount_to_release;
+    milestone.released_at = clock.unix_timestamp;
+
+    // The proposal is executed once its last tranche is paid
+    proposal.winning_option = VOTE_YES;
+    if milestone.released_amount == milestone.amount {
+        milestone.status = MilestoneStatus::Released;
+        proposal.is_executed = true;
+    }
+
+    msg!("Successfully released {} for proposal {}", amount_to_release, proposal_id);
+
+    // Pack updated proposal data
+    proposal.pack(&mut proposal_data);
+    drop(proposal_data);
+
+    milestone.pack(&mut milestone_data);
+    drop(milestone_data);
+
+    emit_milestone_event(
+        b"MilestoneReleased",
+        project_account.key,
+        proposal.milestone_id,
+        milestone.released_at,
+    );
+
+    msg!(
+        "Funds released for proposal {}: {} of {} paid",
+        proposal_id,
+        milestone.released_amount,
+        milestone.amount
+    );
+    Ok(())
+}
+
+// Add milestone instruction processor
+fn process_add_milestone(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    milestone: Milestone,
+) -> ProgramResult {
+    msg!("🔧 NEW CONTRACT VERSION - USING INVOKE_SIGNED FOR MILESTONES");
+    msg!("Starting milestone addition");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let milestone_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Milestone Account key: {}",
+        milestone_account.key
+    );
+
+    let authority_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Authority Account key: {}",
+        authority_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: System Program key: {}",
+        system_program.key
+    );
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load project and get values we need
+    let mut project = load_project(program_id, project_account)?;
+
+    // Verify authority: the authority key itself or a quorum of its council
+    let payer_account =
+        authorize(program_id, project_account, &project, authority_account, accounts)?;
+
+    create_milestone_account(
+        program_id,
+        project_account,
+        &mut project,
+        milestone_account,
+        payer_account,
+        system_program,
+        &milestone,
+    )?;
+
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!("Milestone added successfully");
+    Ok(())
+}
+
+// Complete milestone instruction processor
+fn process_complete_milestone(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    milestone_id: u8,
+) -> ProgramResult {
+    msg!("Starting milestone completion");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let milestone_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Milestone Account key: {}",
+        milestone_account.key
+    );
+
+    let authority_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Authority Account key: {}",
+        authority_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: System Program key: {}",
+        system_program.key
+    );
+
+    let proposal_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Proposal Account key: {}",
+        proposal_account.key
+    );
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let project = load_project(program_id, project_account)?;
+
+    // Verify authority: the authority key itself or a quorum of its council
+    authorize(program_id, project_account, &project, authority_account, accounts)?;
+
+    // Find expected Milestone PDA
+    let (expected_milestone_pda, _bump) = Pubkey::find_program_address(
+        &[b"milestone", project_account.key.as_ref(), &[milestone_id]],
+        program_id,
+    );
+
+    // Verify the provided milestone account is the expected PDA
+    if expected_milestone_pda != *milestone_account.key {
+        msg!(
+            "Invalid milestone account PDA. Expected: {}, Got: {}",
+            expected_milestone_pda,
+            milestone_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify milestone
+    let mut milestone_data = milestone_account.data.borrow_mut();
+    let mut milestone = Milestone::unpack(&milestone_data)?;
+
+    if milestone.status != MilestoneStatus::Proposed {
+        msg!(
+            "Milestone {} cannot be completed in status {:?}",
+            milestone_id,
+            milestone.status
+        );
+        return Err(milestone.status.error().into());
+    }
+
+    // Completion is approved by the milestone's current proposal
+    let (expected_proposal_pda, _bump) = Pubkey::find_program_address(
+        &[
+            b"proposal",
+            project_account.key.as_ref(),
+            &[milestone.proposal_id],
+        ],
+        program_id,
+    );
+
+    if expected_proposal_pda != *proposal_account.key {
+        msg!(
+            "Invalid proposal account PDA. Expected: {}, Got: {}",
+            expected_proposal_pda,
+            proposal_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let proposal = Proposal::unpack(&proposal_account.data.borrow())?;
+
+    let clock = Clock::get()?;
+    verify_milestone_proposal(
+        &project,
+        &proposal,
+        milestone.proposal_id as u64,
+        &milestone,
+        clock.unix_timestamp,
+    )?;
+
+    // Complete milestone; the payout follows with ReleaseFunds
+    msg!("Completing milestone {}", milestone_id);
+    milestone.status = MilestoneStatus::Approved;
+    milestone.approved_at = clock.unix_timestamp;
+
+    // Pack updated milestone data
+    milestone.pack(&mut milestone_data);
+    drop(milestone_data);
+
+    emit_milestone_event(
+        b"MilestoneApproved",
+        project_account.key,
+        milestone_id,
+        clock.unix_timestamp,
+    );
+
+    msg!("Milestone {} completed successfully", milestone_id);
+    Ok(())
+}
+
+// Create vesting instruction processor
+fn process_create_vesting(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    total_amount: u64,
+    start_time: i64,
+    cliff_time: i64,
+    end_time: i64,
+    revocable: bool,
+) -> ProgramResult {
+    msg!("Starting vesting schedule creation");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let vesting_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Vesting Account key: {}",
+        vesting_account.key
+    );
+
+    let authority_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Authority Account key: {}",
+        authority_account.key
+    );
+
+    let beneficiary_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Beneficiary Account key: {}",
+        beneficiary_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: System Program key: {}",
+        system_program.key
+    );
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    // Verify authority: the authority key itself or a quorum of its council
+    let payer_account =
+        authorize(program_id, project_account, &project, authority_account, accounts)?;
+
+    if project.is_dissolved {
+        msg!("Project is dissolved");
+        return Err(UnicornFactoryError::ProjectDissolved.into());
+    }
+
+    // Verify schedule shape. Schedules cannot be backdated to vest on creation.
+    let clock = Clock::get()?;
+    if total_amount == 0
+        || start_time < clock.unix_timestamp
+        || start_time >= end_time
+        || cliff_time < start_time
+        || cliff_time > end_time
+    {
+        msg!(
+            "Invalid vesting schedule. start={}, cliff={}, end={}, amount={}, now={}",
+            start_time,
+            cliff_time,
+            end_time,
+            total_amount,
+            clock.unix_timestamp
+        );
+        return Err(UnicornFactoryError::InvalidVestingSchedule.into());
+    }
+
+    // Verify allocation fits in the reserved share of supply
+    let vesting_allocated = project
+        .vesting_allocated
+        .checked_add(total_amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    if vesting_allocated > project.vesting_reserve {
+        msg!(
+            "Vesting reserve exceeded. Allocated: {}, Reserve: {}",
+            vesting_allocated,
+            project.vesting_reserve
+        );
+        return Err(UnicornFactoryError::VestingReserveExceeded.into());
+    }
+
+    // Verify vesting PDA
+    let (expected_vesting_pda, vesting_bump) = Pubkey::find_program_address(
+        &[
+            b"vesting",
+            project_account.key.as_ref(),
+            beneficiary_account.key.as_ref(),
+        ],
+        program_id,
+    );
+
+    if expected_vesting_pda != *vesting_account.key {
+        msg!(
+            "Invalid vesting account PDA. Expected: {}, Got: {}",
+            expected_vesting_pda,
+            vesting_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Verify vesting account is not already initialized
+    if vesting_account.data.borrow().iter().any(|&x| x != 0) {
+        msg!("Vesting account already initialized");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // Calculate rent
+    let rent = Rent::get()?;
+    let space = VestingSchedule::LEN;
+    let lamports = rent.minimum_balance(space);
+
+    // Create vesting account using invoke_signed
+    let vesting_seeds = &[
+        b"vesting".as_ref(),
+        project_account.key.as_ref(),
+        beneficiary_account.key.as_ref(),
+        &[vesting_bump],
+    ];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer_account.key,
+            vesting_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        ),
+        &[
+            payer_account.clone(),
+            vesting_account.clone(),
+            system_program.clone(),
+        ],
+        &[vesting_seeds],
+    )?;
+
+    // Create and pack vesting data
+    let vesting = VestingSchedule {
+        project: *project_account.key,
+        beneficiary: *beneficiary_account.key,
+        total_amount,
+        claimed_amount: 0,
+        start_time,
+        cliff_time,
+        end_time,
+        revocable,
+        is_revoked: false,
+    };
+
+    {
+        let mut vesting_data = vesting_account.data.borrow_mut();
+        vesting.pack(&mut vesting_data);
+    }
+
+    // Update project vesting allocation
+    project.vesting_allocated = vesting_allocated;
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!(
+        "Vesting schedule created for {}: {} tokens",
+        beneficiary_account.key,
+        total_amount
+    );
+    Ok(())
+}
+
+// Claim vested instruction processor
+fn process_claim_vested(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Starting vested token claim");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let vesting_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Vesting Account key: {}",
+        vesting_account.key
+    );
+
+    let beneficiary_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Beneficiary Account key: {}",
+        beneficiary_account.key
+    );
+
+    let beneficiary_token_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Beneficiary Token Account key: {}",
+        beneficiary_token_account.key
+    );
+
+    let project_token = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Project Token key: {}",
+        project_token.key
+    );
+
+    let token_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Token Program key: {}",
+        token_program.key
+    );
+
+    // Verify beneficiary is signer
+    if !beneficiary_account.is_signer {
+        msg!("Beneficiary is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify token program
+    if token_program.key != &spl_token::id() {
+        msg!("Invalid token program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let project = load_project(program_id, project_account)?;
+
+    if project.is_dissolved {
+        msg!("Project is dissolved");
+        return Err(UnicornFactoryError::ProjectDissolved.into());
+    }
+
+    // Nothing vests into circulation while contributors' funds are still at stake
+    if project.raise_state != RaiseState::Succeeded {
+        msg!("Vested tokens are claimable only after a successful raise");
+        return Err(UnicornFactoryError::RaiseNotSucceeded.into());
+    }
+
+    if project.token_mint != *project_token.key {
+        msg!(
+            "Project token mint mismatch. Project mint: {}, Provided mint: {}",
+            project.token_mint,
+            project_token.key
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Verify vesting PDA
+    let (expected_vesting_pda, _vesting_bump) = Pubkey::find_program_address(
+        &[
+            b"vesting",
+            project_account.key.as_ref(),
+            beneficiary_account.key.as_ref(),
+        ],
+        program_id,
+    );
+
+    if expected_vesting_pda != *vesting_account.key {
+        msg!(
+            "Invalid vesting account PDA. Expected: {}, Got: {}",
+            expected_vesting_pda,
+            vesting_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load vesting schedule
+    let mut vesting_data = vesting_account.data.borrow_mut();
+    let mut vesting = VestingSchedule::unpack(&vesting_data)?;
+
+    // Calculate claimable tokens
+    let clock = Clock::get()?;
+    let vested = vesting.vested_amount(clock.unix_timestamp);
+    let claimable = vested.saturating_sub(vesting.claimed_amount);
+    if claimable == 0 {
+        msg!("Nothing to claim. Vested: {}, Claimed: {}", vested, vesting.claimed_amount);
+        return Err(UnicornFactoryError::NothingToClaim.into());
+    }
+
+    // Mint vested tokens to beneficiary
+    let seeds = &[
+        b"project".as_ref(),
+        project.creator.as_ref(),
+        &[project.bump],
+    ];
+
+    invoke_signed(
+        &token_instruction::mint_to(
+            token_program.key,
+            project_token.key,
+            beneficiary_token_account.key,
+            project_account.key,
+            &[],
+            claimable,
+        )?,
+        &[
+            project_token.clone(),
+            beneficiary_token_account.clone(),
+            project_account.clone(),
+        ],
+        &[seeds],
+    )?;
+
+    // Update vesting schedule
+    vesting.claimed_amount = vested;
+    vesting.pack(&mut vesting_data);
+    drop(vesting_data);
+
+    msg!("Claimed {} vested tokens", claimable);
+    Ok(())
+}
+
+// Revoke vesting instruction processor
+fn process_revoke_vesting(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Starting vesting revocation");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let vesting_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Vesting Account key: {}",
+        vesting_account.key
+    );
+
+    let authority_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Authority Account key: {}",
+        authority_account.key
+    );
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    // Verify authority: the authority key itself or a quorum of its council
+    authorize(program_id, project_account, &project, authority_account, accounts)?;
+
+    if project.is_dissolved {
+        msg!("Project is dissolved");
+        return Err(UnicornFactoryError::ProjectDissolved.into());
+    }
+
+    // Load vesting schedule
+    let mut vesting_data = vesting_account.data.borrow_mut();
+    let mut vesting = VestingSchedule::unpack(&vesting_data)?;
+
+    // Verify vesting PDA
+    let (expected_vesting_pda, _vesting_bump) = Pubkey::find_program_address(
+        &[
+            b"vesting",
+            project_account.key.as_ref(),
+            vesting.beneficiary.as_ref(),
+        ],
+        program_id,
+    );
+
+    if expected_vesting_pda != *vesting_account.key {
+        msg!(
+            "Invalid vesting account PDA. Expected: {}, Got: {}",
+            expected_vesting_pda,
+            vesting_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if !vesting.revocable {
+        msg!("Vesting schedule is not revocable");
+        return Err(UnicornFactoryError::VestingNotRevocable.into());
+    }
+
+    if vesting.is_revoked {
+        msg!("Vesting schedule is already revoked");
+        return Err(UnicornFactoryError::VestingAlreadyRevoked.into());
+    }
+
+    // Freeze the schedule at what has vested so far and return the rest to the reserve
+    let clock = Clock::get()?;
+    let vested = vesting.vested_amount(clock.unix_timestamp);
+    let unvested = vesting.total_amount.saturating_sub(vested);
+
+    vesting.total_amount = vested;
+    vesting.end_time = vesting.end_time.min(clock.unix_timestamp.max(vesting.cliff_time));
+    vesting.is_revoked = true;
+    vesting.pack(&mut vesting_data);
+    drop(vesting_data);
+
+    project.vesting_allocated = project.vesting_allocated.saturating_sub(unvested);
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!(
+        "Vesting schedule revoked. Vested: {}, Returned to reserve: {}",
+        vested,
+        unvested
+    );
+    Ok(())
+}
+
+// Finalize raise instruction processor
+fn process_finalize_raise(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Starting raise finalization");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    if project.raise_state != RaiseState::Open {
+        msg!("Raise already finalized");
+        return Err(UnicornFactoryError::RaiseClosed.into());
+    }
+
+    // Anyone can settle the raise once the goal is met or the deadline passes
+    let clock = Clock::get()?;
+    if project.launch_mode == LaunchMode::BatchAuction {
+        msg!("Batch auctions are finalized by settlement");
+        return Err(UnicornFactoryError::InvalidLaunchMode.into());
+    } else if project.launch_mode == LaunchMode::DutchAuction {
+        // An auction that did not sell out clears at its floor price, or
+        // higher if the bids would buy more than the supply there, but never
+        // above the last bid's price so no bidder pays more than they bid.
+        // Every bid is then filled at the clearing price.
+        if clock.unix_timestamp <= project.auction_end_time {
+            msg!("Auction is still open");
+            return Err(UnicornFactoryError::RaiseStillOpen.into());
+        }
+        let mut clearing_price = project
+            .total_raised
+            .div_ceil(project.auction_supply)
+            .max(project.auction_floor_price);
+        if project.auction_tokens_sold > 0 {
+            clearing_price = clearing_price.min(project.clearing_price);
+        }
+        project.auction_tokens_sold =
+            (project.total_raised / clearing_price).min(project.auction_supply);
+        project.close_auction(clearing_price)?;
+    } else if project.total_raised >= project.funding_goal {
+        project.raise_state = RaiseState::Succeeded;
+    } else if project.raise_deadline != 0 && clock.unix_timestamp > project.raise_deadline {
+        project.raise_state = RaiseState::Failed;
+    } else {
+        msg!("Raise is still open");
+        return Err(UnicornFactoryError::RaiseStillOpen.into());
+    }
+    project.is_active = false;
+
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!("Raise finalized with state {:?}", project.raise_state);
+    Ok(())
+}
+
+// Claim tokens instruction processor
+fn process_claim_tokens(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Starting locked token claim");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let contributor_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Contributor Account key: {}",
+        contributor_account.key
+    );
+
+    let record_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Contributor Record key: {}",
+        record_account.key
+    );
+
+    let contributor_token_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Contributor Token Account key: {}",
+        contributor_token_account.key
+    );
+
+    let project_token = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Project Token key: {}",
+        project_token.key
+    );
+
+    let token_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Token Program key: {}",
+        token_program.key
+    );
+
+    // Verify contributor is signer
+    if !contributor_account.is_signer {
+        msg!("Contributor is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify token program
+    if token_program.key != &spl_token::id() {
+        msg!("Invalid token program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    if project.raise_state != RaiseState::Succeeded {
+        msg!("Locked tokens are claimable only after a successful raise");
+        return Err(UnicornFactoryError::RaiseNotSucceeded.into());
+    }
+
+    if project.launch_mode != LaunchMode::BondingCurve {
+        msg!("Auction bids are claimed through settlement");
+        return Err(UnicornFactoryError::InvalidLaunchMode.into());
+    }
+
+    if project.token_mint != *project_token.key {
+        msg!(
+            "Project token mint mismatch. Project mint: {}, Provided mint: {}",
+            project.token_mint,
+            project_token.key
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Load contributor record
+    verify_contributor_record(program_id, project_account, contributor_account, record_account)?;
+    let mut record_data = record_account.data.borrow_mut();
+    let mut record = ContributorRecord::unpack(&record_data)?;
+
+    let claimable = record.tokens_purchased.saturating_sub(record.tokens_claimed);
+    if claimable == 0 {
+        msg!("Nothing to claim");
+        return Err(UnicornFactoryError::NothingToClaim.into());
+    }
+
+    if project.is_dissolved {
+        // Nothing is minted once the project is dissolved, so the tokens are
+        // paid out at the redemption rate they were counted in at
+        let vault_account = next_account_info(account_info_iter)?;
+        msg!(
+            "Processing account 6: Vault Account key: {}",
+            vault_account.key
+        );
+        verify_vault(program_id, project_account, &project, vault_account)?;
+
+        let contributor_quote_account = if project.is_native_quote() {
+            contributor_account
+        } else {
+            let contributor_quote_account = next_account_info(account_info_iter)?;
+            msg!(
+                "Processing account 7: Contributor Quote Account key: {}",
+                contributor_quote_account.key
+            );
+            contributor_quote_account
+        };
+
+        let payout = project.redemption_value(claimable);
+        if payout > 0 {
+            withdraw_from_vault(
+                project_account,
+                &project,
+                vault_account,
+                contributor_quote_account,
+                payout,
+            )?;
+        }
+        msg!("Paid {} for {} locked tokens", payout, claimable);
+    } else {
+        // Release escrowed tokens to the contributor
+        let seeds = &[
+            b"project".as_ref(),
+            project.creator.as_ref(),
+            &[project.bump],
+        ];
+
+        invoke_signed(
+            &token_instruction::mint_to(
+                token_program.key,
+                project_token.key,
+                contributor_token_account.key,
+                project_account.key,
+                &[],
+                claimable,
+            )?,
+            &[
+                project_token.clone(),
+                contributor_token_account.clone(),
+                project_account.clone(),
+            ],
+            &[seeds],
+        )?;
+        msg!("Claimed {} locked tokens", claimable);
+    }
+
+    record.tokens_claimed = record.tokens_purchased;
+    record.pack(&mut record_data);
+    drop(record_data);
+
+    project.unclaimed_tokens = project.unclaimed_tokens.saturating_sub(claimable);
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    Ok(())
+}
+
+// Refund instruction processor
+fn process_refund(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Starting contribution refund");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let contributor_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Contributor Account key: {}",
+        contributor_account.key
+    );
+
+    let record_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Contributor Record key: {}",
+        record_account.key
+    );
+
+    let token_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Token Program key: {}",
+        token_program.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: System Program key: {}",
+        system_program.key
+    );
+
+    let vault_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Vault Account key: {}",
+        vault_account.key
+    );
+
+    // Verify contributor is signer
+    if !contributor_account.is_signer {
+        msg!("Contributor is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify programs
+    if token_program.key != &spl_token::id() || system_program.key != &system_program::id() {
+        msg!("Invalid token or system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    if project.raise_state != RaiseState::Failed {
+        msg!("Refunds are available only after a failed raise");
+        return Err(UnicornFactoryError::RaiseNotFailed.into());
+    }
+
+    verify_vault(program_id, project_account, &project, vault_account)?;
+
+    // SPL quote projects refund to the contributor's quote token account
+    let contributor_quote_account = if project.is_native_quote() {
+        contributor_account
+    } else {
+        let contributor_quote_account = next_account_info(account_info_iter)?;
+        msg!(
+            "Processing account 6: Contributor Quote Account key: {}",
+            contributor_quote_account.key
+        );
+        contributor_quote_account
+    };
+
+    let contributor_token_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Contributor Token Account key: {}",
+        contributor_token_account.key
+    );
+
+    let project_token = next_account_info(account_info_iter)?;
+    msg!("Project Token key: {}", project_token.key);
+
+    if project.token_mint != *project_token.key {
+        msg!(
+            "Project token mint mismatch. Project mint: {}, Provided mint: {}",
+            project.token_mint,
+            project_token.key
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Load contributor record
+    verify_contributor_record(program_id, project_account, contributor_account, record_account)?;
+    let mut record_data = record_account.data.borrow_mut();
+    let mut record = ContributorRecord::unpack(&record_data)?;
+
+    if record.is_refunded {
+        msg!("Contribution already refunded");
+        return Err(UnicornFactoryError::AlreadyRefunded.into());
+    }
+
+    // Tokens delivered at contribution time are burned before the contribution
+    // is paid back, so nobody keeps both the tokens and the refund
+    if record.tokens_claimed > 0 {
+        invoke(
+            &token_instruction::burn(
+                token_program.key,
+                contributor_token_account.key,
+                project_token.key,
+                contributor_account.key,
+                &[],
+                record.tokens_claimed,
+            )?,
+            &[
+                contributor_token_account.clone(),
+                project_token.clone(),
+                contributor_account.clone(),
+            ],
+        )?;
+    }
+
+    // Contributions are paid back pro rata out of what the vault still holds,
+    // so curve sales before the raise failed cannot leave later refunds short
+    let pool = vault_balance(&project, vault_account)?.saturating_sub(project.held_deposits);
+    let refund_amount = if project.total_contributed == 0 {
+        0
+    } else {
+        (record.total_contributed as u128 * pool as u128 / project.total_contributed as u128)
+            .min(record.total_contributed as u128) as u64
+    };
+    withdraw_from_vault(
+        project_account,
+        &project,
+        vault_account,
+        contributor_quote_account,
+        refund_amount,
+    )?;
+
+    record.is_refunded = true;
+    record.pack(&mut record_data);
+    drop(record_data);
+
+    project.total_raised = project.total_raised.saturating_sub(refund_amount);
+    project.total_contributed = project
+        .total_contributed
+        .saturating_sub(record.total_contributed);
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!("Refunded {} to {}", refund_amount, contributor_account.key);
+    Ok(())
+}
+
+// Graduate instruction processor
+fn process_graduate(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Starting project graduation");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let payer_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Payer Account key: {}",
+        payer_account.key
+    );
+
+    let vault_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Vault Account key: {}",
+        vault_account.key
+    );
+
+    let pool_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Pool Account key: {}",
+        pool_account.key
+    );
+
+    let pool_token_vault = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Pool Token Vault key: {}",
+        pool_token_vault.key
+    );
+
+    let pool_quote_vault = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Pool Quote Vault key: {}",
+        pool_quote_vault.key
+    );
+
+    let lp_mint_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 6: LP Mint key: {}",
+        lp_mint_account.key
+    );
+
+    let project_token = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 7: Project Token key: {}",
+        project_token.key
+    );
+
+    let token_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 8: Token Program key: {}",
+        token_program.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 9: System Program key: {}",
+        system_program.key
+    );
+
+    // Verify payer is signer
+    if !payer_account.is_signer {
+        msg!("Payer is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify programs
+    if token_program.key != &spl_token::id() || system_program.key != &system_program::id() {
+        msg!("Invalid token or system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    if project.is_graduated {
+        msg!("Project already graduated");
+        return Err(UnicornFactoryError::AlreadyGraduated.into());
+    }
+
+    if project.is_dissolved {
+        msg!("Project is dissolved");
+        return Err(UnicornFactoryError::ProjectDissolved.into());
+    }
+
+    if project.raise_state != RaiseState::Succeeded {
+        msg!("Project can graduate only after a successful raise");
+        return Err(UnicornFactoryError::RaiseNotSucceeded.into());
+    }
+
+    if project.token_mint != *project_token.key {
+        msg!(
+            "Project token mint mismatch. Project mint: {}, Provided mint: {}",
+            project.token_mint,
+            project_token.key
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    verify_vault(program_id, project_account, &project, vault_account)?;
+
+    // SPL quote projects need the quote mint to open the pool quote vault
+    let quote_mint_account = if project.is_native_quote() {
+        None
+    } else {
+        let quote_mint_account = next_account_info(account_info_iter)?;
+        msg!(
+            "Processing account 10: Quote Mint key: {}",
+            quote_mint_account.key
+        );
+        if *quote_mint_account.key != project.quote_mint {
+            msg!("Invalid quote mint account");
+            return Err(UnicornFactoryError::InvalidQuoteMint.into());
+        }
+        Some(quote_mint_account)
+    };
+
+    // Size the pool from the share of the funding goal milestones were never
+    // allowed to allocate, plus the token reserve
+    let quote_amount = project.graduation_amount(project.funding_goal);
+    let token_amount = project.graduation_token_reserve;
+    if quote_amount == 0 || token_amount == 0 {
+        msg!(
+            "Graduation needs liquidity on both sides. Quote: {}, Tokens: {}",
+            quote_amount,
+            token_amount
+        );
+        return Err(UnicornFactoryError::InvalidGraduationConfig.into());
+    }
+
+    // The pool's tokens must still fit in the supply after everything minted so far
+    let minted = {
+        let mint_data = project_token.try_borrow_data()?;
+        spl_token::state::Mint::unpack(&mint_data)?.supply
+    };
+    let supply_after = minted
+        .checked_add(token_amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    if supply_after > project.total_supply {
+        msg!(
+            "Token supply exceeded. Minted: {}, Pool reserve: {}, Total supply: {}",
+            minted,
+            token_amount,
+            project.total_supply
+        );
+        return Err(UnicornFactoryError::TokenSupplyExceeded.into());
+    }
+
+    // Verify pool PDAs
+    let (expected_pool_pda, pool_bump) =
+        Pubkey::find_program_address(&[b"pool", project_account.key.as_ref()], program_id);
+    if expected_pool_pda != *pool_account.key {
+        msg!(
+            "Invalid pool account PDA. Expected: {}, Got: {}",
+            expected_pool_pda,
+            pool_account.key
+        );
+        return Err(UnicornFactoryError::InvalidPoolAccount.into());
+    }
+
+    let (expected_token_vault, token_vault_bump) =
+        Pubkey::find_program_address(&[b"pool_token", pool_account.key.as_ref()], program_id);
+    let (expected_quote_vault, quote_vault_bump) =
+        Pubkey::find_program_address(&[b"pool_quote", pool_account.key.as_ref()], program_id);
+    let (expected_lp_mint, lp_mint_bump) =
+        Pubkey::find_program_address(&[b"lp_mint", pool_account.key.as_ref()], program_id);
+    if expected_token_vault != *pool_token_vault.key
+        || expected_quote_vault != *pool_quote_vault.key
+        || expected_lp_mint != *lp_mint_account.key
+    {
+        msg!("Invalid pool vault or LP mint account");
+        return Err(UnicornFactoryError::InvalidPoolAccount.into());
+    }
+
+    // Create the pool state account
+    let pool_seeds: &[&[u8]] = &[b"pool", project_account.key.as_ref(), &[pool_bump]];
+    amm::create_pda_account(
+        payer_account,
+        pool_account,
+        system_program,
+        Pool::LEN,
+        program_id,
+        pool_seeds,
+    )?;
+
+    // Create the pool token vault
+    let token_vault_seeds: &[&[u8]] = &[
+        b"pool_token",
+        pool_account.key.as_ref(),
+        &[token_vault_bump],
+    ];
+    amm::create_pool_token_account(
+        payer_account,
+        pool_token_vault,
+        project_token,
+        pool_account,
+        system_program,
+        token_vault_seeds,
+    )?;
+
+    // Create the pool quote vault
+    let quote_vault_seeds: &[&[u8]] = &[
+        b"pool_quote",
+        pool_account.key.as_ref(),
+        &[quote_vault_bump],
+    ];
+    match quote_mint_account {
+        Some(quote_mint_account) => amm::create_pool_token_account(
+            payer_account,
+            pool_quote_vault,
+            quote_mint_account,
+            pool_account,
+            system_program,
+            quote_vault_seeds,
+        )?,
+        None => {
+            let rent = Rent::get()?;
+            invoke(
+                &system_instruction::transfer(
+                    payer_account.key,
+                    pool_quote_vault.key,
+                    rent.minimum_balance(0),
+                ),
+                &[payer_account.clone(), pool_quote_vault.clone()],
+            )?;
+        }
+    }
+
+    // Create the LP mint with the pool PDA as mint authority
+    let lp_mint_seeds: &[&[u8]] = &[b"lp_mint", pool_account.key.as_ref(), &[lp_mint_bump]];
+    amm::create_pda_account(
+        payer_account,
+        lp_mint_account,
+        system_program,
+        spl_token::state::Mint::LEN,
+        &spl_token::id(),
+        lp_mint_seeds,
+    )?;
+
+    let token_decimals = {
+        let mint_data = project_token.try_borrow_data()?;
+        spl_token::state::Mint::unpack(&mint_data)?.decimals
+    };
+
+    invoke(
+        &token_instruction::initialize_mint2(
+            &spl_token::id(),
+            lp_mint_account.key,
+            pool_account.key,
+            None,
+            token_decimals,
+        )?,
+        std::slice::from_ref(lp_mint_account),
+    )?;
+
+    // Mint the reserved token allocation into the pool
+    let seeds = &[
+        b"project".as_ref(),
+        project.creator.as_ref(),
+        &[project.bump],
+    ];
+
+    invoke_signed(
+        &token_instruction::mint_to(
+            token_program.key,
+            project_token.key,
+            pool_token_vault.key,
+            project_account.key,
+            &[],
+            token_amount,
+        )?,
+        &[
+            project_token.clone(),
+            pool_token_vault.clone(),
+            project_account.clone(),
+        ],
+        &[seeds],
+    )?;
+
+    // Move the graduation share of the raise from the project vault into the pool
+    withdraw_from_vault(
+        project_account,
+        &project,
+        vault_account,
+        pool_quote_vault,
+        quote_amount,
+    )?;
+
+    // Seed the pool; the initial liquidity is never minted, so it stays locked
+    let initial_liquidity =
+        amm::integer_sqrt(token_amount as u128 * quote_amount as u128) as u64;
+    let pool = Pool {
+        project: *project_account.key,
+        token_mint: project.token_mint,
+        quote_mint: project.quote_mint,
+        token_reserve: token_amount,
+        quote_reserve: quote_amount,
+        lp_supply: initial_liquidity,
+        locked_liquidity: initial_liquidity,
+        fee_bps: amm::POOL_FEE_BPS,
+        bump: pool_bump,
+        token_vault_bump,
+        quote_vault_bump,
+        lp_mint_bump,
+    };
+
+    {
+        let mut pool_data = pool_account.data.borrow_mut();
+        pool.pack(&mut pool_data);
+    }
+
+    // Update project state
+    project.is_graduated = true;
+    project.total_released = project
+        .total_released
+        .checked_add(quote_amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!(
+        "Project graduated. Pool seeded with {} tokens and {} quote, {} liquidity locked",
+        token_amount,
+        quote_amount,
+        initial_liquidity
+    );
+    Ok(())
+}
+
+// Configure sale round instruction processor
+fn process_configure_sale_round(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    start_time: i64,
+    end_time: i64,
+    price: u64,
+    per_wallet_cap: u64,
+    merkle_root: [u8; 32],
+) -> ProgramResult {
+    msg!("Starting sale round configuration");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let sale_round_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Sale Round Account key: {}",
+        sale_round_account.key
+    );
+
+    let authority_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Authority Account key: {}",
+        authority_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: System Program key: {}",
+        system_program.key
+    );
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    // Verify authority: the authority key itself or a quorum of its council
+    let payer_account =
+        authorize(program_id, project_account, &project, authority_account, accounts)?;
+
+    if !project.is_active {
+        msg!("Project is not active");
+        return Err(UnicornFactoryError::ProjectNotActive.into());
+    }
+
+    // A project gets a single presale round, which has to end in the future
+    let clock = Clock::get()?;
+    if project.sale_round_end != 0 {
+        msg!("Sale round already configured");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    if start_time >= end_time || end_time <= clock.unix_timestamp || price == 0 {
+        msg!(
+            "Invalid sale round. start={}, end={}, price={}",
+            start_time,
+            end_time,
+            price
+        );
+        return Err(UnicornFactoryError::InvalidSaleRound.into());
+    }
+
+    // Verify sale round PDA
+    let (expected_sale_round_pda, sale_round_bump) = Pubkey::find_program_address(
+        &[b"sale_round", project_account.key.as_ref()],
+        program_id,
+    );
+
+    if expected_sale_round_pda != *sale_round_account.key {
+        msg!(
+            "Invalid sale round PDA. Expected: {}, Got: {}",
+            expected_sale_round_pda,
+            sale_round_account.key
+        );
+        return Err(UnicornFactoryError::InvalidSaleRound.into());
+    }
+
+    // Calculate rent
+    let rent = Rent::get()?;
+    let space = SaleRound::LEN;
+    let lamports = rent.minimum_balance(space);
+
+    // Create sale round account using invoke_signed
+    let sale_round_seeds = &[
+        b"sale_round".as_ref(),
+        project_account.key.as_ref(),
+        &[sale_round_bump],
+    ];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer_account.key,
+            sale_round_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        ),
+        &[
+            payer_account.clone(),
+            sale_round_account.clone(),
+            system_program.clone(),
+        ],
+        &[sale_round_seeds],
+    )?;
+
+    let sale_round = SaleRound {
+        project: *project_account.key,
+        start_time,
+        end_time,
+        price,
+        per_wallet_cap,
+        merkle_root,
+        total_sold: 0,
+    };
+
+    {
+        let mut sale_round_data = sale_round_account.data.borrow_mut();
+        sale_round.pack(&mut sale_round_data);
+    }
+
+    // Update project state
+    project.sale_round_end = end_time;
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!(
+        "Sale round configured from {} to {} at price {}",
+        start_time,
+        end_time,
+        price
+    );
+    Ok(())
+}
+
+// Add funding round instruction processor
+//
+// Rounds are scheduled before the raise starts and run back to back. Every
+// existing round account follows the system program, in order, so the new
+// round can be checked against their windows and goals.
+fn process_add_funding_round(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: FundingRoundParams,
+) -> ProgramResult {
+    msg!("Starting funding round creation");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let round_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Round Account key: {}",
+        round_account.key
+    );
+
+    let authority_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Authority Account key: {}",
+        authority_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: System Program key: {}",
+        system_program.key
+    );
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    // Verify authority: the authority key itself or a quorum of its council
+    let payer_account =
+        authorize(program_id, project_account, &project, authority_account, accounts)?;
+
+    if !project.is_active {
+        msg!("Project is not active");
+        return Err(UnicornFactoryError::ProjectNotActive.into());
+    }
+
+    if params.goal == 0 || params.price == 0 || params.start_time >= params.end_time {
+        msg!(
+            "Invalid funding round. goal={}, price={}, start={}, end={}",
+            params.goal,
+            params.price,
+            params.start_time,
+            params.end_time
+        );
+        return Err(UnicornFactoryError::InvalidFundingRound.into());
+    }
+
+    let clock = Clock::get()?;
+    if params.start_time < clock.unix_timestamp {
+        msg!(
+            "Funding round starts in the past. Start: {}, Now: {}",
+            params.start_time,
+            clock.unix_timestamp
+        );
+        return Err(UnicornFactoryError::InvalidFundingRound.into());
+    }
+
+    if project.total_raised > 0 {
+        msg!("Funding rounds cannot be added once the raise has taken contributions");
+        return Err(UnicornFactoryError::InvalidFundingRound.into());
+    }
+
+    let mut goals = params.goal;
+    for index in 0..project.round_count {
+        let existing_account = next_account_info(account_info_iter)?;
+        let existing = load_funding_round(program_id, project_account, existing_account, index)?;
+
+        if index == 0 && clock.unix_timestamp >= existing.start_time {
+            msg!("Funding rounds cannot be added once the raise has started");
+            return Err(UnicornFactoryError::InvalidFundingRound.into());
+        }
+
+        if params.start_time <= existing.end_time {
+            msg!(
+                "Funding round overlaps round {} ending at {}",
+                index,
+                existing.end_time
+            );
+            return Err(UnicornFactoryError::InvalidFundingRound.into());
+        }
+
+        goals = goals
+            .checked_add(existing.goal)
+            .ok_or(UnicornFactoryError::Overflow)?;
+    }
+
+    if goals > project.funding_goal {
+        msg!(
+            "Funding round goals exceed the funding goal. Rounds: {}, Goal: {}",
+            goals,
+            project.funding_goal
+        );
+        return Err(UnicornFactoryError::InvalidFundingRound.into());
+    }
+
+    // Rounds are appended in order
+    let index = project.round_count;
+    let round_count = index.checked_add(1).ok_or(UnicornFactoryError::Overflow)?;
+
+    // Verify round PDA
+    let (expected_round_pda, round_bump) = Pubkey::find_program_address(
+        &[b"round", project_account.key.as_ref(), &[index]],
+        program_id,
+    );
+
+    if expected_round_pda != *round_account.key {
+        msg!(
+            "Invalid funding round PDA. Expected: {}, Got: {}",
+            expected_round_pda,
+            round_account.key
+        );
+        return Err(UnicornFactoryError::InvalidFundingRound.into());
+    }
+
+    // Calculate rent
+    let rent = Rent::get()?;
+    let space = FundingRound::LEN;
+    let lamports = rent.minimum_balance(space);
+
+    // Create round account using invoke_signed
+    let round_seeds = &[
+        b"round".as_ref(),
+        project_account.key.as_ref(),
+        &[index],
+        &[round_bump],
+    ];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer_account.key,
+            round_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        ),
+        &[
+            payer_account.clone(),
+            round_account.clone(),
+            system_program.clone(),
+        ],
+        &[round_seeds],
+    )?;
+
+    let round = FundingRound {
+        project: *project_account.key,
+        index,
+        goal: params.goal,
+        raised: 0,
+        pricing_mode: params.pricing_mode,
+        price: params.price,
+        start_time: params.start_time,
+        end_time: params.end_time,
+        merkle_root: params.merkle_root,
+    };
+
+    {
+        let mut round_data = round_account.data.borrow_mut();
+        round.pack(&mut round_data);
+    }
+
+    // Update project state
+    project.round_count = round_count;
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!(
+        "Funding round {} added with goal {} from {} to {}",
+        index,
+        round.goal,
+        round.start_time,
+        round.end_time
+    );
+    Ok(())
+}
+
+// Settle bid instruction processor
+//
+// Delivers an auction bidder's tokens and refunds whatever they paid above the
+// final clearing price.
+fn process_settle_bid(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Starting auction bid settlement");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let contributor_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Contributor Account key: {}",
+        contributor_account.key
+    );
+
+    let record_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Contributor Record key: {}",
+        record_account.key
+    );
+
+    let contributor_token_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Contributor Token Account key: {}",
+        contributor_token_account.key
+    );
+
+    let project_token = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Project Token key: {}",
+        project_token.key
+    );
+
+    let token_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Token Program key: {}",
+        token_program.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 6: System Program key: {}",
+        system_program.key
+    );
+
+    let vault_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 7: Vault Account key: {}",
+        vault_account.key
+    );
+
+    // Verify contributor is signer
+    if !contributor_account.is_signer {
+        msg!("Contributor is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify programs
+    if token_program.key != &spl_token::id() || system_program.key != &system_program::id() {
+        msg!("Invalid token or system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    if project.launch_mode != LaunchMode::DutchAuction {
+        msg!("Project is not an auction");
+        return Err(UnicornFactoryError::InvalidLaunchMode.into());
+    }
+
+    if project.raise_state != RaiseState::Succeeded {
+        msg!("Bids settle only after a successful auction");
+        return Err(UnicornFactoryError::RaiseNotSucceeded.into());
+    }
+
+    if project.token_mint != *project_token.key {
+        msg!(
+            "Project token mint mismatch. Project mint: {}, Provided mint: {}",
+            project.token_mint,
+            project_token.key
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    verify_vault(program_id, project_account, &project, vault_account)?;
+
+    // SPL quote projects refund to the contributor's quote token account
+    let contributor_quote_account = if project.is_native_quote() {
+        contributor_account
+    } else {
+        let contributor_quote_account = next_account_info(account_info_iter)?;
+        msg!(
+            "Processing account 8: Contributor Quote Account key: {}",
+            contributor_quote_account.key
+        );
+        contributor_quote_account
+    };
+
+    // Load contributor record
+    verify_contributor_record(program_id, project_account, contributor_account, record_account)?;
+    let mut record_data = record_account.data.borrow_mut();
+    let mut record = ContributorRecord::unpack(&record_data)?;
+
+    if record.tokens_purchased == record.tokens_claimed {
+        msg!("Nothing to settle");
+        return Err(UnicornFactoryError::NothingToClaim.into());
+    }
+
+    // Every bid pays the clearing price and the rest of the deposit is
+    // returned. When the deposits would buy more than the supply at the
+    // clearing price, each bid gets the quantity it locked in; otherwise each
+    // deposit is filled at the clearing price, which the supply can always
+    // afford. The clearing price is at most any bid's own price, so neither
+    // fill is below the quantity bid.
+    let fills_at_clearing_price = project.fills_at_clearing_price();
+    let tokens = if fills_at_clearing_price {
+        record.total_contributed / project.clearing_price
+    } else {
+        record.tokens_purchased
+    };
+    let cost = u64::try_from(tokens as u128 * project.clearing_price as u128)
+        .map_err(|_| UnicornFactoryError::Overflow)?;
+    let mut refund_amount = record.total_contributed.saturating_sub(cost);
+
+    if project.is_dissolved {
+        // Nothing is minted once the project is dissolved; the bid is paid
+        // out at the redemption rate the way the dissolution counted it
+        refund_amount = if fills_at_clearing_price {
+            project.deposit_redemption_value(record.total_contributed, project.clearing_price)
+        } else {
+            refund_amount
+                .checked_add(project.redemption_value(tokens))
+                .ok_or(UnicornFactoryError::Overflow)?
+        };
+    } else if tokens > 0 {
+        let seeds = &[
+            b"project".as_ref(),
+            project.creator.as_ref(),
+            &[project.bump],
+        ];
+
+        invoke_signed(
+            &token_instruction::mint_to(
+                token_program.key,
+                project_token.key,
+                contributor_token_account.key,
+                project_account.key,
+                &[],
+                tokens,
+            )?,
+            &[
+                project_token.clone(),
+                contributor_token_account.clone(),
+                project_account.clone(),
+            ],
+            &[seeds],
+        )?;
+    }
+
+    if refund_amount > 0 {
+        withdraw_from_vault(
+            project_account,
+            &project,
+            vault_account,
+            contributor_quote_account,
+            refund_amount,
+        )?;
+    }
+
+    project.unclaimed_tokens = project
+        .unclaimed_tokens
+        .saturating_sub(record.tokens_purchased);
+    project.unsettled_deposits = project
+        .unsettled_deposits
+        .saturating_sub(record.total_contributed);
+
+    record.tokens_purchased = tokens;
+    record.tokens_claimed = tokens;
+    record.total_contributed = cost;
+    record.pack(&mut record_data);
+    drop(record_data);
+
+    // What the bid paid is now raised; the refund never was
+    project.total_raised = project
+        .total_raised
+        .checked_add(cost)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!(
+        "Settled {} tokens at {}, refunded {}",
+        tokens,
+        project.clearing_price,
+        refund_amount
+    );
+    Ok(())
+}
+
+// Commit instruction processor
+fn process_commit(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    msg!("Starting batch auction commitment");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let user_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: User Account key: {}",
+        user_account.key
+    );
+
+    let commitment_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Commitment Account key: {}",
+        commitment_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: System Program key: {}",
+        system_program.key
+    );
+
+    let vault_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Vault Account key: {}",
+        vault_account.key
+    );
+
+    // Verify user is signer
+    if !user_account.is_signer {
+        msg!("User is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    if project.is_dissolved {
+        msg!("Project is dissolved");
+        return Err(UnicornFactoryError::ProjectDissolved.into());
+    }
+
+    if project.launch_mode != LaunchMode::BatchAuction {
+        msg!("Project is not a batch auction");
+        return Err(UnicornFactoryError::InvalidLaunchMode.into());
+    }
+
+    if !project.is_active {
+        return Err(UnicornFactoryError::ProjectNotActive.into());
+    }
+
+    if project.is_paused {
+        msg!("Project is paused");
+        return Err(UnicornFactoryError::ProjectPaused.into());
+    }
+
+    let clock = Clock::get()?;
+    if clock.unix_timestamp < project.auction_start_time {
+        msg!("Commitments open at {}", project.auction_start_time);
+        return Err(UnicornFactoryError::AuctionNotStarted.into());
+    }
+    if clock.unix_timestamp > project.auction_end_time {
+        msg!("Commitments closed at {}", project.auction_end_time);
+        return Err(UnicornFactoryError::RaiseClosed.into());
+    }
+
+    if amount == 0 || amount < project.min_contribution {
+        msg!(
+            "Commitment below minimum. Amount: {}, Minimum: {}",
+            amount,
+            project.min_contribution
+        );
+        return Err(UnicornFactoryError::ContributionBelowMinimum.into());
+    }
+
+    verify_vault(program_id, project_account, &project, vault_account)?;
+
+    // SPL quote projects are paid from the user's quote token account
+    let user_quote_account = if project.is_native_quote() {
+        user_account
+    } else {
+        let user_quote_account = next_account_info(account_info_iter)?;
+        msg!(
+            "Processing account 5: User Quote Account key: {}",
+            user_quote_account.key
+        );
+        user_quote_account
+    };
+
+    // Load the commitment, creating it on the user's first commit
+    let commitment_bump =
+        verify_commitment(program_id, project_account, user_account, commitment_account)?;
+    let mut commitment = if commitment_account.data_is_empty() {
+        let rent = Rent::get()?;
+        let space = Commitment::LEN;
+        let lamports = rent.minimum_balance(space);
+
+        let commitment_seeds = &[
+            b"commitment".as_ref(),
+            project_account.key.as_ref(),
+            user_account.key.as_ref(),
+            &[commitment_bump],
+        ];
+
+        invoke_signed(
+            &system_instruction::create_account(
+                user_account.key,
+                commitment_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            ),
+            &[
+                user_account.clone(),
+                commitment_account.clone(),
+                system_program.clone(),
+            ],
+            &[commitment_seeds],
+        )?;
+
+        Commitment {
+            project: *project_account.key,
+            user: *user_account.key,
+            amount: 0,
+            is_claimed: false,
+        }
+    } else {
+        let commitment_data = commitment_account.data.borrow();
+        Commitment::unpack(&commitment_data)?
+    };
+
+    let committed = commitment
+        .amount
+        .checked_add(amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    if project.max_contribution_per_wallet != 0 && committed > project.max_contribution_per_wallet
+    {
+        msg!(
+            "Wallet commitment cap exceeded. Committed: {}, Cap: {}",
+            committed,
+            project.max_contribution_per_wallet
+        );
+        return Err(UnicornFactoryError::ContributionCapExceeded.into());
+    }
+
+    deposit_to_vault(
+        &project,
+        user_account,
+        user_quote_account,
+        vault_account,
+        amount,
+    )?;
+
+    commitment.amount = committed;
+    let mut commitment_data = commitment_account.data.borrow_mut();
+    commitment.pack(&mut commitment_data);
+    drop(commitment_data);
+
+    // Update project state
+    project.total_raised = project
+        .total_raised
+        .checked_add(amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    project.unsettled_deposits = project
+        .unsettled_deposits
+        .checked_add(amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!("Committed {}, total committed {}", amount, project.total_raised);
+    Ok(())
+}
+
+// Settle instruction processor
+//
+// Closes a batch auction once its window has passed. All commitments clear at
+// one price: total committed over the supply, never below the floor price.
+fn process_settle(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Starting batch auction settlement");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    if project.is_dissolved {
+        msg!("Project is dissolved");
+        return Err(UnicornFactoryError::ProjectDissolved.into());
+    }
+
+    if project.launch_mode != LaunchMode::BatchAuction {
+        msg!("Project is not a batch auction");
+        return Err(UnicornFactoryError::InvalidLaunchMode.into());
+    }
+
+    if project.raise_state != RaiseState::Open {
+        msg!("Auction already settled");
+        return Err(UnicornFactoryError::RaiseClosed.into());
+    }
+
+    let clock = Clock::get()?;
+    if clock.unix_timestamp <= project.auction_end_time {
+        msg!("Commitment window is still open");
+        return Err(UnicornFactoryError::RaiseStillOpen.into());
+    }
+
+    let clearing_price = project
+        .total_raised
+        .div_ceil(project.auction_supply)
+        .max(project.auction_floor_price)
+        .max(1);
+    project.auction_tokens_sold =
+        (project.total_raised / clearing_price).min(project.auction_supply);
+
+    // Keep the committed total so failed auctions can refund in full
+    let total_committed = project.total_raised;
+    project.close_auction(clearing_price)?;
+    if project.raise_state == RaiseState::Failed {
+        project.total_raised = total_committed;
+    }
+
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!(
+        "Batch auction settled with state {:?} at price {}, {} tokens sold",
+        project.raise_state,
+        project.clearing_price,
+        project.auction_tokens_sold
+    );
+    Ok(())
+}
+
+// Claim commitment instruction processor
+//
+// After a successful settlement users receive tokens in proportion to their
+// commitment and get back the remainder below one token's price. A failed
+// auction refunds the whole commitment.
+fn process_claim_commitment(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Starting commitment claim");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let user_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: User Account key: {}",
+        user_account.key
+    );
+
+    let commitment_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Commitment Account key: {}",
+        commitment_account.key
+    );
+
+    let user_token_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: User Token Account key: {}",
+        user_token_account.key
+    );
+
+    let project_token = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Project Token key: {}",
+        project_token.key
+    );
+
+    let token_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Token Program key: {}",
+        token_program.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 6: System Program key: {}",
+        system_program.key
+    );
+
+    let vault_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 7: Vault Account key: {}",
+        vault_account.key
+    );
+
+    // Verify user is signer
+    if !user_account.is_signer {
+        msg!("User is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify programs
+    if token_program.key != &spl_token::id() || system_program.key != &system_program::id() {
+        msg!("Invalid token or system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    if project.launch_mode != LaunchMode::BatchAuction {
+        msg!("Project is not a batch auction");
+        return Err(UnicornFactoryError::InvalidLaunchMode.into());
+    }
+
+    if project.raise_state == RaiseState::Open {
+        msg!("Auction not settled yet");
+        return Err(UnicornFactoryError::RaiseStillOpen.into());
+    }
+
+    if project.token_mint != *project_token.key {
+        msg!(
+            "Project token mint mismatch. Project mint: {}, Provided mint: {}",
+            project.token_mint,
+            project_token.key
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    verify_vault(program_id, project_account, &project, vault_account)?;
+
+    // SPL quote projects refund to the user's quote token account
+    let user_quote_account = if project.is_native_quote() {
+        user_account
+    } else {
+        let user_quote_account = next_account_info(account_info_iter)?;
+        msg!(
+            "Processing account 8: User Quote Account key: {}",
+            user_quote_account.key
+        );
+        user_quote_account
+    };
+
+    // Load commitment
+    verify_commitment(program_id, project_account, user_account, commitment_account)?;
+    let mut commitment_data = commitment_account.data.borrow_mut();
+    let mut commitment = Commitment::unpack(&commitment_data)?;
+
+    if commitment.is_claimed {
+        msg!("Commitment already claimed");
+        return Err(UnicornFactoryError::NothingToClaim.into());
+    }
+
+    let (tokens, mut refund_amount) = if project.raise_state == RaiseState::Succeeded {
+        let tokens = commitment.amount / project.clearing_price;
+        (tokens, commitment.amount - tokens * project.clearing_price)
+    } else {
+        (0, commitment.amount)
+    };
+
+    if project.is_dissolved {
+        // Nothing is minted once the project is dissolved; the commitment is
+        // paid out at the redemption rate the way the dissolution counted it
+        refund_amount =
+            project.deposit_redemption_value(commitment.amount, project.clearing_price);
+    } else if tokens > 0 {
+        let seeds = &[
+            b"project".as_ref(),
+            project.creator.as_ref(),
+            &[project.bump],
+        ];
+
+        invoke_signed(
+            &token_instruction::mint_to(
+                token_program.key,
+                project_token.key,
+                user_token_account.key,
+                project_account.key,
+                &[],
+                tokens,
+            )?,
+            &[
+                project_token.clone(),
+                user_token_account.clone(),
+                project_account.clone(),
+            ],
+            &[seeds],
+        )?;
+    }
+
+    if refund_amount > 0 {
+        withdraw_from_vault(
+            project_account,
+            &project,
+            vault_account,
+            user_quote_account,
+            refund_amount,
+        )?;
+    }
+
+    commitment.is_claimed = true;
+    commitment.pack(&mut commitment_data);
+    drop(commitment_data);
+
+    // A successful auction raises what the commitment paid for its tokens,
+    // while full refunds of a failed one come out of the committed total
+    if project.raise_state == RaiseState::Succeeded {
+        project.total_raised = project
+            .total_raised
+            .checked_add(tokens * project.clearing_price)
+            .ok_or(UnicornFactoryError::Overflow)?;
+    } else {
+        project.total_raised = project.total_raised.saturating_sub(refund_amount);
+    }
+    project.unsettled_deposits = project
+        .unsettled_deposits
+        .saturating_sub(commitment.amount);
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!(
+        "Claimed {} tokens and {} refund for {}",
+        tokens,
+        refund_amount,
+        user_account.key
+    );
+    Ok(())
+}
+
+// Create council instruction processor
+fn process_create_council(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    threshold: u8,
+    members: Vec<Pubkey>,
+    treasury: Pubkey,
+) -> ProgramResult {
+    msg!("Starting council creation");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let council_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Council Account key: {}",
+        council_account.key
+    );
+
+    let authority_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Authority Account key: {}",
+        authority_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: System Program key: {}",
+        system_program.key
+    );
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let project = load_project(program_id, project_account)?;
+
+    // Verify authority: the authority key itself or a quorum of its council
+    let payer_account =
+        authorize(program_id, project_account, &project, authority_account, accounts)?;
+
+    // Verify signer set
+    verify_council_members(threshold, &members, &treasury)?;
+
+    // Verify council PDA
+    let (expected_council_pda, council_bump) =
+        Pubkey::find_program_address(&[b"council", project_account.key.as_ref()], program_id);
+
+    if expected_council_pda != *council_account.key {
+        msg!(
+            "Invalid council PDA. Expected: {}, Got: {}",
+            expected_council_pda,
+            council_account.key
+        );
+        return Err(UnicornFactoryError::InvalidCouncil.into());
+    }
+
+    // Calculate rent
+    let rent = Rent::get()?;
+    let space = ProjectCouncil::LEN;
+    let lamports = rent.minimum_balance(space);
+
+    // Create council account using invoke_signed
+    let council_seeds = &[
+        b"council".as_ref(),
+        project_account.key.as_ref(),
+        &[council_bump],
+    ];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer_account.key,
+            council_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        ),
+        &[
+            payer_account.clone(),
+            council_account.clone(),
+            system_program.clone(),
+        ],
+        &[council_seeds],
+    )?;
+
+    let council = ProjectCouncil {
+        project: *project_account.key,
+        threshold,
+        members,
+        treasury,
+    };
+
+    {
+        let mut council_data = council_account.data.borrow_mut();
+        council.pack(&mut council_data);
+    }
+
+    msg!(
+        "Council created with {} members and threshold {}",
+        council.members.len(),
+        threshold
+    );
+    Ok(())
+}
+
+// Update council instruction processor
+//
+// Replaces the council's members, threshold and treasury. The change needs a
+// quorum of the current members, who sign anywhere in the instruction's
+// accounts.
+fn process_update_council(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    threshold: u8,
+    members: Vec<Pubkey>,
+    treasury: Pubkey,
+) -> ProgramResult {
+    msg!("Starting council update");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let council_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Council Account key: {}",
+        council_account.key
+    );
+
+    if project_account.owner != program_id {
+        msg!("Project account not owned by program");
+        return Err(UnicornFactoryError::InvalidProjectAccount.into());
+    }
+
+    // Verify the current council approves
+    authorize_as(
+        program_id,
+        project_account,
+        council_account.key,
+        council_account,
+        accounts,
+    )?;
+
+    verify_council_members(threshold, &members, &treasury)?;
+
+    let council = ProjectCouncil {
+        project: *project_account.key,
+        threshold,
+        members,
+        treasury,
+    };
+
+    {
+        let mut council_data = council_account.data.borrow_mut();
+        council.pack(&mut council_data);
+    }
+
+    msg!(
+        "Council updated to {} members and threshold {}",
+        council.members.len(),
+        threshold
+    );
+    Ok(())
+}
+
+// Transfer authority instruction processor
+//
+// First half of the handoff: the current authority nominates a successor, who
+// takes over only once they accept.
+fn process_transfer_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_authority: Pubkey,
+) -> ProgramResult {
+    msg!("Starting authority transfer");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let authority_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Authority Account key: {}",
+        authority_account.key
+    );
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    // Verify authority: the authority key itself or a quorum of its council
+    authorize(program_id, project_account, &project, authority_account, accounts)?;
+
+    // Nominating the default key cancels a pending transfer
+    project.pending_authority = new_authority;
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!("Authority transfer to {} pending acceptance", new_authority);
+    Ok(())
+}
+
+// Accept authority instruction processor
+fn process_accept_authority(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Starting authority acceptance");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let new_authority_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: New Authority Account key: {}",
+        new_authority_account.key
+    );
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    if project.pending_authority == Pubkey::default() {
+        msg!("No authority transfer pending");
+        return Err(UnicornFactoryError::NoPendingAuthority.into());
+    }
+
+    // The nominee accepts by signing, or by council quorum when it is the council
+    authorize_as(
+        program_id,
+        project_account,
+        &project.pending_authority,
+        new_authority_account,
+        accounts,
+    )?;
+
+    project.authority = project.pending_authority;
+    project.pending_authority = Pubkey::default();
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!("Project authority is now {}", project.authority);
+    Ok(())
+}
+
+// Cancel proposal instruction processor
+//
+// The guardian can stop a proposal at any point before its timelock expires.
+fn process_cancel_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proposal_id: u64,
+) -> ProgramResult {
+    msg!("Starting proposal cancellation");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let proposal_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Proposal Account key: {}",
+        proposal_account.key
+    );
+
+    let guardian_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Guardian Account key: {}",
+        guardian_account.key
+    );
+
+    // Load and verify project
+    let project = load_project(program_id, project_account)?;
+
+    // Verify guardian is configured and is signer
+    if project.governance.guardian == Pubkey::default()
+        || !guardian_account.is_signer
+        || guardian_account.key != &project.governance.guardian
+    {
+        msg!("Invalid guardian or guardian is not signer");
+        return Err(UnicornFactoryError::InvalidGuardian.into());
+    }
+
+    let mut proposal = load_proposal(program_id, project_account, proposal_account, proposal_id)?;
+
+    if proposal.is_executed {
+        msg!("Proposal {} is already executed", proposal_id);
+        return Err(UnicornFactoryError::ProposalAlreadyExecuted.into());
+    }
+
+    if proposal.is_cancelled {
+        msg!("Proposal {} was already cancelled", proposal_id);
+        return Err(UnicornFactoryError::ProposalCancelled.into());
+    }
+
+    let clock = Clock::get()?;
+    if clock.unix_timestamp > proposal.executable_at(&project.governance) {
+        msg!("Timelock for proposal {} has passed", proposal_id);
+        return Err(UnicornFactoryError::VetoWindowClosed.into());
+    }
+
+    proposal.is_cancelled = true;
+    let mut proposal_data = proposal_account.data.borrow_mut();
+    proposal.pack(&mut proposal_data);
+    drop(proposal_data);
+
+    msg!("Proposal {} cancelled by guardian", proposal_id);
+    Ok(())
+}
+
+// Veto vote instruction processor
+//
+// During the timelock token holders can veto a passed proposal, weighted by
+// their token balance. Reaching the project's veto threshold, measured against
+// the token supply, cancels the proposal.
+fn process_veto_vote(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proposal_id: u64,
+) -> ProgramResult {
+    msg!("Starting veto vote");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let proposal_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Proposal Account key: {}",
+        proposal_account.key
+    );
+
+    let voter_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Voter Account key: {}",
+        voter_account.key
+    );
+
+    let voter_escrow_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Voter Escrow key: {}",
+        voter_escrow_account.key
+    );
+
+    let project_token = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Project Token key: {}",
+        project_token.key
+    );
+
+    let veto_record_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Veto Record key: {}",
+        veto_record_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 6: System Program key: {}",
+        system_program.key
+    );
+
+    // Verify voter is signer
+    if !voter_account.is_signer {
+        msg!("Voter is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and verify project
+    let project = load_project(program_id, project_account)?;
+
+    if project.governance.veto_threshold_bps == 0 {
+        msg!("Veto votes are disabled for this project");
+        return Err(UnicornFactoryError::VetoWindowClosed.into());
+    }
+
+    if project.token_mint != *project_token.key {
+        msg!(
+            "Project token mint mismatch. Project mint: {}, Provided mint: {}",
+            project.token_mint,
+            project_token.key
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut proposal = load_proposal(program_id, project_account, proposal_account, proposal_id)?;
+
+    if proposal.is_executed || proposal.is_cancelled {
+        msg!("Proposal {} is no longer pending", proposal_id);
+        return Err(UnicornFactoryError::VetoWindowClosed.into());
+    }
+
+    // Vetoes are only open for passed proposals while they are timelocked
+    let clock = Clock::get()?;
+    if !proposal.passed()
+        || clock.unix_timestamp <= proposal.tally_end()
+        || clock.unix_timestamp > proposal.executable_at(&project.governance)
+    {
+        msg!("Proposal {} is not in its veto window", proposal_id);
+        return Err(UnicornFactoryError::VetoWindowClosed.into());
+    }
+
+    // Weight the veto by the voter's escrow, locked until the veto window closes
+    let weight = voting_weight(
+        program_id,
+        project_account,
+        voter_escrow_account,
+        voter_account.key,
+        proposal.executable_at(&project.governance),
+    )?;
+    if weight == 0 {
+        msg!("Voter has no escrowed project tokens");
+        return Err(UnicornFactoryError::InvalidAmount.into());
+    }
+
+    let supply = {
+        let mint_data = project_token.try_borrow_data()?;
+        spl_token::state::Mint::unpack(&mint_data)?.supply
+    };
+
+    // Verify veto record PDA; its existence marks the voter as having vetoed
+    let (expected_veto_pda, veto_bump) = Pubkey::find_program_address(
+        &[
+            b"veto",
+            proposal_account.key.as_ref(),
+            voter_account.key.as_ref(),
+        ],
+        program_id,
+    );
+
+    if expected_veto_pda != *veto_record_account.key {
+        msg!(
+            "Invalid veto record PDA. Expected: {}, Got: {}",
+            expected_veto_pda,
+            veto_record_account.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if !veto_record_account.data_is_empty() {
+        msg!("Voter already vetoed proposal {}", proposal_id);
+        return Err(UnicornFactoryError::AlreadyVoted.into());
+    }
+
+    let rent = Rent::get()?;
+    let space = VetoRecord::LEN;
+    let lamports = rent.minimum_balance(space);
+
+    let veto_seeds = &[
+        b"veto".as_ref(),
+        proposal_account.key.as_ref(),
+        voter_account.key.as_ref(),
+        &[veto_bump],
+    ];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            voter_account.key,
+            veto_record_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        ),
+        &[
+            voter_account.clone(),
+            veto_record_account.clone(),
+            system_program.clone(),
+        ],
+        &[veto_seeds],
+    )?;
+
+    let veto_record = VetoRecord {
+        proposal: *proposal_account.key,
+        voter: *voter_account.key,
+        weight,
+    };
+
+    {
+        let mut veto_data = veto_record_account.data.borrow_mut();
+        veto_record.pack(&mut veto_data);
+    }
+
+    // Tally the veto and cancel once the threshold is reached
+    proposal.veto_votes = proposal
+        .veto_votes
+        .checked_add(weight)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    if proposal.veto_votes as u128 * 10_000
+        >= supply as u128 * project.governance.veto_threshold_bps as u128
+    {
+        proposal.is_cancelled = true;
+        msg!("Veto threshold reached, proposal {} cancelled", proposal_id);
+    }
+
+    let mut proposal_data = proposal_account.data.borrow_mut();
+    proposal.pack(&mut proposal_data);
+    drop(proposal_data);
+
+    msg!(
+        "Veto of {} recorded on proposal {}, total {}",
+        weight,
+        proposal_id,
+        proposal.veto_votes
+    );
+    Ok(())
+}
+
+// Delegate instruction processor
+fn process_delegate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    delegate: Pubkey,
+) -> ProgramResult {
+    msg!("Starting delegation");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let delegator_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Delegator Account key: {}",
+        delegator_account.key
+    );
+
+    let delegation_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Delegation Record key: {}",
+        delegation_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: System Program key: {}",
+        system_program.key
+    );
+
+    // Verify delegator is signer
+    if !delegator_account.is_signer {
+        msg!("Delegator is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if project_account.owner != program_id {
+        msg!("Project account is not owned by this program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if delegate == *delegator_account.key {
+        msg!("Cannot delegate to yourself");
+        return Err(UnicornFactoryError::InvalidDelegation.into());
+    }
+
+    let delegation_bump = verify_delegation_record(
+        program_id,
+        project_account,
+        delegator_account.key,
+        delegation_account,
+    )?;
+
+    // Create the record on first delegation; later calls switch the delegate
+    if delegation_account.data_is_empty() {
+        let rent = Rent::get()?;
+        let space = DelegationRecord::LEN;
+        let lamports = rent.minimum_balance(space);
+
+        let delegation_seeds = &[
+            b"delegation".as_ref(),
+            project_account.key.as_ref(),
+            delegator_account.key.as_ref(),
+            &[delegation_bump],
+        ];
+
+        invoke_signed(
+            &system_instruction::create_account(
+                delegator_account.key,
+                delegation_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            ),
+            &[
+                delegator_account.clone(),
+                delegation_account.clone(),
+                system_program.clone(),
+            ],
+            &[delegation_seeds],
+        )?;
+    }
+
+    let delegation = DelegationRecord {
+        project: *project_account.key,
+        delegator: *delegator_account.key,
+        delegate,
+    };
+
+    let mut delegation_data = delegation_account.data.borrow_mut();
+    delegation.pack(&mut delegation_data);
+    drop(delegation_data);
+
+    msg!("{} now delegates votes to {}", delegator_account.key, delegate);
+    Ok(())
+}
+
+// Undelegate instruction processor
+fn process_undelegate(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Starting undelegation");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let delegator_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Delegator Account key: {}",
+        delegator_account.key
+    );
+
+    let delegation_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Delegation Record key: {}",
+        delegation_account.key
+    );
+
+    // Verify delegator is signer
+    if !delegator_account.is_signer {
+        msg!("Delegator is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    verify_delegation_record(
+        program_id,
+        project_account,
+        delegator_account.key,
+        delegation_account,
+    )?;
+
+    if delegation_account.owner != program_id || delegation_account.data_is_empty() {
+        msg!("No active delegation for {}", delegator_account.key);
+        return Err(UnicornFactoryError::InvalidDelegation.into());
+    }
+
+    // Close the record and return its rent to the delegator. Votes a delegate
+    // already cast with this weight stay recorded on their proposals.
+    close_record_account(delegation_account, delegator_account)?;
+
+    msg!("{} no longer delegates votes", delegator_account.key);
+    Ok(())
+}
+
+// Change vote and withdraw vote instruction processor
+//
+// Moves the voter's recorded weight to `new_choice`, or removes it from the tally
+// and closes the record when `new_choice` is None so the voter can vote again.
+// Accounts after the voter's vote record are vote records of delegators whose
+// weight the voter cast; they are revised along with the voter's own vote.
+fn process_revise_vote(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proposal_id: u64,
+    new_choice: Option<u8>,
+) -> ProgramResult {
+    msg!("Starting vote revision");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let proposal_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Proposal Account key: {}",
+        proposal_account.key
+    );
+
+    let voter_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Voter Account key: {}",
+        voter_account.key
+    );
+
+    let vote_record_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Vote Record key: {}",
+        vote_record_account.key
+    );
+
+    // Verify voter is signer
+    if !voter_account.is_signer {
+        msg!("Voter is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut proposal = load_proposal(program_id, project_account, proposal_account, proposal_id)?;
+    verify_voting_open(&proposal)?;
+
+    if let Some(choice) = new_choice {
+        // Secret ballots are changed by withdrawing and committing again
+        if proposal.is_secret() || choice == VOTE_HIDDEN || proposal.tally_mut(choice).is_none() {
+            msg!("Vote on proposal {} cannot be changed to {}", proposal_id, choice);
+            return Err(UnicornFactoryError::InvalidVoteChoice.into());
+        }
+    }
+
+    let mut revised_weight: u64 = 0;
+    let records = std::iter::once(vote_record_account).chain(account_info_iter);
+    for (i, record_account) in records.enumerate() {
+        if record_account.owner != program_id || record_account.data_is_empty() {
+            msg!("No vote recorded in {}", record_account.key);
+            return Err(UnicornFactoryError::VoteNotFound.into());
+        }
+
+        let mut record = {
+            let record_data = record_account.data.borrow();
+            VoteRecord::unpack(&record_data)?
+        };
+        verify_vote_record(program_id, proposal_account, &record.voter, record_account)?;
+
+        // The first record is the voter's own; the rest must have been cast by them
+        if (i == 0 && record.voter != *voter_account.key) || record.cast_by != *voter_account.key
+        {
+            msg!(
+                "Vote of {} was cast by {}, not the signer",
+                record.voter,
+                record.cast_by
+            );
+            return Err(UnicornFactoryError::VoteNotFound.into());
+        }
+
+        remove_vote_weight(&mut proposal, record.choice, record.weight);
+        match new_choice {
+            Some(choice) => {
+                add_vote_weight(&mut proposal, choice, record.weight)?;
+                record.choice = choice;
+                let mut record_data = record_account.data.borrow_mut();
+                record.pack(&mut record_data);
+            }
+            None => close_record_account(record_account, voter_account)?,
+        }
+
+        revised_weight = revised_weight
+            .checked_add(record.weight)
+            .ok_or(UnicornFactoryError::Overflow)?;
+    }
+
+    let mut proposal_data = proposal_account.try_borrow_mut_data()?;
+    proposal.pack(&mut proposal_data);
+    drop(proposal_data);
+
+    match new_choice {
+        Some(choice) => msg!(
+            "Moved {} vote weight to choice {} on proposal {}",
+            revised_weight,
+            choice,
+            proposal_id
+        ),
+        None => msg!(
+            "Withdrew {} vote weight from proposal {}",
+            revised_weight,
+            proposal_id
+        ),
+    }
+    Ok(())
+}
+
+// Create choice proposal instruction processor
+//
+// Multi-choice proposals are not tied to a milestone. Holders vote with an
+// option index and Execute records the leading option once voting closes.
+fn process_create_choice_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    description: String,
+    options: Vec<String>,
+) -> ProgramResult {
+    msg!("Starting choice proposal creation");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let proposal_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Proposal Account key: {}",
+        proposal_account.key
+    );
+
+    let authority_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Proposer Account key: {}",
+        authority_account.key
+    );
+
+    let system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: System Program key: {}",
+        system_program.key
+    );
+
+    let project_token = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Project Token key: {}",
+        project_token.key
+    );
+
+    // Verify system program
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
 
-// Helper functions for bonding curve calculations
-fn calculate_tokens(amount: u64, current_price: u64) -> u64 {
-    amount.checked_div(current_price).unwrap_or(0)
+    if options.len() < 2
+        || options.len() > MAX_PROPOSAL_OPTIONS
+        || options
+            .iter()
+            .any(|label| label.is_empty() || label.len() > 32)
+    {
+        msg!(
+            "Choice proposals need 2 to {} options labeled in at most 32 bytes",
+            MAX_PROPOSAL_OPTIONS
+        );
+        return Err(UnicornFactoryError::InvalidProposalType.into());
+    }
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    // The authority proposes freely; holders above the threshold post a deposit
+    let clock = Clock::get()?;
+    let voting_end = clock.unix_timestamp + 180;
+    let tally_end = project.governance.tally_end(voting_end);
+    let (payer_account, deposit) = authorize_proposer(
+        program_id,
+        project_account,
+        &mut project,
+        authority_account,
+        accounts,
+        account_info_iter,
+        tally_end,
+    )?;
+
+    let option_count = options.len();
+    let proposal = Proposal {
+        creator: *authority_account.key,
+        title,
+        description,
+        milestone_id: 0,
+        yes_votes: 0,
+        no_votes: 0,
+        is_executed: false,
+        created_at: clock.unix_timestamp,
+        voting_end,
+        is_cancelled: false,
+        veto_votes: 0,
+        abstain_votes: 0,
+        quorum_votes: quorum_votes(&project, project_token)?,
+        option_labels: options,
+        option_votes: vec![0; option_count],
+        winning_option: 0,
+        reveal_end: project.governance.reveal_end(voting_end),
+        unrevealed_votes: 0,
+        action: ProposalAction::None,
+        deposit,
+        evidence_version: 0,
+        evidence_hash: [0u8; 32],
+    };
+
+    create_proposal_account(
+        program_id,
+        project_account,
+        &mut project,
+        proposal_account,
+        payer_account,
+        system_program,
+        &proposal,
+    )?;
+
+    // Pack updated project data
+    let mut project_data = project_account.data.borrow_mut();
+    project.pack(&mut project_data);
+    drop(project_data);
+
+    msg!(
+        "Choice proposal {} created with {} options",
+        project.proposal_count - 1,
+        option_count
+    );
+    Ok(())
 }
 
-fn calculate_new_price(total_raised: u64, funding_goal: u64) -> u64 {
-    let price_increase = total_raised
-        .checked_mul(100)
-        .unwrap_or(0)
-        .checked_div(funding_goal)
-        .unwrap_or(0);
-    1 + price_increase
+// Execute instruction processor
+//
+// Anyone can execute a proposal once its timelock has passed. Multi-choice
+// proposals record the leading option; action proposals carry out their action
+// with the accounts listed on `execute_action`.
+fn process_execute(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proposal_id: u64,
+) -> ProgramResult {
+    msg!("Starting proposal execution");
+    let account_info_iter = &mut accounts.iter();
+
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let proposal_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Proposal Account key: {}",
+        proposal_account.key
+    );
+
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
+
+    let mut proposal = load_proposal(program_id, project_account, proposal_account, proposal_id)?;
+
+    if !proposal.is_multi_choice() && proposal.action == ProposalAction::None {
+        msg!("Yes/no proposal {} is executed by releasing funds", proposal_id);
+        return Err(UnicornFactoryError::InvalidProposalType.into());
+    }
+
+    if proposal.is_executed {
+        msg!("Proposal {} is already executed", proposal_id);
+        return Err(UnicornFactoryError::ProposalAlreadyExecuted.into());
+    }
+
+    if proposal.is_cancelled {
+        msg!("Proposal {} was cancelled", proposal_id);
+        return Err(UnicornFactoryError::ProposalCancelled.into());
+    }
+
+    let clock = Clock::get()?;
+    if clock.unix_timestamp <= proposal.tally_end() {
+        msg!(
+            "Voting period for proposal {} has not ended yet",
+            proposal_id
+        );
+        return Err(UnicornFactoryError::VotingPeriodNotEnded.into());
+    }
+
+    if !proposal.has_quorum() {
+        msg!(
+            "Proposal {} did not reach quorum of {}",
+            proposal_id,
+            proposal.quorum_votes
+        );
+        return Err(UnicornFactoryError::QuorumNotReached.into());
+    }
+
+    let winning_option = if proposal.is_multi_choice() {
+        let Some(winning_option) = proposal.leading_option() else {
+            msg!("Proposal {} has no single leading option", proposal_id);
+            return Err(UnicornFactoryError::ProposalDidNotPass.into());
+        };
+        winning_option
+    } else {
+        if !proposal.passed() {
+            msg!("Proposal {} did not win the vote", proposal_id);
+            return Err(UnicornFactoryError::ProposalDidNotPass.into());
+        }
+        VOTE_YES
+    };
+
+    // Enforce the timelock and the execution window
+    let executable_at = proposal.executable_at(&project.governance);
+    if clock.unix_timestamp <= executable_at {
+        msg!(
+            "Proposal {} is timelocked until {}",
+            proposal_id,
+            executable_at
+        );
+        return Err(UnicornFactoryError::TimelockActive.into());
+    }
+
+    if proposal.is_expired(&project.governance, clock.unix_timestamp) {
+        msg!("Proposal {} expired before execution", proposal_id);
+        return Err(UnicornFactoryError::ProposalExpired.into());
+    }
+
+    if proposal.is_multi_choice() {
+        msg!(
+            "Proposal {} winning option {}: {}",
+            proposal_id,
+            winning_option,
+            proposal.option_labels[winning_option as usize]
+        );
+    } else {
+        execute_action(
+            program_id,
+            project_account,
+            &mut project,
+            &proposal,
+            account_info_iter,
+        )?;
+
+        let mut project_data = project_account.data.borrow_mut();
+        project.pack(&mut project_data);
+        drop(project_data);
+    }
+
+    proposal.is_executed = true;
+    proposal.winning_option = winning_option;
+
+    let mut proposal_data = proposal_account.try_borrow_mut_data()?;
+    proposal.pack(&mut proposal_data);
+    drop(proposal_data);
+
+    msg!("Proposal {} executed", proposal_id);
+    Ok(())
 }
 
-// Main program logic
-pub fn process_instruction(
+// Reveal vote instruction processor
+//
+// Opens a committed ballot during the reveal phase and moves its weight from
+// `unrevealed_votes` to the revealed choice. Accounts after the voter's vote
+// record are vote records of delegators the voter committed for; they take the
+// same choice.
+fn process_reveal_vote(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    instruction_data: &[u8],
+    proposal_id: u64,
+    choice: u8,
+    salt: [u8; 32],
 ) -> ProgramResult {
-    let instruction = UnicornFactoryInstruction::unpack(instruction_data)?;
+    msg!("Starting vote reveal");
+    let account_info_iter = &mut accounts.iter();
 
-    match instruction {
-        UnicornFactoryInstruction::InitializeProject {
-            name,
-            symbol,
-            funding_goal,
-        } => {
-            msg!("Instruction: Initialize Project");
-            process_initialize_project(program_id, accounts, name, symbol, funding_goal)
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
+
+    let proposal_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Proposal Account key: {}",
+        proposal_account.key
+    );
+
+    let voter_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Voter Account key: {}",
+        voter_account.key
+    );
+
+    let vote_record_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Vote Record key: {}",
+        vote_record_account.key
+    );
+
+    // Verify voter is signer
+    if !voter_account.is_signer {
+        msg!("Voter is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut proposal = load_proposal(program_id, project_account, proposal_account, proposal_id)?;
+
+    if proposal.is_executed || proposal.is_cancelled {
+        msg!("Proposal {} is no longer pending", proposal_id);
+        return Err(UnicornFactoryError::RevealPhaseClosed.into());
+    }
+
+    let clock = Clock::get()?;
+    if !proposal.is_secret()
+        || clock.unix_timestamp <= proposal.voting_end
+        || clock.unix_timestamp > proposal.reveal_end
+    {
+        msg!("Proposal {} is not in its reveal phase", proposal_id);
+        return Err(UnicornFactoryError::RevealPhaseClosed.into());
+    }
+
+    if choice == VOTE_HIDDEN || proposal.tally_mut(choice).is_none() {
+        msg!("Proposal {} has no choice {}", proposal_id, choice);
+        return Err(UnicornFactoryError::InvalidVoteChoice.into());
+    }
+
+    let commitment = ballot_commitment(choice, &salt, voter_account.key);
+
+    let mut revealed_weight: u64 = 0;
+    let records = std::iter::once(vote_record_account).chain(account_info_iter);
+    for (i, record_account) in records.enumerate() {
+        if record_account.owner != program_id || record_account.data_is_empty() {
+            msg!("No vote recorded in {}", record_account.key);
+            return Err(UnicornFactoryError::VoteNotFound.into());
         }
-        UnicornFactoryInstruction::Contribute { amount } => {
-            msg!("Instruction: Contribute");
-            process_contribute(accounts, amount)
+
+        let mut record = {
+            let record_data = record_account.data.borrow();
+            VoteRecord::unpack(&record_data)?
+        };
+        verify_vote_record(program_id, proposal_account, &record.voter, record_account)?;
+
+        // The first record is the voter's own; the rest must have been cast by them
+        if (i == 0 && record.voter != *voter_account.key) || record.cast_by != *voter_account.key
+        {
+            msg!(
+                "Vote of {} was cast by {}, not the signer",
+                record.voter,
+                record.cast_by
+            );
+            return Err(UnicornFactoryError::VoteNotFound.into());
         }
-        UnicornFactoryInstruction::BuyTokens { amount } => {
-            msg!("Instruction: Buy Tokens");
-            process_buy_tokens(accounts, amount)
+
+        if record.choice != VOTE_HIDDEN {
+            msg!("Vote of {} is already revealed", record.voter);
+            return Err(UnicornFactoryError::AlreadyVoted.into());
         }
-        UnicornFactoryInstruction::SellTokens { amount } => {
-            msg!("Instruction: Sell Tokens");
-            process_sell_tokens(accounts, amount)
+
+        if record.commitment != commitment {
+            msg!("Revealed ballot does not match the commitment of {}", record.voter);
+            return Err(UnicornFactoryError::BallotMismatch.into());
         }
-        UnicornFactoryInstruction::CreateProposal {
-            title,
-            description,
-            milestone_id,
-        } => {
-            msg!("Instruction: Create Proposal");
-            process_create_proposal(
+
+        remove_vote_weight(&mut proposal, VOTE_HIDDEN, record.weight);
+        add_vote_weight(&mut proposal, choice, record.weight)?;
+        record.choice = choice;
+        let mut record_data = record_account.data.borrow_mut();
+        record.pack(&mut record_data);
+        drop(record_data);
+
+        revealed_weight = revealed_weight
+            .checked_add(record.weight)
+            .ok_or(UnicornFactoryError::Overflow)?;
+    }
+
+    let mut proposal_data = proposal_account.try_borrow_mut_data()?;
+    proposal.pack(&mut proposal_data);
+    drop(proposal_data);
+
+    msg!(
+        "Revealed {} vote weight for choice {} on proposal {}, {} still unrevealed",
+        revealed_weight,
+        choice,
+        proposal_id,
+        proposal.unrevealed_votes
+    );
+    Ok(())
+}
+
+// Carries out the action of a passed proposal. Accounts are the ones after the
+// proposal account in Execute:
+//   AddMilestone: milestone, payer (signer), system program
+//   CancelMilestone: milestone
+//   TransferTreasury: vault, recipient (token account for SPL quote mints)
+//   Dissolve: vault, project token mint
+fn execute_action<'a, 'b>(
+    program_id: &Pubkey,
+    project_account: &'a AccountInfo<'b>,
+    project: &mut Project,
+    proposal: &Proposal,
+    account_info_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+) -> ProgramResult {
+    if project.is_dissolved {
+        msg!("Project is dissolved");
+        return Err(UnicornFactoryError::ProjectDissolved.into());
+    }
+
+    match proposal.action {
+        ProposalAction::None => {
+            msg!("Milestone proposals are executed by releasing funds");
+            Err(UnicornFactoryError::InvalidProposalType.into())
+        }
+        ProposalAction::SetCurveParams { base_price, slope } => {
+            if base_price == 0 {
+                msg!("Curve base price must be greater than zero");
+                return Err(UnicornFactoryError::InvalidAmount.into());
+            }
+            project.curve_base_price = base_price;
+            project.curve_slope = slope;
+            project.token_price = calculate_new_price(project);
+            msg!(
+                "Curve set to base {} slope {}, price now {}",
+                base_price,
+                slope,
+                project.token_price
+            );
+            Ok(())
+        }
+        ProposalAction::SetFundingGoal { funding_goal } => {
+            let allocatable = funding_goal.saturating_sub(project.graduation_amount(funding_goal));
+            if funding_goal == 0 || project.total_allocated > allocatable {
+                msg!(
+                    "Funding goal {} cannot cover {} allocated to milestones",
+                    funding_goal,
+                    project.total_allocated
+                );
+                return Err(UnicornFactoryError::MilestoneAllocationExceedsGoal.into());
+            }
+            project.funding_goal = funding_goal;
+            project.token_price = calculate_new_price(project);
+            msg!("Funding goal set to {}", funding_goal);
+            Ok(())
+        }
+        ProposalAction::SetAuthority { authority } => {
+            project.authority = authority;
+            project.pending_authority = Pubkey::default();
+            msg!("Project authority replaced by {}", authority);
+            Ok(())
+        }
+        ProposalAction::AddMilestone { amount, deadline } => {
+            let milestone_account = next_account_info(account_info_iter)?;
+            let payer_account = next_account_info(account_info_iter)?;
+            let system_program = next_account_info(account_info_iter)?;
+
+            if !payer_account.is_signer {
+                msg!("Milestone payer is not a signer");
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            if system_program.key != &system_program::id() {
+                msg!("Invalid system program");
+                return Err(ProgramError::IncorrectProgramId);
+            }
+
+            // Governance-added milestones pay out in a single tranche
+            let milestone = Milestone::new(
+                proposal.title.clone(),
+                proposal.description.clone(),
+                amount,
+                deadline,
+                1,
+                0,
+            );
+            create_milestone_account(
                 program_id,
-                accounts,
-                title,
-                description,
-                milestone_id,
-            )
+                project_account,
+                project,
+                milestone_account,
+                payer_account,
+                system_program,
+                &milestone,
+            )?;
+            msg!("Milestone {} added", project.milestone_count - 1);
+            Ok(())
         }
-        UnicornFactoryInstruction::Vote { proposal_id, vote } => {
-            msg!("Instruction: Vote");
-            process_vote(program_id, accounts, proposal_id, vote)
+        ProposalAction::CancelMilestone { milestone_id } => {
+            let milestone_account = next_account_info(account_info_iter)?;
+            verify_milestone(program_id, project_account, milestone_account, milestone_id)?;
+
+            let mut milestone_data = milestone_account.data.borrow_mut();
+            let mut milestone = Milestone::unpack(&milestone_data)?;
+            if matches!(
+                milestone.status,
+                MilestoneStatus::Approved | MilestoneStatus::Released | MilestoneStatus::Cancelled
+            ) {
+                msg!("Milestone {} is already closed", milestone_id);
+                return Err(milestone.status.error().into());
+            }
+
+            // Free the allocation for future milestones
+            milestone.status = MilestoneStatus::Cancelled;
+            milestone.pack(&mut milestone_data);
+            project.total_allocated = project.total_allocated.saturating_sub(milestone.amount);
+            msg!("Milestone {} cancelled", milestone_id);
+            Ok(())
         }
-        UnicornFactoryInstruction::ReleaseFunds { proposal_id } => {
-            msg!("Instruction: Release Funds");
-            process_release_funds(program_id, accounts, proposal_id)
+        ProposalAction::TransferTreasury { recipient, amount } => {
+            let vault_account = next_account_info(account_info_iter)?;
+            let recipient_account = next_account_info(account_info_iter)?;
+
+            verify_vault(program_id, project_account, project, vault_account)?;
+            if *recipient_account.key != recipient {
+                msg!(
+                    "Invalid recipient. Expected: {}, Got: {}",
+                    recipient,
+                    recipient_account.key
+                );
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let releasable = releasable_amount(project, vault_balance(project, vault_account)?);
+            if amount > releasable {
+                msg!(
+                    "Insufficient treasury for transfer. Releasable: {}, Requested: {}",
+                    releasable,
+                    amount
+                );
+                return Err(UnicornFactoryError::InsufficientTreasury.into());
+            }
+
+            withdraw_from_vault(
+                project_account,
+                project,
+                vault_account,
+                recipient_account,
+                amount,
+            )?;
+            project.total_released = project
+                .total_released
+                .checked_add(amount)
+                .ok_or(UnicornFactoryError::Overflow)?;
+            msg!("Transferred {} from the treasury to {}", amount, recipient);
+            Ok(())
         }
-        UnicornFactoryInstruction::AddMilestone {
-            title,
-            description,
-            amount,
-        } => {
-            msg!("Instruction: Add Milestone");
-            process_add_milestone(program_id, accounts, title, description, amount)
+        ProposalAction::SetPaused { paused } => {
+            project.is_paused = paused;
+            msg!("Project {}", if paused { "paused" } else { "unpaused" });
+            Ok(())
         }
-        UnicornFactoryInstruction::CompleteMilestone { milestone_id } => {
-            msg!("Instruction: Complete Milestone");
-            process_complete_milestone(program_id, accounts, milestone_id)
+        ProposalAction::Dissolve => {
+            let vault_account = next_account_info(account_info_iter)?;
+            let project_token = next_account_info(account_info_iter)?;
+
+            verify_vault(program_id, project_account, project, vault_account)?;
+            if project.token_mint != *project_token.key {
+                msg!(
+                    "Project token mint mismatch. Project mint: {}, Provided mint: {}",
+                    project.token_mint,
+                    project_token.key
+                );
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // Until the raise succeeds its buyers are owed refunds, not a
+            // share of the treasury
+            if project.raise_state != RaiseState::Succeeded {
+                msg!("Only a project whose raise succeeded can be dissolved");
+                return Err(UnicornFactoryError::RaiseNotSucceeded.into());
+            }
+
+            // Close the curve and hand the treasury back to holders at the
+            // rate it stands at now; no tokens are minted after this. Tokens
+            // bought and not yet delivered count towards the supply and are
+            // paid out at the same rate when claimed, while refunds owed to
+            // bidders stay out of the treasury.
+            let (owed_tokens, owed_refunds) = project.undelivered_entitlements();
+            let mint_data = project_token.try_borrow_data()?;
+            project.dissolved_supply = spl_token::state::Mint::unpack(&mint_data)?
+                .supply
+                .checked_add(owed_tokens)
+                .ok_or(UnicornFactoryError::Overflow)?;
+            drop(mint_data);
+            project.dissolved_treasury = vault_balance(project, vault_account)?
+                .saturating_sub(project.held_deposits)
+                .saturating_sub(owed_refunds);
+            project.is_dissolved = true;
+            project.is_active = false;
+            msg!(
+                "Project dissolved, {} in the treasury open for redemption by {} tokens",
+                project.dissolved_treasury,
+                project.dissolved_supply
+            );
+            Ok(())
         }
     }
 }
 
-// Initialize project instruction processor
-fn process_initialize_project(
+// Create action proposal instruction processor
+fn process_create_action_proposal(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    name: String,
-    symbol: String,
-    funding_goal: u64,
+    title: String,
+    description: String,
+    action: ProposalAction,
 ) -> ProgramResult {
-    msg!("Starting project initialization");
+    msg!("Starting action proposal creation");
     let account_info_iter = &mut accounts.iter();
 
     let project_account = next_account_info(account_info_iter)?;
@@ -701,589 +10555,431 @@ fn process_initialize_project(
         project_account.key
     );
 
+    let proposal_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Proposal Account key: {}",
+        proposal_account.key
+    );
+
     let authority_account = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 1: Authority Account key: {}",
+        "Processing account 2: Proposer Account key: {}",
         authority_account.key
     );
 
     let system_program = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 2: System Program key: {}",
+        "Processing account 3: System Program key: {}",
         system_program.key
     );
 
-    let token_program = next_account_info(account_info_iter)?;
-    msg!(
-        "Processing account 3: Token Program key: {}",
-        token_program.key
-    );
-
-    let token_mint_account = next_account_info(account_info_iter)?;
+    let project_token = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 4: Token Mint Account key: {}",
-        token_mint_account.key
+        "Processing account 4: Project Token key: {}",
+        project_token.key
     );
 
-    // Verify authority is signer
-    if !authority_account.is_signer {
-        msg!("Authority is not a signer");
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-
     // Verify system program
     if system_program.key != &system_program::id() {
         msg!("Invalid system program");
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    // Verify token program
-    if token_program.key != &spl_token::id() {
-        msg!("Invalid token program");
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    // Find PDA bump
-    let (pda, bump) =
-        Pubkey::find_program_address(&[b"project", authority_account.key.as_ref()], program_id);
-
-    msg!("Generated PDA: {}", pda);
-    msg!("Bump: {}", bump);
-
-    // Verify PDA matches
-    if pda != *project_account.key {
-        msg!(
-            "Invalid project account. Expected: {}, Got: {}",
-            pda,
-            project_account.key
-        );
-        return Err(UnicornFactoryError::InvalidProjectAccount.into());
-    }
-
-    // Verify project account is not already initialized
-    if project_account.data.borrow().iter().any(|&x| x != 0) {
-        msg!("Project account already initialized");
-        return Err(UnicornFactoryError::InvalidProjectAccount.into());
-    }
-
-    // Create project account
-    let project = Project {
-        authority: *authority_account.key,
-        name: name.clone(),
-        symbol: symbol.clone(),
-        funding_goal,
-        total_raised: 0,
-        token_price: 1,
-        is_active: true,
-        bump,
-        token_mint: *token_mint_account.key,
-        milestone_count: 0,
-        proposal_count: 0,
-    };
-
-    msg!(
-        "Project data prepared: name={}, symbol={}, funding_goal={}, token_mint={}",
-        name,
-        symbol,
-        funding_goal,
-        project.token_mint
-    );
-
-    // Calculate account size and rent
-    let rent = Rent::get()?;
-    let space = Project::LEN;
-    let lamports = rent.minimum_balance(space);
-
-    msg!("Account space: {}, Lamports: {}", space, lamports);
-
-    // Create account with PDA as signer
-    let seeds = &[b"project".as_ref(), authority_account.key.as_ref(), &[bump]];
-
-    msg!("Creating project account with seeds: {:?}", seeds);
-
-    // Create the account using invoke_signed
-    invoke_signed(
-        &system_instruction::create_account(
-            authority_account.key,
-            project_account.key,
-            lamports,
-            space as u64,
-            program_id,
-        ),
-        &[
-            authority_account.clone(),
-            project_account.clone(),
-            system_program.clone(),
-        ],
-        &[seeds],
-    )?;
-
-    msg!("Account created successfully");
-
-    // Pack project data
-    let mut project_data = vec![0; Project::LEN];
-    project.pack(&mut project_data);
-    project_account
-        .data
-        .borrow_mut()
-        .copy_from_slice(&project_data);
-
-    msg!("Project initialized successfully");
-    Ok(())
-}
-
-// Contribute instruction processor
-fn process_contribute(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
-    let project_account = next_account_info(account_info_iter)?;
-    let contributor_account = next_account_info(account_info_iter)?;
-    let contributor_token_account = next_account_info(account_info_iter)?;
-    let project_token = next_account_info(account_info_iter)?;
-    let token_program = next_account_info(account_info_iter)?;
-    let _system_program = next_account_info(account_info_iter)?;
-
-    // Verify contributor is signer
-    if !contributor_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+    if action == ProposalAction::None {
+        msg!("Action proposals need an action");
+        return Err(UnicornFactoryError::InvalidProposalType.into());
     }
 
     // Load and verify project
-    let project_data = project_account.data.borrow();
-    let mut project = Project::unpack(&project_data)?;
-
-    drop(project_data);
-
-    if !project.is_active {
-        return Err(UnicornFactoryError::ProjectNotActive.into());
-    }
-
-    if project.total_raised >= project.funding_goal {
-        return Err(UnicornFactoryError::FundingGoalReached.into());
-    }
-
-    // Calculate tokens to mint
-    let tokens_to_mint = calculate_tokens(amount, project.token_price);
+    let mut project = load_project(program_id, project_account)?;
 
-    // Transfer SOL from contributor to project
-    invoke(
-        &system_instruction::transfer(contributor_account.key, project_account.key, amount),
-        &[contributor_account.clone(), project_account.clone()],
+    // The authority proposes freely; holders above the threshold post a deposit
+    let clock = Clock::get()?;
+    let voting_end = clock.unix_timestamp + 180;
+    let tally_end = project.governance.tally_end(voting_end);
+    let (payer_account, deposit) = authorize_proposer(
+        program_id,
+        project_account,
+        &mut project,
+        authority_account,
+        accounts,
+        account_info_iter,
+        tally_end,
     )?;
 
-    // Mint tokens to contributor
-    let seeds = &[
-        b"project".as_ref(),
-        project.authority.as_ref(),
-        &[project.bump],
-    ];
+    let proposal = Proposal {
+        creator: *authority_account.key,
+        title,
+        description,
+        milestone_id: 0,
+        yes_votes: 0,
+        no_votes: 0,
+        is_executed: false,
+        created_at: clock.unix_timestamp,
+        voting_end,
+        is_cancelled: false,
+        veto_votes: 0,
+        abstain_votes: 0,
+        quorum_votes: quorum_votes(&project, project_token)?,
+        option_labels: Vec::new(),
+        option_votes: Vec::new(),
+        winning_option: 0,
+        reveal_end: project.governance.reveal_end(voting_end),
+        unrevealed_votes: 0,
+        action,
+        deposit,
+        evidence_version: 0,
+        evidence_hash: [0u8; 32],
+    };
 
-    invoke_signed(
-        &token_instruction::mint_to(
-            token_program.key,
-            project_token.key,
-            contributor_token_account.key,
-            project_account.key,
-            &[],
-            tokens_to_mint,
-        )?,
-        &[
-            project_token.clone(),
-            contributor_token_account.clone(),
-            project_account.clone(),
-        ],
-        &[seeds],
+    create_proposal_account(
+        program_id,
+        project_account,
+        &mut project,
+        proposal_account,
+        payer_account,
+        system_program,
+        &proposal,
     )?;
 
-    // Update project state
-    project.total_raised = project
-        .total_raised
-        .checked_add(amount)
-        .ok_or(UnicornFactoryError::Overflow)?;
-    project.token_price = calculate_new_price(project.total_raised, project.funding_goal);
-
-    // Check if funding goal is reached
-    if project.total_raised >= project.funding_goal {
-        project.is_active = false;
-    }
-
     // Pack updated project data
-    let mut project_data = vec![0; Project::LEN];
+    let mut project_data = project_account.data.borrow_mut();
     project.pack(&mut project_data);
-    project_account
-        .data
-        .borrow_mut()
-        .copy_from_slice(&project_data);
+    drop(project_data);
 
+    msg!(
+        "Action proposal {} created: {:?}",
+        project.proposal_count - 1,
+        action
+    );
     Ok(())
 }
 
-// Buy tokens instruction processor
-fn process_buy_tokens(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+// Settle deposit instruction processor
+//
+// Once a holder proposal can no longer be vetoed or cancelled its deposit is
+// refunded to the proposer if the proposal reached quorum and still stands, and
+// otherwise slashed to the project treasury, where it can be spent like raised
+// funds.
+fn process_settle_deposit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proposal_id: u64,
+) -> ProgramResult {
+    msg!("Starting proposal deposit settlement");
     let account_info_iter = &mut accounts.iter();
+
     let project_account = next_account_info(account_info_iter)?;
-    let buyer_account = next_account_info(account_info_iter)?;
-    let buyer_token_account = next_account_info(account_info_iter)?;
-    let project_token = next_account_info(account_info_iter)?;
-    let token_program = next_account_info(account_info_iter)?;
-    let _system_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
 
-    // Verify buyer is signer
-    if !buyer_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
+    let proposal_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Proposal Account key: {}",
+        proposal_account.key
+    );
 
-    // Load and verify project
-    let project_data = project_account.data.borrow();
-    let mut project = Project::unpack(&project_data)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 2: Vault Account key: {}",
+        vault_account.key
+    );
 
-    drop(project_data);
+    // The proposer for SOL projects, their quote token account for SPL projects
+    let recipient_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Recipient Account key: {}",
+        recipient_account.key
+    );
 
-    if !project.is_active {
-        return Err(UnicornFactoryError::ProjectNotActive.into());
-    }
+    // Load and verify project
+    let mut project = load_project(program_id, project_account)?;
 
-    // Calculate tokens to mint based on current price
-    let tokens_to_mint = calculate_tokens(amount, project.token_price);
+    verify_vault(program_id, project_account, &project, vault_account)?;
 
-    // Transfer SOL from buyer to project
-    invoke(
-        &system_instruction::transfer(buyer_account.key, project_account.key, amount),
-        &[buyer_account.clone(), project_account.clone()],
-    )?;
+    let mut proposal = load_proposal(program_id, project_account, proposal_account, proposal_id)?;
 
-    // Mint tokens to buyer
-    let seeds = &[
-        b"project".as_ref(),
-        project.authority.as_ref(),
-        &[project.bump],
-    ];
+    if proposal.deposit == 0 {
+        msg!("Proposal {} holds no deposit", proposal_id);
+        return Err(UnicornFactoryError::NothingToClaim.into());
+    }
 
-    invoke_signed(
-        &token_instruction::mint_to(
-            token_program.key,
-            project_token.key,
-            buyer_token_account.key,
-            project_account.key,
-            &[],
-            tokens_to_mint,
-        )?,
-        &[
-            project_token.clone(),
-            buyer_token_account.clone(),
-            project_account.clone(),
-        ],
-        &[seeds],
-    )?;
+    let clock = Clock::get()?;
+    if clock.unix_timestamp <= proposal.executable_at(&project.governance) {
+        msg!("Proposal {} can still be vetoed or cancelled", proposal_id);
+        return Err(UnicornFactoryError::VotingPeriodNotEnded.into());
+    }
 
-    // Update project state
-    project.total_raised = project
-        .total_raised
-        .checked_add(amount)
-        .ok_or(UnicornFactoryError::Overflow)?;
-    project.token_price = calculate_new_price(project.total_raised, project.funding_goal);
+    let deposit = proposal.deposit;
+    project.held_deposits = project.held_deposits.saturating_sub(deposit);
 
-    // Check if funding goal is reached
-    if project.total_raised >= project.funding_goal {
-        project.is_active = false;
+    if proposal.has_quorum() && !proposal.is_cancelled {
+        if project.is_native_quote() {
+            if *recipient_account.key != proposal.creator {
+                msg!("Deposit refunds go to the proposer {}", proposal.creator);
+                return Err(ProgramError::InvalidAccountData);
+            }
+        } else {
+            let recipient_data = recipient_account.try_borrow_data()?;
+            let recipient = spl_token::state::Account::unpack(&recipient_data)?;
+            if recipient.owner != proposal.creator || recipient.mint != project.quote_mint {
+                msg!("Refund account is not the proposer's quote token account");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        withdraw_from_vault(
+            project_account,
+            &project,
+            vault_account,
+            recipient_account,
+            deposit,
+        )?;
+        msg!("Refunded deposit of {} to {}", deposit, proposal.creator);
+    } else {
+        project.slashed_deposits = project
+            .slashed_deposits
+            .checked_add(deposit)
+            .ok_or(UnicornFactoryError::Overflow)?;
+        msg!(
+            "Proposal {} missed quorum or was cancelled, deposit of {} slashed to the treasury",
+            proposal_id,
+            deposit
+        );
     }
 
-    // Pack updated project data
-    let mut project_data = vec![0; Project::LEN];
+    proposal.deposit = 0;
+
+    let mut proposal_data = proposal_account.try_borrow_mut_data()?;
+    proposal.pack(&mut proposal_data);
+    drop(proposal_data);
+
+    let mut project_data = project_account.data.borrow_mut();
     project.pack(&mut project_data);
-    project_account
-        .data
-        .borrow_mut()
-        .copy_from_slice(&project_data);
+    drop(project_data);
 
     Ok(())
 }
 
-// Sell tokens instruction processor
-fn process_sell_tokens(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
-    msg!("=== SELL TOKENS START ===");
-    msg!("Amount to sell: {}", amount);
-
-    // Log all received accounts first
-    msg!("Total accounts received: {}", accounts.len());
-    for (i, account) in accounts.iter().enumerate() {
-        msg!("Account {}: key={}, owner={}, lamports={}, data_len={}, executable={}, is_signer={}, is_writable={}", 
-             i, account.key, account.owner, account.lamports(), 
-             account.data_len(), account.executable, account.is_signer, account.is_writable);
-    }
-
+// Redeem treasury instruction processor
+//
+// After a dissolve proposal passes, holders burn project tokens for their
+// pro-rata share of the treasury snapshotted at dissolution, excluding proposal
+// deposits then held in escrow. Every token redeems at the same rate, however
+// the vault or the supply move afterwards.
+fn process_redeem_treasury(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    msg!("Starting treasury redemption");
     let account_info_iter = &mut accounts.iter();
+
     let project_account = next_account_info(account_info_iter)?;
-    msg!("✓ Project account loaded: {}", project_account.key);
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
 
-    let seller_account = next_account_info(account_info_iter)?;
-    msg!("✓ Seller account loaded: {}", seller_account.key);
+    let holder_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 1: Holder Account key: {}",
+        holder_account.key
+    );
 
-    let seller_token_account = next_account_info(account_info_iter)?;
+    let holder_token_account = next_account_info(account_info_iter)?;
     msg!(
-        "✓ Seller token account loaded: {}",
-        seller_token_account.key
+        "Processing account 2: Holder Token Account key: {}",
+        holder_token_account.key
     );
 
     let project_token = next_account_info(account_info_iter)?;
-    msg!("✓ Project token loaded: {}", project_token.key);
+    msg!(
+        "Processing account 3: Project Token key: {}",
+        project_token.key
+    );
 
     let token_program = next_account_info(account_info_iter)?;
-    msg!("✓ Token program loaded: {}", token_program.key);
-
-    let system_program = next_account_info(account_info_iter)?;
-    msg!("✓ System program loaded: {}", system_program.key);
+    msg!(
+        "Processing account 4: Token Program key: {}",
+        token_program.key
+    );
 
-    // Comprehensive account validations
-    msg!("=== VALIDATION PHASE ===");
+    let vault_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Vault Account key: {}",
+        vault_account.key
+    );
 
-    // Verify seller is signer
-    if !seller_account.is_signer {
-        msg!("❌ ERROR: Seller is not a signer");
+    // Verify holder is signer
+    if !holder_account.is_signer {
+        msg!("Holder is not a signer");
         return Err(ProgramError::MissingRequiredSignature);
     }
-    msg!("✓ Seller is signer");
 
-    // Verify token program ID
     if token_program.key != &spl_token::id() {
-        msg!(
-            "❌ ERROR: Invalid token program. Expected: {}, Got: {}",
-            spl_token::id(),
-            token_program.key
-        );
-        return Err(ProgramError::IncorrectProgramId);
-    }
-    msg!("✓ Token program ID correct");
-
-    // Verify system program ID
-    if system_program.key != &system_program::id() {
-        msg!(
-            "❌ ERROR: Invalid system program. Expected: {}, Got: {}",
-            system_program::id(),
-            system_program.key
-        );
+        msg!("Invalid token program");
         return Err(ProgramError::IncorrectProgramId);
     }
-    msg!("✓ System program ID correct");
 
     // Load and verify project
-    msg!("=== PROJECT LOADING ===");
-    let project_data = project_account.data.borrow();
-    let mut project = Project::unpack(&project_data)?;
-    msg!(
-        "✓ Project loaded: name={}, authority={}, total_raised={}, token_price={}, token_mint={}",
-        project.name,
-        project.authority,
-        project.total_raised,
-        project.token_price,
-        project.token_mint
-    );
-
-    drop(project_data);
+    let project = load_project(program_id, project_account)?;
 
-    if !project.is_active {
-        msg!("❌ ERROR: Project is not active");
-        return Err(UnicornFactoryError::ProjectNotActive.into());
+    if !project.is_dissolved {
+        msg!("Treasury redemptions open only after the project is dissolved");
+        return Err(UnicornFactoryError::ProjectNotDissolved.into());
     }
-    msg!("✓ Project is active");
 
-    // Verify project token mint matches
     if project.token_mint != *project_token.key {
         msg!(
-            "❌ ERROR: Project token mint mismatch. Project mint: {}, Provided mint: {}",
+            "Project token mint mismatch. Project mint: {}, Provided mint: {}",
             project.token_mint,
             project_token.key
         );
         return Err(ProgramError::InvalidAccountData);
     }
-    msg!("✓ Project token mint matches");
-
-    // Check seller token account
-    msg!("=== SELLER TOKEN ACCOUNT VALIDATION ===");
-    if seller_token_account.data_is_empty() {
-        msg!("❌ ERROR: Seller token account is empty/doesn't exist");
-        return Err(ProgramError::UninitializedAccount);
-    }
-    msg!("✓ Seller token account exists");
 
-    if seller_token_account.owner != &spl_token::id() {
-        msg!(
-            "❌ ERROR: Seller token account owner mismatch. Expected: {}, Got: {}",
-            spl_token::id(),
-            seller_token_account.owner
-        );
-        return Err(ProgramError::IncorrectProgramId);
-    }
-    msg!("✓ Seller token account owned by token program");
+    verify_vault(program_id, project_account, &project, vault_account)?;
 
-    // Parse seller token account data
-    let seller_token_data = seller_token_account.try_borrow_data()?;
-    if seller_token_data.len() != spl_token::state::Account::LEN {
+    // SPL quote projects pay out to the holder's quote token account
+    let holder_quote_account = if project.is_native_quote() {
+        holder_account
+    } else {
+        let holder_quote_account = next_account_info(account_info_iter)?;
         msg!(
-            "❌ ERROR: Invalid token account data length. Expected: {}, Got: {}",
-            spl_token::state::Account::LEN,
-            seller_token_data.len()
+            "Processing account 6: Holder Quote Account key: {}",
+            holder_quote_account.key
         );
-        return Err(ProgramError::InvalidAccountData);
-    }
-    msg!("✓ Seller token account data length correct");
-
-    let seller_token_info = spl_token::state::Account::unpack(&seller_token_data)?;
-    msg!("✓ Seller token account parsed successfully");
-    msg!("  - Balance: {}", seller_token_info.amount);
-    msg!("  - Mint: {}", seller_token_info.mint);
-    msg!("  - Owner: {}", seller_token_info.owner);
-    msg!("  - Delegate: {:?}", seller_token_info.delegate);
-    msg!("  - State: {:?}", seller_token_info.state);
+        holder_quote_account
+    };
 
-    // Validate token account details
-    if seller_token_info.amount < amount {
-        msg!(
-            "❌ ERROR: Insufficient token balance. Has: {}, Needs: {}",
-            seller_token_info.amount,
-            amount
-        );
+    if amount == 0 {
+        msg!("Redemption amount must be greater than zero");
         return Err(UnicornFactoryError::InvalidAmount.into());
     }
-    msg!("✓ Sufficient token balance");
 
-    if seller_token_info.mint != *project_token.key {
-        msg!(
-            "❌ ERROR: Token account mint mismatch. Expected: {}, Got: {}",
+    let payout = project.redemption_value(amount);
+
+    invoke(
+        &token_instruction::burn(
+            token_program.key,
+            holder_token_account.key,
             project_token.key,
-            seller_token_info.mint
-        );
-        return Err(ProgramError::InvalidAccountData);
-    }
-    msg!("✓ Token account mint correct");
+            holder_account.key,
+            &[],
+            amount,
+        )?,
+        &[
+            holder_token_account.clone(),
+            project_token.clone(),
+            holder_account.clone(),
+        ],
+    )?;
 
-    if seller_token_info.owner != *seller_account.key {
-        msg!(
-            "❌ ERROR: Token account owner mismatch. Expected: {}, Got: {}",
-            seller_account.key,
-            seller_token_info.owner
-        );
-        return Err(ProgramError::InvalidAccountData);
+    if payout > 0 {
+        withdraw_from_vault(
+            project_account,
+            &project,
+            vault_account,
+            holder_quote_account,
+            payout,
+        )?;
     }
-    msg!("✓ Token account owner correct");
-
-    drop(seller_token_data);
-
-    // Calculate SOL to return
-    let sol_to_return = amount
-        .checked_mul(project.token_price)
-        .ok_or(UnicornFactoryError::Overflow)?;
-    msg!("✓ SOL to return calculated: {}", sol_to_return);
 
-    // Check project account balance
-    let project_balance = project_account.lamports();
     msg!(
-        "Project account balance: {}, Need to pay: {}",
-        project_balance,
-        sol_to_return
-    );
-
-    if project_balance < sol_to_return {
-        msg!("❌ ERROR: Project insufficient balance to pay back seller");
-        return Err(UnicornFactoryError::InvalidAmount.into());
-    }
-    msg!("✓ Project has sufficient balance");
-
-    // Prepare burn instruction
-    msg!("=== PREPARING BURN INSTRUCTION ===");
-    let burn_instruction = token_instruction::burn(
-        token_program.key,
-        seller_token_account.key,
-        project_token.key,
-        seller_account.key,
-        &[],
+        "Redeemed {} tokens of {} for {} of {} in the treasury",
         amount,
-    )?;
+        project.dissolved_supply,
+        payout,
+        project.dissolved_treasury
+    );
+    Ok(())
+}
 
-    msg!("Burn instruction created:");
-    msg!("  - Program ID: {}", burn_instruction.program_id);
-    msg!("  - Accounts count: {}", burn_instruction.accounts.len());
-    for (i, acc) in burn_instruction.accounts.iter().enumerate() {
-        msg!(
-            "    Account {}: pubkey={}, is_signer={}, is_writable={}",
-            i,
-            acc.pubkey,
-            acc.is_signer,
-            acc.is_writable
-        );
-    }
-    msg!("  - Data length: {}", burn_instruction.data.len());
+// Check milestone deadline instruction processor
+//
+// Permissionless crank that marks an unfinished milestone as missed once its
+// deadline has passed. The milestone's earlier proposal can no longer release
+// funds; a fresh proposal has to be voted on instead.
+fn process_check_milestone_deadline(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    milestone_id: u8,
+) -> ProgramResult {
+    msg!("Starting milestone deadline check");
+    let account_info_iter = &mut accounts.iter();
 
-    msg!("=== EXECUTING BURN ===");
-    invoke(
-        &burn_instruction,
-        &[
-            seller_token_account.clone(),
-            project_token.clone(),
-            seller_account.clone(),
-        ],
-    )?;
-    msg!("✓ Tokens burned successfully");
+    let project_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 0: Project Account key: {}",
+        project_account.key
+    );
 
-    // Manual lamport transfer (required for accounts with data)
-    msg!("=== EXECUTING MANUAL LAMPORT TRANSFER ===");
+    let milestone_account = next_account_info(account_info_iter)?;
     msg!(
-        "Transferring {} lamports from project to seller",
-        sol_to_return
+        "Processing account 1: Milestone Account key: {}",
+        milestone_account.key
     );
 
-    // Check if project has enough lamports
-    let project_lamports = project_account.lamports();
-    if project_lamports < sol_to_return {
-        msg!("❌ ERROR: Project insufficient balance to pay back seller");
-        return Err(UnicornFactoryError::InvalidAmount.into());
+    if project_account.owner != program_id || milestone_account.owner != program_id {
+        msg!("Project or milestone account is not owned by this program");
+        return Err(ProgramError::IncorrectProgramId);
     }
-    msg!("✓ Project has sufficient balance: {}", project_lamports);
 
-    // Perform manual lamport transfer
-    **project_account.lamports.borrow_mut() -= sol_to_return;
-    **seller_account.lamports.borrow_mut() += sol_to_return;
-    msg!("✓ Transferred {} lamports manually", sol_to_return);
+    verify_milestone(program_id, project_account, milestone_account, milestone_id)?;
 
-    // Update project state
-    msg!("=== UPDATING PROJECT STATE ===");
-    project.total_raised = project
-        .total_raised
-        .checked_sub(sol_to_return)
-        .ok_or(UnicornFactoryError::Overflow)?;
-    project.token_price = calculate_new_price(project.total_raised, project.funding_goal);
-    msg!(
-        "Updated project state: total_raised={}, token_price={}",
-        project.total_raised,
-        project.token_price
-    );
+    let mut milestone_data = milestone_account.data.borrow_mut();
+    let mut milestone = Milestone::unpack(&milestone_data)?;
 
-    if project.total_raised >= project.funding_goal {
-        project.is_active = false;
-        msg!("Project funding goal reached, marking as inactive");
+    // A missed milestone's fresh proposal is not held to the same deadline
+    let clock = Clock::get()?;
+    if milestone.deadline == 0
+        || milestone.missed_at != 0
+        || clock.unix_timestamp <= milestone.deadline
+        || !matches!(
+            milestone.status,
+            MilestoneStatus::Pending | MilestoneStatus::Proposed
+        )
+    {
+        msg!("Milestone {} is not overdue", milestone_id);
+        return Err(UnicornFactoryError::MilestoneNotOverdue.into());
     }
 
-    // Pack updated project data
-    let mut project_data = vec![0; Project::LEN];
-    project.pack(&mut project_data);
-    project_account
-        .data
-        .borrow_mut()
-        .copy_from_slice(&project_data);
+    milestone.status = MilestoneStatus::Missed;
+    milestone.missed_at = clock.unix_timestamp;
+    milestone.pack(&mut milestone_data);
+    drop(milestone_data);
 
-    msg!("✓ Project data updated");
-    msg!("=== SELL TOKENS SUCCESSFUL ===");
+    emit_milestone_event(
+        b"MilestoneMissed",
+        project_account.key,
+        milestone_id,
+        milestone.deadline,
+    );
+
+    msg!(
+        "Milestone {} missed its deadline {}",
+        milestone_id,
+        milestone.deadline
+    );
     Ok(())
 }
 
-// Create proposal instruction processor
-fn process_create_proposal(
+// Submit milestone evidence instruction processor
+//
+// Records what the team delivered for a milestone as a new evidence version.
+// Earlier versions stay on chain as the history of amendments, and proposals
+// snapshot the version that was current when they were created.
+fn process_submit_milestone_evidence(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    title: String,
-    description: String,
     milestone_id: u8,
+    content_hash: [u8; 32],
+    uri: String,
 ) -> ProgramResult {
-    msg!("Starting proposal creation");
+    msg!("Starting milestone evidence submission");
     let account_info_iter = &mut accounts.iter();
 
     let project_account = next_account_info(account_info_iter)?;
@@ -1292,22 +10988,22 @@ fn process_create_proposal(
         project_account.key
     );
 
-    let proposal_account = next_account_info(account_info_iter)?;
+    let milestone_account = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 1: Proposal Account key: {}",
-        proposal_account.key
+        "Processing account 1: Milestone Account key: {}",
+        milestone_account.key
     );
 
-    let authority_account = next_account_info(account_info_iter)?;
+    let evidence_account = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 2: Authority Account key: {}",
-        authority_account.key
+        "Processing account 2: Evidence Account key: {}",
+        evidence_account.key
     );
 
-    let milestone_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 3: Milestone Account key: {}",
-        milestone_account.key
+        "Processing account 3: Authority Account key: {}",
+        authority_account.key
     );
 
     let system_program = next_account_info(account_info_iter)?;
@@ -1316,251 +11012,134 @@ fn process_create_proposal(
         system_program.key
     );
 
-    // Verify system program
     if system_program.key != &system_program::id() {
         msg!("Invalid system program");
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    // Load and verify project
-    let mut project_data = project_account.data.borrow_mut();
-    let mut project = Project::unpack(&project_data)?;
-
-    // Verify authority is project authority and is signer
-    if !authority_account.is_signer || authority_account.key != &project.authority {
-        msg!("Invalid authority or authority is not signer");
-        return Err(UnicornFactoryError::InvalidAuthority.into());
+    if milestone_account.owner != program_id {
+        msg!("Milestone account is not owned by this program");
+        return Err(ProgramError::IncorrectProgramId);
     }
 
-    // Load and verify milestone
+    let project = load_project(program_id, project_account)?;
+
+    // Verify authority: the authority key itself or a quorum of its council
+    let payer_account =
+        authorize(program_id, project_account, &project, authority_account, accounts)?;
+
+    verify_milestone(program_id, project_account, milestone_account, milestone_id)?;
+
     let mut milestone_data = milestone_account.data.borrow_mut();
     let mut milestone = Milestone::unpack(&milestone_data)?;
 
-    // Verify milestone PDA
-    let (expected_milestone_pda, _milestone_bump) = Pubkey::find_program_address(
-        &[b"milestone", project_account.key.as_ref(), &[milestone_id]],
-        program_id,
-    );
-
-    if expected_milestone_pda != *milestone_account.key {
-        msg!(
-            "Invalid milestone account PDA. Expected: {}, Got: {}",
-            expected_milestone_pda,
-            milestone_account.key
-        );
-        return Err(ProgramError::IncorrectProgramId);
+    if matches!(
+        milestone.status,
+        MilestoneStatus::Released | MilestoneStatus::Cancelled
+    ) {
+        msg!("Milestone {} is already closed", milestone_id);
+        return Err(milestone.status.error().into());
     }
 
-    // Check if milestone already has a proposal
-    if milestone.has_proposal {
-        msg!("Milestone {} already has a proposal", milestone_id);
-        return Err(UnicornFactoryError::MilestoneAlreadyHasProposal.into());
+    if !is_evidence_uri(&uri) || content_hash == [0u8; 32] {
+        msg!("Evidence needs an ipfs://, ar:// or https:// URI and a content hash");
+        return Err(UnicornFactoryError::InvalidEvidence.into());
     }
 
-    // Determine the index for the new proposal
-    let proposal_index = project.proposal_count;
-    msg!("New proposal index: {}", proposal_index);
+    let version = milestone
+        .evidence_version
+        .checked_add(1)
+        .ok_or(UnicornFactoryError::Overflow)?;
 
-    // Find Proposal PDA and bump
-    let (expected_proposal_pda, proposal_bump) = Pubkey::find_program_address(
-        &[b"proposal", project_account.key.as_ref(), &[proposal_index]],
+    // Verify evidence PDA for the next version
+    let (expected_evidence_pda, evidence_bump) = Pubkey::find_program_address(
+        &[
+            b"evidence",
+            project_account.key.as_ref(),
+            &[milestone_id],
+            &[version],
+        ],
         program_id,
     );
 
-    // Verify the provided proposal account is the expected PDA
-    if expected_proposal_pda != *proposal_account.key {
+    if expected_evidence_pda != *evidence_account.key {
         msg!(
-            "Invalid proposal account PDA. Expected: {}, Got: {}",
-            expected_proposal_pda,
-            proposal_account.key
+            "Invalid evidence account PDA. Expected: {}, Got: {}",
+            expected_evidence_pda,
+            evidence_account.key
         );
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    // Verify proposal account is not already initialized
-    if proposal_account.data.borrow().iter().any(|&x| x != 0) {
-        msg!("Proposal account already initialized");
-        return Err(ProgramError::AccountAlreadyInitialized);
-    }
-
-    // Calculate account size and rent
     let rent = Rent::get()?;
-    let space = Proposal::LEN;
+    let space = MilestoneEvidence::LEN;
     let lamports = rent.minimum_balance(space);
 
-    msg!("Proposal account space: {}, Lamports: {}", space, lamports);
-
-    // Create proposal account using invoke_signed
-    let proposal_seeds = &[
-        b"proposal".as_ref(),
+    let evidence_seeds = &[
+        b"evidence".as_ref(),
         project_account.key.as_ref(),
-        &[proposal_index],
-        &[proposal_bump],
+        &[milestone_id],
+        &[version],
+        &[evidence_bump],
     ];
 
     invoke_signed(
         &system_instruction::create_account(
-            authority_account.key,
-            proposal_account.key,
+            payer_account.key,
+            evidence_account.key,
             lamports,
             space as u64,
             program_id,
         ),
         &[
-            authority_account.clone(),
-            proposal_account.clone(),
+            payer_account.clone(),
+            evidence_account.clone(),
             system_program.clone(),
         ],
-        &[proposal_seeds],
+        &[evidence_seeds],
     )?;
 
-    msg!("Proposal account created successfully");
-
-    // Create proposal data
     let clock = Clock::get()?;
-    let proposal = Proposal {
-        creator: *authority_account.key,
-        title: title.clone(),
-        description: description.clone(),
+    let evidence = MilestoneEvidence {
+        project: *project_account.key,
         milestone_id,
-        yes_votes: 0,
-        no_votes: 0,
-        is_executed: false,
-        created_at: clock.unix_timestamp,
-        voting_end: clock.unix_timestamp + 180, // 24 hours from now
+        version,
+        uri,
+        content_hash,
+        submitted_by: *authority_account.key,
+        submitted_at: clock.unix_timestamp,
     };
+    evidence.pack(&mut evidence_account.data.borrow_mut());
 
-    // Pack proposal data into the new account
-    let mut proposal_data_buffer = proposal_account.data.borrow_mut();
-    proposal.pack(&mut proposal_data_buffer);
-    drop(proposal_data_buffer);
-
-    // Update milestone to indicate it has a proposal
-    milestone.has_proposal = true;
+    // The milestone points at its latest evidence
+    milestone.evidence_version = version;
+    milestone.evidence_hash = content_hash;
     milestone.pack(&mut milestone_data);
     drop(milestone_data);
 
-    // Increment proposal count in project account
-    project.proposal_count += 1;
-
-    // Pack updated project data
-    project.pack(&mut project_data);
-    drop(project_data);
-
-    msg!("Proposal added and project count updated successfully");
-    Ok(())
-}
-
-// Vote instruction processor
-fn process_vote(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    proposal_id: u64,
-    vote: bool,
-) -> ProgramResult {
-    msg!("Starting vote processing");
-    let account_info_iter = &mut accounts.iter();
-
-    let project_account = next_account_info(account_info_iter)?;
-    msg!(
-        "Processing account 0: Project Account key: {}",
-        project_account.key
-    );
-
-    let proposal_account = next_account_info(account_info_iter)?;
-    msg!(
-        "Processing account 1: Proposal Account key: {}",
-        proposal_account.key
-    );
-
-    let voter_account = next_account_info(account_info_iter)?;
-    msg!(
-        "Processing account 2: Voter Account key: {}",
-        voter_account.key
+    emit_milestone_event(
+        b"MilestoneEvidence",
+        project_account.key,
+        milestone_id,
+        clock.unix_timestamp,
     );
 
-    let system_program = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 3: System Program key: {}",
-        system_program.key
-    );
-
-    // Verify voter is signer
-    if !voter_account.is_signer {
-        msg!("Voter is not a signer");
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-
-    // Verify system program
-    if system_program.key != &system_program::id() {
-        msg!("Invalid system program");
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    // Load and verify project
-    let project_data = project_account.data.borrow();
-    let project = Project::unpack(&project_data)?;
-    drop(project_data);
-
-    // Find expected Proposal PDA (using single byte like create_proposal)
-    let (expected_proposal_pda, _bump) = Pubkey::find_program_address(
-        &[
-            b"proposal",
-            project_account.key.as_ref(),
-            &[proposal_id as u8],
-        ],
-        program_id,
+        "Evidence version {} submitted for milestone {}",
+        version,
+        milestone_id
     );
-
-    // Verify the provided proposal account is the expected PDA
-    if expected_proposal_pda != *proposal_account.key {
-        msg!(
-            "Invalid proposal account PDA. Expected: {}, Got: {}",
-            expected_proposal_pda,
-            proposal_account.key
-        );
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    // Deserialize proposal data
-    let mut proposal_data = proposal_account.try_borrow_mut_data()?;
-    msg!("Proposal account data length: {}", proposal_data.len());
-    let mut proposal = Proposal::unpack(&proposal_data)?;
-
-    if proposal.is_executed {
-        msg!("Proposal is already executed");
-        return Err(UnicornFactoryError::ProposalAlreadyExecuted.into());
-    }
-
-    // Check if voting period has ended
-    let clock = Clock::get()?;
-    if clock.unix_timestamp > proposal.voting_end {
-        msg!("Voting period has ended");
-        return Err(UnicornFactoryError::VotingPeriodEnded.into());
-    }
-
-    // Update vote count
-    if vote {
-        proposal.yes_votes += 1;
-    } else {
-        proposal.no_votes += 1;
-    }
-
-    // Pack updated proposal data
-    proposal.pack(&mut proposal_data);
-    drop(proposal_data);
-
-    msg!("Vote processed successfully");
     Ok(())
 }
 
-// Release funds instruction processor
-fn process_release_funds(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    proposal_id: u64,
-) -> ProgramResult {
-    msg!("Starting funds release for proposal {}", proposal_id);
+// Migrate project instruction processor
+//
+// Brings a project created before the layout was versioned up to the current
+// layout. Fields added since then default to the behaviour the project had
+// before they existed, and the SOL it raised moves out of the project account
+// into its vault. The payer covers the extra rent.
+fn process_migrate_project(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Starting project migration");
     let account_info_iter = &mut accounts.iter();
 
     let project_account = next_account_info(account_info_iter)?;
@@ -1569,166 +11148,138 @@ fn process_release_funds(
         project_account.key
     );
 
-    let proposal_account = next_account_info(account_info_iter)?;
-    msg!(
-        "Processing account 1: Proposal Account key: {}",
-        proposal_account.key
-    );
-
-    let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 2: Authority Account key: {}",
-        authority_account.key
-    );
-
-    let milestone_account = next_account_info(account_info_iter)?;
-     msg!(
-        "Processing account 3: Milestone Account key: {}",
-        milestone_account.key
+        "Processing account 1: Payer Account key: {}",
+        payer_account.key
     );
 
     let system_program = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 4: System Program key: {}",
+        "Processing account 2: System Program key: {}",
         system_program.key
     );
 
-    // Verify system program
-    if system_program.key != &system_program::id() {
-        msg!("Invalid system program");
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    // Load and verify project
-    let project_data = project_account.data.borrow();
-    let project = Project::unpack(&project_data)?;
-    
-    // Store the values we need before dropping the borrow
-    let project_authority = project.authority;
-    let project_bump = project.bump;
-    drop(project_data);
-
-    // Verify authority is project authority and is signer
-    if !authority_account.is_signer || authority_account.key != &project_authority {
-        msg!("Invalid authority or authority is not signer");
-        return Err(UnicornFactoryError::InvalidAuthority.into());
-    }
-
-    // Find expected Proposal PDA (using single byte like create_proposal)
-    let (expected_proposal_pda, _bump) = Pubkey::find_program_address(
-        &[
-            b"proposal",
-            project_account.key.as_ref(),
-            &[proposal_id as u8],
-        ],
-        program_id,
+    let vault_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Vault Account key: {}",
+        vault_account.key
     );
 
-    // Verify the provided proposal account is the expected PDA
-    if expected_proposal_pda != *proposal_account.key {
-        msg!(
-            "Invalid proposal account PDA. Expected: {}, Got: {}",
-            expected_proposal_pda,
-            proposal_account.key
-        );
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    // Load and verify proposal
-    let mut proposal_data = proposal_account.data.borrow_mut();
-    let mut proposal = Proposal::unpack(&proposal_data)?;
+    if !payer_account.is_signer {
+        msg!("Payer is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
 
-    if proposal.is_executed {
-        msg!("Proposal {} is already executed", proposal_id);
-        return Err(UnicornFactoryError::ProposalAlreadyExecuted.into());
+    if system_program.key != &system_program::id() {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
     }
 
-    // Check if voting period has ended
-    let clock = Clock::get()?;
-    if clock.unix_timestamp <= proposal.voting_end {
-        msg!(
-            "Voting period for proposal {} has not ended yet",
-            proposal_id
-        );
-        return Err(UnicornFactoryError::VotingPeriodNotEnded.into());
+    if project_account.owner != program_id {
+        msg!("Project account is not owned by this program");
+        return Err(ProgramError::IncorrectProgramId);
     }
 
-    // Check if proposal has won (yes votes > no votes)
-    if proposal.yes_votes <= proposal.no_votes {
-        msg!("Proposal {} did not win the vote", proposal_id);
-        return Err(UnicornFactoryError::ProposalDidNotPass.into());
+    if project_account.data_len() != LEGACY_PROJECT_LEN {
+        msg!("Project account is already on layout {}", PROJECT_LAYOUT_VERSION);
+        return Err(UnicornFactoryError::InvalidProjectAccount.into());
     }
 
-     // Load and verify milestone account
-    let mut milestone_data = milestone_account.data.borrow_mut();
-    let mut milestone = Milestone::unpack(&milestone_data)?;
+    // The legacy fields are a prefix of the current layout, so the zeroed tail
+    // unpacks to an open bonding-curve raise with every later feature disabled
+    let mut project_data = vec![0; Project::LEN];
+    project_data[..LEGACY_PROJECT_LEN].copy_from_slice(&project_account.data.borrow());
+    project_data[LEGACY_PROJECT_LEN] = PROJECT_LAYOUT_VERSION;
+    let mut project = Project::unpack(&project_data)?;
 
-    // Verify milestone PDA using the milestone_id from the proposal
-    let (expected_milestone_pda, _milestone_bump) = Pubkey::find_program_address(
-        &[b"milestone", project_account.key.as_ref(), &[proposal.milestone_id]],
+    // Verify project PDA
+    let expected_project = Pubkey::create_program_address(
+        &[b"project", project.authority.as_ref(), &[project.bump]],
         program_id,
-    );
-
-     if expected_milestone_pda != *milestone_account.key {
+    )
+    .map_err(|_| UnicornFactoryError::InvalidProjectAccount)?;
+    if expected_project != *project_account.key {
         msg!(
-            "Invalid milestone account PDA for release. Expected: {}, Got: {}",
-            expected_milestone_pda,
-            milestone_account.key
+            "Invalid project account. Expected: {}, Got: {}",
+            expected_project,
+            project_account.key
         );
-        return Err(ProgramError::IncorrectProgramId);
+        return Err(UnicornFactoryError::InvalidProjectAccount.into());
     }
 
-
-    // Release funds using manual lamport transfer
-    let amount_to_release = milestone.amount;
-    msg!(
-        "Releasing {} lamports for proposal {}",
-        amount_to_release,
-        proposal_id
-    );
-
-    // Check if project has enough lamports
-    let project_lamports = project_account.lamports();
-    if project_lamports < amount_to_release {
-        msg!("Project insufficient balance to pay back authority");
-        return Err(UnicornFactoryError::InvalidAmount.into());
+    let (vault_pda, vault_bump) =
+        Pubkey::find_program_address(&[b"vault", project_account.key.as_ref()], program_id);
+    if vault_pda != *vault_account.key {
+        msg!(
+            "Invalid vault account. Expected: {}, Got: {}",
+            vault_pda,
+            vault_account.key
+        );
+        return Err(UnicornFactoryError::InvalidVaultAccount.into());
     }
 
-    // Manual lamport transfer
-    **project_account.lamports.borrow_mut() -= amount_to_release;
-    **authority_account.lamports.borrow_mut() += amount_to_release;
+    // Legacy projects kept raised SOL above the project account's rent
+    let rent = Rent::get()?;
+    let raised_balance = project_account
+        .lamports()
+        .saturating_sub(rent.minimum_balance(LEGACY_PROJECT_LEN));
+
+    project.vault_bump = vault_bump;
+    project.creator = project.authority;
+    project.curve_base_price = 1;
+    project.curve_slope = 100;
+    if !project.is_active {
+        project.raise_state = RaiseState::Succeeded;
+    }
 
-    // Mark proposal as executed
-    proposal.is_executed = true;
+    // Whatever left the project account beyond sells was released to the
+    // authority, and only the remaining balance is left to allocate
+    project.total_released = project.total_raised.saturating_sub(raised_balance);
+    project.total_allocated = project.total_released;
+
+    // Fund the larger project account and the vault's rent-exempt minimum
+    let project_top_up = rent
+        .minimum_balance(Project::LEN)
+        .saturating_sub(rent.minimum_balance(LEGACY_PROJECT_LEN));
+    let vault_top_up = rent
+        .minimum_balance(0)
+        .saturating_sub(vault_account.lamports());
+    for (account, lamports) in [(project_account, project_top_up), (vault_account, vault_top_up)] {
+        if lamports > 0 {
+            invoke(
+                &system_instruction::transfer(payer_account.key, account.key, lamports),
+                &[payer_account.clone(), account.clone()],
+            )?;
+        }
+    }
 
-    msg!("Successfully released {} lamports for proposal {}", amount_to_release, proposal_id);
+    project_account.realloc(Project::LEN, false)?;
 
-    // Pack updated proposal data
-    proposal.pack(&mut proposal_data);
-    drop(proposal_data);
+    // Move the raised SOL into the vault
+    **project_account.try_borrow_mut_lamports()? -= raised_balance;
+    **vault_account.try_borrow_mut_lamports()? += raised_balance;
 
-    // Mark milestone as completed
-    milestone.is_completed = true;
-    milestone.pack(&mut milestone_data);
-    drop(milestone_data);
+    project.pack(&mut project_account.data.borrow_mut());
 
     msg!(
-        "Funds released and proposal {} marked as executed successfully",
-        proposal_id
+        "Project migrated to layout {}. Moved {} lamports to the vault",
+        PROJECT_LAYOUT_VERSION,
+        raised_balance
     );
     Ok(())
 }
 
-// Add milestone instruction processor
-fn process_add_milestone(
+// Deposit escrow instruction processor
+//
+// Moves project tokens into the escrow vault and credits them to the holder's
+// voter escrow, creating either account on first use.
+fn process_deposit_escrow(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    title: String,
-    description: String,
     amount: u64,
 ) -> ProgramResult {
-    msg!("🔧 NEW CONTRACT VERSION - USING INVOKE_SIGNED FOR MILESTONES");
-    msg!("Starting milestone addition");
+    msg!("Starting escrow deposit");
     let account_info_iter = &mut accounts.iter();
 
     let project_account = next_account_info(account_info_iter)?;
@@ -1737,133 +11288,166 @@ fn process_add_milestone(
         project_account.key
     );
 
-    let milestone_account = next_account_info(account_info_iter)?;
+    let holder_account = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 1: Milestone Account key: {}",
-        milestone_account.key
+        "Processing account 1: Holder Account key: {}",
+        holder_account.key
     );
 
-    let authority_account = next_account_info(account_info_iter)?;
+    let holder_token_account = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 2: Authority Account key: {}",
-        authority_account.key
+        "Processing account 2: Holder Token Account key: {}",
+        holder_token_account.key
+    );
+
+    let escrow_account = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 3: Voter Escrow key: {}",
+        escrow_account.key
+    );
+
+    let escrow_vault = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Escrow Vault key: {}",
+        escrow_vault.key
+    );
+
+    let project_token = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Project Token key: {}",
+        project_token.key
+    );
+
+    let token_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 6: Token Program key: {}",
+        token_program.key
     );
 
     let system_program = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 3: System Program key: {}",
+        "Processing account 7: System Program key: {}",
         system_program.key
     );
 
-    // Verify system program
-    if system_program.key != &system_program::id() {
-        msg!("Invalid system program");
-        return Err(ProgramError::IncorrectProgramId);
+    // Verify holder is signer
+    if !holder_account.is_signer {
+        msg!("Holder is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // Load project and get values we need
-    let project = {
-        let project_data = project_account.data.borrow();
-        Project::unpack(&project_data)?
-    };
-
-    let project_authority = project.authority;
-    let milestone_index = project.milestone_count;
+    // Verify programs
+    if token_program.key != &spl_token::id() || system_program.key != &system_program::id() {
+        msg!("Invalid token or system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
 
-    // Verify authority is project authority and is signer
-    if !authority_account.is_signer || authority_account.key != &project_authority {
-        msg!("Invalid authority or authority is not signer");
-        return Err(UnicornFactoryError::InvalidAuthority.into());
+    if amount == 0 {
+        msg!("Escrow amount must be greater than zero");
+        return Err(UnicornFactoryError::InvalidAmount.into());
     }
 
-    // Verify milestone PDA
-    let (expected_milestone_pda, milestone_bump) = Pubkey::find_program_address(
-        &[
-            b"milestone",
-            project_account.key.as_ref(),
-            &[milestone_index],
-        ],
-        program_id,
-    );
+    // Load and verify project
+    let project = load_project(program_id, project_account)?;
 
-    if expected_milestone_pda != *milestone_account.key {
+    if project.token_mint != *project_token.key {
         msg!(
-            "Invalid milestone account PDA. Expected: {}, Got: {}",
-            expected_milestone_pda,
-            milestone_account.key
+            "Project token mint mismatch. Project mint: {}, Provided mint: {}",
+            project.token_mint,
+            project_token.key
         );
-        return Err(ProgramError::IncorrectProgramId);
+        return Err(ProgramError::InvalidAccountData);
     }
 
-    // Verify milestone account is not already initialized
-    if milestone_account.data.borrow().iter().any(|&x| x != 0) {
-        msg!("Milestone account already initialized");
-        return Err(ProgramError::AccountAlreadyInitialized);
+    let escrow_vault_bump = verify_escrow_vault(program_id, project_account, escrow_vault)?;
+    if escrow_vault.data_is_empty() {
+        let escrow_vault_seeds: &[&[u8]] = &[
+            b"escrow_vault",
+            project_account.key.as_ref(),
+            &[escrow_vault_bump],
+        ];
+        amm::create_pool_token_account(
+            holder_account,
+            escrow_vault,
+            project_token,
+            project_account,
+            system_program,
+            escrow_vault_seeds,
+        )?;
     }
 
-    // Calculate rent
-    let rent = Rent::get()?;
-    let space = Milestone::LEN;
-    let lamports = rent.minimum_balance(space);
-
-    // Create milestone account using invoke_signed
-    let milestone_seeds = &[
-        b"milestone".as_ref(),
-        project_account.key.as_ref(),
-        &[milestone_index],
-        &[milestone_bump],
-    ];
-
-    invoke_signed(
-        &system_instruction::create_account(
-            authority_account.key,
-            milestone_account.key,
-            lamports,
-            space as u64,
+    let escrow_bump =
+        verify_voter_escrow(program_id, project_account, holder_account.key, escrow_account)?;
+    let mut escrow = if escrow_account.data_is_empty() {
+        let escrow_seeds: &[&[u8]] = &[
+            b"escrow",
+            project_account.key.as_ref(),
+            holder_account.key.as_ref(),
+            &[escrow_bump],
+        ];
+        amm::create_pda_account(
+            holder_account,
+            escrow_account,
+            system_program,
+            VoterEscrow::LEN,
             program_id,
-        ),
+            escrow_seeds,
+        )?;
+        VoterEscrow {
+            project: *project_account.key,
+            holder: *holder_account.key,
+            amount: 0,
+            locked_until: 0,
+            proposal_until: 0,
+        }
+    } else {
+        let escrow_data = escrow_account.data.borrow();
+        VoterEscrow::unpack(&escrow_data)?
+    };
+
+    invoke(
+        &token_instruction::transfer(
+            token_program.key,
+            holder_token_account.key,
+            escrow_vault.key,
+            holder_account.key,
+            &[],
+            amount,
+        )?,
         &[
-            authority_account.clone(),
-            milestone_account.clone(),
-            system_program.clone(),
+            holder_token_account.clone(),
+            escrow_vault.clone(),
+            holder_account.clone(),
         ],
-        &[milestone_seeds],
     )?;
 
-    // Create and pack milestone data
-    let milestone = Milestone {
-        title: title.clone(),
-        description: description.clone(),
-        amount,
-        is_completed: false,
-        completed_at: 0,
-        has_proposal: false,
-    };
-
-    {
-        let mut milestone_data_buffer = milestone_account.data.borrow_mut();
-        milestone.pack(&mut milestone_data_buffer);
-    }
-
-    // Update project milestone count
-    {
-        let mut project_data = project_account.data.borrow_mut();
-        let mut project = Project::unpack(&project_data)?;
-        project.milestone_count += 1;
-        project.pack(&mut project_data);
-    }
+    escrow.amount = escrow
+        .amount
+        .checked_add(amount)
+        .ok_or(UnicornFactoryError::Overflow)?;
+    let mut escrow_data = escrow_account.data.borrow_mut();
+    escrow.pack(&mut escrow_data);
+    drop(escrow_data);
 
-    msg!("Milestone added successfully");
+    msg!(
+        "Escrowed {} tokens for {}, {} in total",
+        amount,
+        holder_account.key,
+        escrow.amount
+    );
     Ok(())
 }
 
-// Complete milestone instruction processor
-fn process_complete_milestone(
+// Withdraw escrow instruction processor
+//
+// Returns escrowed project tokens to the holder once every tally they were
+// counted in has closed.
+fn process_withdraw_escrow(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    milestone_id: u8,
+    amount: u64,
 ) -> ProgramResult {
-    msg!("Starting milestone completion");
+    msg!("Starting escrow withdrawal");
     let account_info_iter = &mut accounts.iter();
 
     let project_account = next_account_info(account_info_iter)?;
@@ -1872,76 +11456,108 @@ fn process_complete_milestone(
         project_account.key
     );
 
-    let milestone_account = next_account_info(account_info_iter)?;
+    let holder_account = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 1: Milestone Account key: {}",
-        milestone_account.key
+        "Processing account 1: Holder Account key: {}",
+        holder_account.key
     );
 
-    let authority_account = next_account_info(account_info_iter)?;
+    let holder_token_account = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 2: Authority Account key: {}",
-        authority_account.key
+        "Processing account 2: Holder Token Account key: {}",
+        holder_token_account.key
     );
 
-    let system_program = next_account_info(account_info_iter)?;
+    let escrow_account = next_account_info(account_info_iter)?;
     msg!(
-        "Processing account 3: System Program key: {}",
-        system_program.key
+        "Processing account 3: Voter Escrow key: {}",
+        escrow_account.key
     );
 
-    // Verify system program
-    if system_program.key != &system_program::id() {
-        msg!("Invalid system program");
+    let escrow_vault = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 4: Escrow Vault key: {}",
+        escrow_vault.key
+    );
+
+    let token_program = next_account_info(account_info_iter)?;
+    msg!(
+        "Processing account 5: Token Program key: {}",
+        token_program.key
+    );
+
+    // Verify holder is signer
+    if !holder_account.is_signer {
+        msg!("Holder is not a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify token program
+    if token_program.key != &spl_token::id() {
+        msg!("Invalid token program");
         return Err(ProgramError::IncorrectProgramId);
     }
 
     // Load and verify project
-    let project_data = project_account.data.borrow();
-    let project = Project::unpack(&project_data)?;
-    drop(project_data);
+    let project = load_project(program_id, project_account)?;
 
-    // Verify authority is project authority and is signer
-    if !authority_account.is_signer || authority_account.key != &project.authority {
-        msg!("Invalid authority or authority is not signer");
-        return Err(UnicornFactoryError::InvalidAuthority.into());
+    verify_escrow_vault(program_id, project_account, escrow_vault)?;
+    verify_voter_escrow(program_id, project_account, holder_account.key, escrow_account)?;
+    if escrow_account.data_is_empty() {
+        msg!("Holder has no voter escrow");
+        return Err(UnicornFactoryError::NothingToClaim.into());
     }
 
-    // Find expected Milestone PDA
-    let (expected_milestone_pda, _bump) = Pubkey::find_program_address(
-        &[b"milestone", project_account.key.as_ref(), &[milestone_id]],
-        program_id,
-    );
+    let mut escrow_data = escrow_account.data.borrow_mut();
+    let mut escrow = VoterEscrow::unpack(&escrow_data)?;
 
-    // Verify the provided milestone account is the expected PDA
-    if expected_milestone_pda != *milestone_account.key {
+    let clock = Clock::get()?;
+    if clock.unix_timestamp <= escrow.locked_until {
+        msg!("Escrow is locked until {}", escrow.locked_until);
+        return Err(UnicornFactoryError::EscrowLocked.into());
+    }
+
+    if amount == 0 || amount > escrow.amount {
         msg!(
-            "Invalid milestone account PDA. Expected: {}, Got: {}",
-            expected_milestone_pda,
-            milestone_account.key
+            "Invalid withdrawal. Requested: {}, Escrowed: {}",
+            amount,
+            escrow.amount
         );
-        return Err(ProgramError::IncorrectProgramId);
+        return Err(UnicornFactoryError::InvalidAmount.into());
     }
 
-    // Load and verify milestone
-    let mut milestone_data = milestone_account.data.borrow_mut();
-    let mut milestone = Milestone::unpack(&milestone_data)?;
-
-    if milestone.is_completed {
-        msg!("Milestone is already completed");
-        return Err(UnicornFactoryError::MilestoneAlreadyCompleted.into());
-    }
+    let project_seeds = &[
+        b"project".as_ref(),
+        project.creator.as_ref(),
+        &[project.bump],
+    ];
 
-    // Complete milestone
-    msg!("Completing milestone {}", milestone_id);
-    let clock = Clock::get()?;
-    milestone.is_completed = true;
-    milestone.completed_at = clock.unix_timestamp;
+    invoke_signed(
+        &token_instruction::transfer(
+            token_program.key,
+            escrow_vault.key,
+            holder_token_account.key,
+            project_account.key,
+            &[],
+            amount,
+        )?,
+        &[
+            escrow_vault.clone(),
+            holder_token_account.clone(),
+            project_account.clone(),
+        ],
+        &[project_seeds],
+    )?;
 
-    // Pack updated milestone data
-    milestone.pack(&mut milestone_data);
-    drop(milestone_data);
+    escrow.amount -= amount;
+    escrow.pack(&mut escrow_data);
+    drop(escrow_data);
 
-    msg!("Milestone {} completed successfully", milestone_id);
+    msg!(
+        "Withdrew {} escrowed tokens for {}, {} left",
+        amount,
+        holder_account.key,
+        escrow.amount
+    );
     Ok(())
 }
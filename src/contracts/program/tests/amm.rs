@@ -0,0 +1,220 @@
+// Program tests for graduation into the project's constant-product pool.
+
+mod common;
+
+use common::{assert_error, create_token_account, Env, Launch, FUNDING_GOAL};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::BanksClientError;
+use solana_sdk::{instruction::AccountMeta, signature::Signer};
+use unicorn_factory::UnicornFactoryError;
+
+const TOTAL_SUPPLY: u64 = 1_000_000_000_000_000;
+
+// The pool, its token and quote vaults and its LP mint
+fn pool_accounts(env: &Env) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let pool = env.pool_pda(b"pool", &env.project);
+    (
+        pool,
+        env.pool_pda(b"pool_token", &pool),
+        env.pool_pda(b"pool_quote", &pool),
+        env.pool_pda(b"lp_mint", &pool),
+    )
+}
+
+async fn graduate(env: &mut Env) -> Result<(), BanksClientError> {
+    let (pool, pool_token, pool_quote, lp_mint) = pool_accounts(env);
+    let accounts = vec![
+        AccountMeta::new(env.project, false),
+        AccountMeta::new(env.ctx.payer.pubkey(), true),
+        AccountMeta::new(env.vault, false),
+        AccountMeta::new(pool, false),
+        AccountMeta::new(pool_token, false),
+        AccountMeta::new(pool_quote, false),
+        AccountMeta::new(lp_mint, false),
+        AccountMeta::new(env.token_mint, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(env.usdc, false),
+    ];
+    env.send(accounts, vec![15], &[]).await
+}
+
+#[tokio::test]
+async fn graduation_seeds_a_pool_that_swaps_and_takes_liquidity() {
+    let mut env = Env::launch(Launch {
+        graduation_bps: 2_000,
+        graduation_token_reserve: 1_000_000,
+        total_supply: TOTAL_SUPPLY,
+        ..Launch::default()
+    })
+    .await;
+    let (contributor, usdc_account, token_account) = env.wallet(2 * FUNDING_GOAL).await;
+    env.contribute(&contributor, usdc_account, token_account, 900_000)
+        .await
+        .unwrap();
+    // Crossing the goal closes the raise as succeeded
+    env.contribute(&contributor, usdc_account, token_account, 300_000)
+        .await
+        .unwrap();
+
+    let (pool, pool_token, pool_quote, lp_mint) = pool_accounts(&env);
+    graduate(&mut env).await.unwrap();
+
+    // The pool takes its share of the goal, not of the oversubscribed raise
+    assert_eq!(env.token_balance(pool_quote).await, 200_000);
+    assert_eq!(env.token_balance(pool_token).await, 1_000_000);
+    assert_eq!(env.token_balance(env.vault).await, 1_000_000);
+
+    // Buy tokens out of the pool with USDC
+    let tokens_before = env.token_balance(token_account).await;
+    let mut data = vec![16];
+    data.extend_from_slice(&20_000u64.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.push(1); // quote_to_token
+    let accounts = vec![
+        AccountMeta::new(pool, false),
+        AccountMeta::new(contributor.pubkey(), true),
+        AccountMeta::new(token_account, false),
+        AccountMeta::new(pool_token, false),
+        AccountMeta::new(pool_quote, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new(usdc_account, false),
+    ];
+    env.send(accounts, data, &[&contributor]).await.unwrap();
+    let bought = env.token_balance(token_account).await - tokens_before;
+    assert!(bought > 0);
+    assert_eq!(env.token_balance(pool_quote).await, 220_000);
+    assert_eq!(env.token_balance(pool_token).await, 1_000_000 - bought);
+
+    // Provide liquidity and take it back out
+    let lp_account = create_token_account(&mut env.ctx, &lp_mint, &contributor.pubkey()).await;
+    let liquidity_accounts = vec![
+        AccountMeta::new(pool, false),
+        AccountMeta::new(contributor.pubkey(), true),
+        AccountMeta::new(token_account, false),
+        AccountMeta::new(pool_token, false),
+        AccountMeta::new(pool_quote, false),
+        AccountMeta::new(lp_mint, false),
+        AccountMeta::new(lp_account, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new(usdc_account, false),
+    ];
+    let usdc_before = env.token_balance(usdc_account).await;
+    let mut data = vec![17];
+    data.extend_from_slice(&10_000u64.to_le_bytes());
+    data.extend_from_slice(&u64::MAX.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    env.send(liquidity_accounts.clone(), data, &[&contributor])
+        .await
+        .unwrap();
+    let lp_amount = env.token_balance(lp_account).await;
+    let quote_added = usdc_before - env.token_balance(usdc_account).await;
+    assert!(lp_amount > 0);
+    assert_eq!(
+        env.token_balance(token_account).await,
+        tokens_before + bought - 10_000
+    );
+
+    let mut data = vec![18];
+    data.extend_from_slice(&lp_amount.to_le_bytes());
+    data.extend_from_slice(&[0; 8 + 8]); // no minimums
+    env.send(liquidity_accounts.clone(), data, &[&contributor])
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(lp_account).await, 0);
+    // Rounding only ever favours the pool
+    assert!(env.token_balance(usdc_account).await <= usdc_before);
+    assert!(env.token_balance(usdc_account).await + 1 >= usdc_before);
+    assert!(quote_added > 0);
+
+    // Locked graduation liquidity cannot be withdrawn
+    let mut data = vec![18];
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&[0; 8 + 8]);
+    assert!(env
+        .send(liquidity_accounts, data, &[&contributor])
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn only_a_successful_raise_graduates_and_swaps_honour_slippage() {
+    let mut env = Env::launch(Launch {
+        graduation_bps: 2_000,
+        graduation_token_reserve: 1_000_000,
+        total_supply: TOTAL_SUPPLY,
+        ..Launch::default()
+    })
+    .await;
+    let (contributor, usdc_account, token_account) = env.wallet(2 * FUNDING_GOAL).await;
+    env.contribute(&contributor, usdc_account, token_account, 500_000)
+        .await
+        .unwrap();
+    assert_error(
+        graduate(&mut env).await,
+        UnicornFactoryError::RaiseNotSucceeded,
+    );
+
+    env.contribute(&contributor, usdc_account, token_account, 500_000)
+        .await
+        .unwrap();
+    graduate(&mut env).await.unwrap();
+    assert!(env.project_state().await.is_graduated);
+    assert_error(
+        graduate(&mut env).await,
+        UnicornFactoryError::AlreadyGraduated,
+    );
+
+    // A swap that would return less than the caller's minimum is rejected
+    let (pool, pool_token, pool_quote, _) = pool_accounts(&env);
+    let mut data = vec![16];
+    data.extend_from_slice(&20_000u64.to_le_bytes());
+    data.extend_from_slice(&u64::MAX.to_le_bytes());
+    data.push(1); // quote_to_token
+    let accounts = vec![
+        AccountMeta::new(pool, false),
+        AccountMeta::new(contributor.pubkey(), true),
+        AccountMeta::new(token_account, false),
+        AccountMeta::new(pool_token, false),
+        AccountMeta::new(pool_quote, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new(usdc_account, false),
+    ];
+    assert_error(
+        env.send(accounts, data, &[&contributor]).await,
+        UnicornFactoryError::SlippageExceeded,
+    );
+    assert_eq!(env.token_balance(pool_quote).await, 200_000);
+}
+
+#[tokio::test]
+async fn the_pool_reserve_must_fit_in_the_remaining_supply() {
+    let mut env = Env::launch(Launch {
+        graduation_bps: 2_000,
+        graduation_token_reserve: 1_000_000,
+        total_supply: 1_000_001,
+        ..Launch::default()
+    })
+    .await;
+    let (contributor, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&contributor, usdc_account, token_account, FUNDING_GOAL)
+        .await
+        .unwrap();
+    assert!(env.token_balance(token_account).await > 1);
+
+    assert_error(
+        graduate(&mut env).await,
+        UnicornFactoryError::TokenSupplyExceeded,
+    );
+    let (_, pool_token, _, _) = pool_accounts(&env);
+    assert!(env
+        .ctx
+        .banks_client
+        .get_account(pool_token)
+        .await
+        .unwrap()
+        .is_none());
+}
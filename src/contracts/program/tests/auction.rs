@@ -0,0 +1,181 @@
+// Program tests for Dutch and batch auctions and their settlement at the
+// clearing price.
+
+mod common;
+
+use common::{assert_error, Env, Launch, FUNDING_GOAL};
+use unicorn_factory::UnicornFactoryError;
+
+#[tokio::test]
+async fn undersold_dutch_auction_fills_bids_at_the_clearing_price() {
+    let mut env = Env::launch(Launch {
+        dutch_auction: Some((10, 2, 100, FUNDING_GOAL)),
+        ..Launch::default()
+    })
+    .await;
+    let (early, early_usdc, early_tokens) = env.wallet(FUNDING_GOAL).await;
+    let (late, late_usdc, late_tokens) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&early, early_usdc, early_tokens, 500_000)
+        .await
+        .unwrap();
+    env.warp(50).await;
+    env.contribute(&late, late_usdc, late_tokens, 600_000)
+        .await
+        .unwrap();
+
+    // Bids for 150,000 tokens leave the auction undersold, so it clears at the
+    // floor and each deposit buys tokens at that price
+    env.warp(100).await;
+    env.finalize().await.unwrap();
+    env.settle_bid(&early, early_usdc, early_tokens)
+        .await
+        .unwrap();
+    env.settle_bid(&late, late_usdc, late_tokens).await.unwrap();
+
+    assert_eq!(env.token_balance(early_tokens).await, 250_000);
+    assert_eq!(env.token_balance(late_tokens).await, 300_000);
+    assert_eq!(env.token_balance(env.vault).await, 1_100_000);
+}
+
+#[tokio::test]
+async fn late_bidders_never_pay_above_their_own_price() {
+    let mut env = Env::launch(Launch {
+        dutch_auction: Some((10, 2, 100, 200_000)),
+        ..Launch::default()
+    })
+    .await;
+    let (early, early_usdc, early_tokens) = env.wallet(FUNDING_GOAL).await;
+    let (late, late_usdc, late_tokens) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&early, early_usdc, early_tokens, FUNDING_GOAL)
+        .await
+        .unwrap();
+    env.warp(50).await;
+    env.contribute(&late, late_usdc, late_tokens, 540_000)
+        .await
+        .unwrap();
+
+    // 1,540,000 over 200,000 tokens would clear at 8, above the late bid's 6,
+    // so the auction clears at 6 and each bid gets what it locked in
+    env.warp(100).await;
+    env.finalize().await.unwrap();
+    assert_eq!(env.project_state().await.clearing_price, 6);
+    env.settle_bid(&early, early_usdc, early_tokens)
+        .await
+        .unwrap();
+    env.settle_bid(&late, late_usdc, late_tokens).await.unwrap();
+
+    assert_eq!(env.token_balance(early_tokens).await, 100_000);
+    assert_eq!(env.token_balance(late_tokens).await, 90_000);
+    assert_eq!(env.token_balance(early_usdc).await, FUNDING_GOAL - 600_000);
+    assert_eq!(env.token_balance(late_usdc).await, FUNDING_GOAL - 540_000);
+    assert_eq!(env.token_balance(env.vault).await, 1_140_000);
+
+    // Each bid settles once
+    assert_error(
+        env.settle_bid(&late, late_usdc, late_tokens).await,
+        UnicornFactoryError::NothingToClaim,
+    );
+}
+
+#[tokio::test]
+async fn sold_out_auctions_refund_what_the_tokens_did_not_cost() {
+    let mut env = Env::launch(Launch {
+        dutch_auction: Some((10, 2, 100, 100_000)),
+        ..Launch::default()
+    })
+    .await;
+    let (bidder, usdc_account, token_account) = env.wallet(2 * FUNDING_GOAL).await;
+
+    // A bid that cannot buy a single token is turned away
+    assert_error(
+        env.contribute(&bidder, usdc_account, token_account, 9)
+            .await,
+        UnicornFactoryError::InvalidAmount,
+    );
+
+    // Buying the whole supply closes the auction at the bid's price
+    env.contribute(&bidder, usdc_account, token_account, 1_000_009)
+        .await
+        .unwrap();
+    let project = env.project_state().await;
+    assert_eq!(project.auction_tokens_sold, 100_000);
+    assert_eq!(project.clearing_price, 10);
+
+    env.settle_bid(&bidder, usdc_account, token_account)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(token_account).await, 100_000);
+    assert_eq!(
+        env.token_balance(usdc_account).await,
+        2 * FUNDING_GOAL - 1_000_000
+    );
+    assert_eq!(env.token_balance(env.vault).await, 1_000_000);
+}
+
+#[tokio::test]
+async fn batch_auctions_raise_only_what_settled_commitments_paid() {
+    let mut env = Env::launch(Launch {
+        batch_auction: Some((1, 100, 100_000)),
+        ..Launch::default()
+    })
+    .await;
+    let (first, first_usdc, first_tokens) = env.wallet(FUNDING_GOAL).await;
+    let (second, second_usdc, second_tokens) = env.wallet(FUNDING_GOAL).await;
+    env.commit(&first, first_usdc, 600_011).await.unwrap();
+    env.commit(&second, second_usdc, 500_011).await.unwrap();
+    assert_error(env.settle().await, UnicornFactoryError::RaiseStillOpen);
+
+    // 1,100,022 over 100,000 tokens clears at 12. Late commitments are refused.
+    env.warp(101).await;
+    assert_error(
+        env.commit(&first, first_usdc, 1_000).await,
+        UnicornFactoryError::RaiseClosed,
+    );
+    env.settle().await.unwrap();
+    let project = env.project_state().await;
+    assert_eq!(project.clearing_price, 12);
+    assert_eq!(project.total_raised, 0);
+
+    // Each claim raises the whole tokens it bought, not its share of the
+    // rounded-down total, and refunds the rest
+    env.claim_commitment(&first, first_usdc, first_tokens)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(first_tokens).await, 50_000);
+    assert_eq!(env.token_balance(first_usdc).await, FUNDING_GOAL - 600_000);
+    assert_eq!(env.project_state().await.total_raised, 600_000);
+
+    env.claim_commitment(&second, second_usdc, second_tokens)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(second_tokens).await, 41_667);
+    assert_eq!(env.token_balance(second_usdc).await, FUNDING_GOAL - 500_004);
+    assert_eq!(env.project_state().await.total_raised, 1_100_004);
+    assert_eq!(env.token_balance(env.vault).await, 1_100_004);
+
+    assert_error(
+        env.claim_commitment(&second, second_usdc, second_tokens)
+            .await,
+        UnicornFactoryError::NothingToClaim,
+    );
+}
+
+#[tokio::test]
+async fn failed_batch_auctions_refund_every_commitment() {
+    let mut env = Env::launch(Launch {
+        batch_auction: Some((1, 100, 100_000)),
+        ..Launch::default()
+    })
+    .await;
+    let (user, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.commit(&user, usdc_account, 400_000).await.unwrap();
+
+    env.warp(101).await;
+    env.settle().await.unwrap();
+    env.claim_commitment(&user, usdc_account, token_account)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(token_account).await, 0);
+    assert_eq!(env.token_balance(usdc_account).await, FUNDING_GOAL);
+    assert_eq!(env.project_state().await.total_raised, 0);
+}
@@ -0,0 +1,1138 @@
+// Shared program-test harness: a validator running the program with a
+// locally minted mock USDC as the quote token and a project raising in it.
+// Each test binary uses a different subset of the helpers.
+#![allow(dead_code)]
+
+use solana_program::{
+    keccak, program_pack::Pack, pubkey::Pubkey, system_instruction, sysvar::clock::Clock,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use unicorn_factory::{
+    process_instruction, Milestone, MilestoneEvidence, Project, Proposal, ProposalAction,
+    UnicornFactoryError, VOTE_YES,
+};
+
+pub const USDC_DECIMALS: u8 = 6;
+pub const FUNDING_GOAL: u64 = 1_000_000;
+// Project options the tests vary; everything else keeps the program defaults
+#[derive(Default)]
+pub struct Launch {
+    // Makes the authority the guardian
+    pub authority_guards: bool,
+    // Seconds a passed proposal waits before it takes effect, and then has to
+    // take effect in, or forever if zero
+    pub execution_delay: i64,
+    pub execution_window: i64,
+    pub veto_threshold_bps: u16,
+    pub quorum_bps: u16,
+    // Seconds after voting for revealing committed ballots, which are only
+    // taken if this is non-zero
+    pub reveal_period: i64,
+    pub proposal_threshold: u64,
+    pub proposal_deposit: u64,
+    pub total_supply: u64,
+    pub vesting_bps: u16,
+    // Holds bought tokens back until a successful raise
+    pub lockup: bool,
+    // Seconds from now the raise closes, or never if zero
+    pub deadline_in: i64,
+    pub graduation_bps: u16,
+    pub graduation_token_reserve: u64,
+    pub max_contribution_per_wallet: u64,
+    // Runs a Dutch auction of `.3` tokens from now for `.2` seconds, decaying
+    // from price `.0` to `.1`
+    pub dutch_auction: Option<(u64, u64, i64, u64)>,
+    // Collects batch auction commitments for `.1` seconds from now, clearing
+    // `.2` tokens at no less than `.0`
+    pub batch_auction: Option<(u64, i64, u64)>,
+}
+
+pub struct Env {
+    pub ctx: ProgramTestContext,
+    pub program_id: Pubkey,
+    pub usdc: Pubkey,
+    pub token_mint: Pubkey,
+    pub project: Pubkey,
+    pub vault: Pubkey,
+}
+
+impl Env {
+    // Starts a validator with the program, mints mock USDC and initializes a
+    // project raising FUNDING_GOAL of it, closing `deadline_in` seconds from now
+    // or never if zero
+    pub async fn new(deadline_in: i64) -> Self {
+        Env::launch(Launch {
+            deadline_in,
+            ..Launch::default()
+        })
+        .await
+    }
+
+    // Like `new`, with the options in `launch`
+    pub async fn launch(launch: Launch) -> Self {
+        let program_id = Pubkey::new_unique();
+        let program_test = ProgramTest::new(
+            "unicorn_factory",
+            program_id,
+            processor!(process_instruction),
+        );
+        let mut ctx = program_test.start_with_context().await;
+        let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+        let raise_deadline = if launch.deadline_in == 0 {
+            0
+        } else {
+            clock.unix_timestamp + launch.deadline_in
+        };
+
+        let authority = ctx.payer.pubkey();
+        let (project, _) =
+            Pubkey::find_program_address(&[b"project", authority.as_ref()], &program_id);
+        let (vault, _) = Pubkey::find_program_address(&[b"vault", project.as_ref()], &program_id);
+
+        let usdc = Keypair::new();
+        create_mint(&mut ctx, &usdc, &authority, USDC_DECIMALS).await;
+        let token_mint = Keypair::new();
+        create_mint(&mut ctx, &token_mint, &project, 9).await;
+
+        let mut env = Env {
+            ctx,
+            program_id,
+            usdc: usdc.pubkey(),
+            token_mint: token_mint.pubkey(),
+            project,
+            vault,
+        };
+
+        let mut data = vec![0];
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(b"Test");
+        data.extend_from_slice(b"TST");
+        data.extend_from_slice(&FUNDING_GOAL.to_le_bytes());
+        data.extend_from_slice(usdc.pubkey().as_ref()); // quote_mint
+        data.extend_from_slice(&launch.total_supply.to_le_bytes());
+        data.extend_from_slice(&launch.vesting_bps.to_le_bytes());
+        data.push(launch.lockup as u8);
+        data.extend_from_slice(&raise_deadline.to_le_bytes());
+        data.extend_from_slice(&launch.graduation_bps.to_le_bytes());
+        data.extend_from_slice(&launch.graduation_token_reserve.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes()); // min_contribution
+        data.extend_from_slice(&launch.max_contribution_per_wallet.to_le_bytes());
+        match launch.dutch_auction {
+            Some((start_price, floor_price, duration, supply)) => {
+                data.push(1); // launch_mode
+                data.extend_from_slice(&start_price.to_le_bytes());
+                data.extend_from_slice(&floor_price.to_le_bytes());
+                data.extend_from_slice(&clock.unix_timestamp.to_le_bytes());
+                data.extend_from_slice(&(clock.unix_timestamp + duration).to_le_bytes());
+                data.extend_from_slice(&supply.to_le_bytes());
+            }
+            None => match launch.batch_auction {
+                Some((floor_price, duration, supply)) => {
+                    data.push(2); // launch_mode
+                    data.extend_from_slice(&0u64.to_le_bytes()); // start_price
+                    data.extend_from_slice(&floor_price.to_le_bytes());
+                    data.extend_from_slice(&clock.unix_timestamp.to_le_bytes());
+                    data.extend_from_slice(&(clock.unix_timestamp + duration).to_le_bytes());
+                    data.extend_from_slice(&supply.to_le_bytes());
+                }
+                None => {
+                    data.push(0); // launch_mode
+                    data.extend_from_slice(&[0; 8 * 5]); // auction terms
+                }
+            },
+        }
+        data.extend_from_slice(&launch.execution_delay.to_le_bytes());
+        data.extend_from_slice(&launch.execution_window.to_le_bytes());
+        let guardian = if launch.authority_guards {
+            authority
+        } else {
+            Pubkey::default()
+        };
+        data.extend_from_slice(guardian.as_ref());
+        data.extend_from_slice(&launch.veto_threshold_bps.to_le_bytes());
+        data.extend_from_slice(&launch.quorum_bps.to_le_bytes());
+        data.extend_from_slice(&launch.reveal_period.to_le_bytes());
+        data.extend_from_slice(&launch.proposal_threshold.to_le_bytes());
+        data.extend_from_slice(&launch.proposal_deposit.to_le_bytes());
+
+        let accounts = vec![
+            AccountMeta::new(project, false),
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(env.token_mint, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(env.usdc, false),
+        ];
+        env.send(accounts, data, &[]).await.unwrap();
+        env
+    }
+
+    pub async fn send(
+        &mut self,
+        accounts: Vec<AccountMeta>,
+        data: Vec<u8>,
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let instruction = Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        };
+        send(&mut self.ctx, &[instruction], signers).await
+    }
+
+    // Funds a new wallet with SOL for rent and `usdc` of mock USDC, returning
+    // it with its USDC and project token accounts
+    pub async fn wallet(&mut self, usdc: u64) -> (Keypair, Pubkey, Pubkey) {
+        let wallet = Keypair::new();
+        let payer = self.ctx.payer.pubkey();
+        let fund = system_instruction::transfer(&payer, &wallet.pubkey(), 1_000_000_000);
+        send(&mut self.ctx, &[fund], &[]).await.unwrap();
+
+        let usdc_account =
+            create_token_account(&mut self.ctx, &self.usdc.clone(), &wallet.pubkey()).await;
+        let token_account =
+            create_token_account(&mut self.ctx, &self.token_mint.clone(), &wallet.pubkey()).await;
+        if usdc > 0 {
+            let mint_to = spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &self.usdc,
+                &usdc_account,
+                &payer,
+                &[],
+                usdc,
+            )
+            .unwrap();
+            send(&mut self.ctx, &[mint_to], &[]).await.unwrap();
+        }
+        (wallet, usdc_account, token_account)
+    }
+
+    pub fn record(&self, contributor: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"contributor", self.project.as_ref(), contributor.as_ref()],
+            &self.program_id,
+        )
+        .0
+    }
+
+    pub async fn contribute(
+        &mut self,
+        contributor: &Keypair,
+        usdc_account: Pubkey,
+        token_account: Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut data = vec![1];
+        data.extend_from_slice(&amount.to_le_bytes());
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(contributor.pubkey(), true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(self.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new(self.record(&contributor.pubkey()), false),
+            AccountMeta::new(usdc_account, false),
+        ];
+        self.send(accounts, data, &[contributor]).await
+    }
+
+    pub async fn finalize(&mut self) -> Result<(), BanksClientError> {
+        self.send(vec![AccountMeta::new(self.project, false)], vec![12], &[])
+            .await
+    }
+
+    pub fn commitment(&self, user: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"commitment", self.project.as_ref(), user.as_ref()],
+            &self.program_id,
+        )
+        .0
+    }
+
+    pub async fn commit(
+        &mut self,
+        user: &Keypair,
+        usdc_account: Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut data = vec![22];
+        data.extend_from_slice(&amount.to_le_bytes());
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(user.pubkey(), true),
+            AccountMeta::new(self.commitment(&user.pubkey()), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new(usdc_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ];
+        self.send(accounts, data, &[user]).await
+    }
+
+    pub async fn settle(&mut self) -> Result<(), BanksClientError> {
+        self.send(vec![AccountMeta::new(self.project, false)], vec![23], &[])
+            .await
+    }
+
+    pub async fn claim_commitment(
+        &mut self,
+        user: &Keypair,
+        usdc_account: Pubkey,
+        token_account: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(user.pubkey(), true),
+            AccountMeta::new(self.commitment(&user.pubkey()), false),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(self.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new(usdc_account, false),
+        ];
+        self.send(accounts, vec![24], &[user]).await
+    }
+
+    pub async fn settle_bid(
+        &mut self,
+        bidder: &Keypair,
+        usdc_account: Pubkey,
+        token_account: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(bidder.pubkey(), true),
+            AccountMeta::new(self.record(&bidder.pubkey()), false),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(self.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new(usdc_account, false),
+        ];
+        self.send(accounts, vec![21], &[bidder]).await
+    }
+
+    pub fn pool_pda(&self, seed: &[u8], base: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[seed, base.as_ref()], &self.program_id).0
+    }
+
+    // Funding round `index` of the project
+    pub fn round(&self, index: u8) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"round", self.project.as_ref(), &[index]],
+            &self.program_id,
+        )
+        .0
+    }
+
+    // Schedules the next public, fixed-price funding round
+    pub async fn add_round(
+        &mut self,
+        goal: u64,
+        price: u64,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<(), BanksClientError> {
+        let round_count = self.project_state().await.round_count;
+
+        let mut data = vec![20];
+        data.extend_from_slice(&goal.to_le_bytes());
+        data.push(0); // fixed pricing
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&start_time.to_le_bytes());
+        data.extend_from_slice(&end_time.to_le_bytes());
+        data.extend_from_slice(&[0; 32]); // public round
+        let mut accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(self.round(round_count), false),
+            AccountMeta::new(self.ctx.payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ];
+        for index in 0..round_count {
+            accounts.push(AccountMeta::new_readonly(self.round(index), false));
+        }
+        self.send(accounts, data, &[]).await
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        let clock: Clock = self.ctx.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp
+    }
+
+    pub async fn buy(
+        &mut self,
+        buyer: &Keypair,
+        usdc_account: Pubkey,
+        token_account: Pubkey,
+        amount: u64,
+        rounds: &[Pubkey],
+    ) -> Result<(), BanksClientError> {
+        let mut data = vec![2];
+        data.extend_from_slice(&amount.to_le_bytes());
+        let mut accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(buyer.pubkey(), true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(self.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new(self.record(&buyer.pubkey()), false),
+            AccountMeta::new(usdc_account, false),
+        ];
+        for round in rounds {
+            accounts.push(AccountMeta::new(*round, false));
+        }
+        self.send(accounts, data, &[buyer]).await
+    }
+
+    pub async fn sell(
+        &mut self,
+        seller: &Keypair,
+        usdc_account: Pubkey,
+        token_account: Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut data = vec![3];
+        data.extend_from_slice(&amount.to_le_bytes());
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(seller.pubkey(), true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(self.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new(self.record(&seller.pubkey()), false),
+            AccountMeta::new(usdc_account, false),
+        ];
+        self.send(accounts, data, &[seller]).await
+    }
+
+    // Claims the tokens a lockup held back, paid out in USDC instead once the
+    // project is dissolved
+    pub async fn claim_tokens(
+        &mut self,
+        contributor: &Keypair,
+        usdc_account: Pubkey,
+        token_account: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(contributor.pubkey(), true),
+            AccountMeta::new(self.record(&contributor.pubkey()), false),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(self.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new(usdc_account, false),
+        ];
+        self.send(accounts, vec![13], &[contributor]).await
+    }
+
+    pub async fn refund(
+        &mut self,
+        contributor: &Keypair,
+        usdc_account: Pubkey,
+        token_account: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(contributor.pubkey(), true),
+            AccountMeta::new(self.record(&contributor.pubkey()), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new(usdc_account, false),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(self.token_mint, false),
+        ];
+        self.send(accounts, vec![14], &[contributor]).await
+    }
+
+    pub fn vesting(&self, beneficiary: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"vesting", self.project.as_ref(), beneficiary.as_ref()],
+            &self.program_id,
+        )
+        .0
+    }
+
+    // Grants `beneficiary` a revocable schedule vesting `amount` tokens over
+    // `start`..`end` after `cliff`
+    pub async fn create_vesting(
+        &mut self,
+        beneficiary: &Pubkey,
+        amount: u64,
+        start: i64,
+        cliff: i64,
+        end: i64,
+    ) -> Result<(), BanksClientError> {
+        let mut data = vec![9];
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&start.to_le_bytes());
+        data.extend_from_slice(&cliff.to_le_bytes());
+        data.extend_from_slice(&end.to_le_bytes());
+        data.push(1);
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(self.vesting(beneficiary), false),
+            AccountMeta::new(self.ctx.payer.pubkey(), true),
+            AccountMeta::new_readonly(*beneficiary, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ];
+        self.send(accounts, data, &[]).await
+    }
+
+    pub async fn claim_vested(
+        &mut self,
+        beneficiary: &Keypair,
+        token_account: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(self.vesting(&beneficiary.pubkey()), false),
+            AccountMeta::new_readonly(beneficiary.pubkey(), true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(self.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ];
+        self.send(accounts, vec![10], &[beneficiary]).await
+    }
+
+    pub async fn revoke_vesting(&mut self, beneficiary: &Pubkey) -> Result<(), BanksClientError> {
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(self.vesting(beneficiary), false),
+            AccountMeta::new(self.ctx.payer.pubkey(), true),
+        ];
+        self.send(accounts, vec![11], &[]).await
+    }
+
+    pub fn escrow(&self, holder: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"escrow", self.project.as_ref(), holder.as_ref()],
+            &self.program_id,
+        )
+        .0
+    }
+
+    pub fn escrow_vault(&self) -> Pubkey {
+        Pubkey::find_program_address(&[b"escrow_vault", self.project.as_ref()], &self.program_id).0
+    }
+
+    pub async fn deposit_escrow(
+        &mut self,
+        holder: &Keypair,
+        token_account: Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut data = vec![45];
+        data.extend_from_slice(&amount.to_le_bytes());
+        let accounts = vec![
+            AccountMeta::new_readonly(self.project, false),
+            AccountMeta::new(holder.pubkey(), true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(self.escrow(&holder.pubkey()), false),
+            AccountMeta::new(self.escrow_vault(), false),
+            AccountMeta::new_readonly(self.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ];
+        self.send(accounts, data, &[holder]).await
+    }
+
+    pub async fn withdraw_escrow(
+        &mut self,
+        holder: &Keypair,
+        token_account: Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut data = vec![46];
+        data.extend_from_slice(&amount.to_le_bytes());
+        let accounts = vec![
+            AccountMeta::new_readonly(self.project, false),
+            AccountMeta::new_readonly(holder.pubkey(), true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(self.escrow(&holder.pubkey()), false),
+            AccountMeta::new(self.escrow_vault(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ];
+        self.send(accounts, data, &[holder]).await
+    }
+
+    pub fn milestone(&self, index: u8) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"milestone", self.project.as_ref(), &[index]],
+            &self.program_id,
+        )
+        .0
+    }
+
+    pub fn proposal(&self, index: u8) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"proposal", self.project.as_ref(), &[index]],
+            &self.program_id,
+        )
+        .0
+    }
+
+    // Adds the next milestone, due at `deadline` (never if zero) and paid in
+    // `tranche_count` tranches `tranche_interval` seconds apart
+    pub async fn add_milestone(
+        &mut self,
+        amount: u64,
+        deadline: i64,
+        tranche_count: u8,
+        tranche_interval: i64,
+    ) -> Result<(), BanksClientError> {
+        let index = self.project_state().await.milestone_count;
+        let mut data = vec![7];
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(&[0; 24]);
+        data.extend_from_slice(b"M0");
+        data.extend_from_slice(b"Ship");
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&deadline.to_le_bytes());
+        data.push(tranche_count);
+        data.extend_from_slice(&tranche_interval.to_le_bytes());
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(self.milestone(index), false),
+            AccountMeta::new(self.ctx.payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ];
+        self.send(accounts, data, &[]).await
+    }
+
+    // Marks milestone `milestone_id` missed if it is past its deadline
+    pub async fn check_deadline(&mut self, milestone_id: u8) -> Result<(), BanksClientError> {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.project, false),
+            AccountMeta::new(self.milestone(milestone_id), false),
+        ];
+        self.send(accounts, vec![41, milestone_id], &[]).await
+    }
+
+    pub fn evidence(&self, milestone_id: u8, version: u8) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"evidence",
+                self.project.as_ref(),
+                &[milestone_id],
+                &[version],
+            ],
+            &self.program_id,
+        )
+        .0
+    }
+
+    // Submits the next evidence version for milestone `milestone_id` as the
+    // authority
+    pub async fn submit_evidence(
+        &mut self,
+        milestone_id: u8,
+        content_hash: [u8; 32],
+        uri: &str,
+    ) -> Result<(), BanksClientError> {
+        let version = self.milestone_state(milestone_id).await.evidence_version + 1;
+        let mut data = vec![42, milestone_id];
+        data.extend_from_slice(&content_hash);
+        data.extend_from_slice(&(uri.len() as u32).to_le_bytes());
+        data.extend_from_slice(uri.as_bytes());
+        let accounts = vec![
+            AccountMeta::new_readonly(self.project, false),
+            AccountMeta::new(self.milestone(milestone_id), false),
+            AccountMeta::new(self.evidence(milestone_id, version), false),
+            AccountMeta::new(self.ctx.payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ];
+        self.send(accounts, data, &[]).await
+    }
+
+    // Proposes releasing milestone `milestone_id` as the authority, or as a
+    // holder posting the deposit from their USDC account
+    pub async fn propose(
+        &mut self,
+        milestone_id: u8,
+        holder: Option<(&Keypair, Pubkey)>,
+        extra_accounts: &[AccountMeta],
+    ) -> Result<(), BanksClientError> {
+        let index = self.project_state().await.proposal_count;
+        let mut data = vec![4];
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(b"P0");
+        data.extend_from_slice(b"Pay!");
+        data.push(milestone_id);
+        let proposer = holder.map_or(self.ctx.payer.pubkey(), |(holder, _)| holder.pubkey());
+        let mut accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(self.proposal(index), false),
+            AccountMeta::new(proposer, true),
+            AccountMeta::new(self.milestone(milestone_id), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(self.token_mint, false),
+        ];
+        let mut signers = Vec::new();
+        if let Some((holder, usdc_account)) = holder {
+            accounts.push(AccountMeta::new(self.escrow(&proposer), false));
+            accounts.push(AccountMeta::new(self.vault, false));
+            accounts.push(AccountMeta::new(usdc_account, false));
+            // The deposit transfer invokes the token program
+            accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+            signers.push(holder);
+        }
+        accounts.extend_from_slice(extra_accounts);
+        self.send(accounts, data, &signers).await
+    }
+
+    // Adds milestone 0 paying `amount` and proposes it, as the authority or as
+    // a holder posting the deposit from their USDC account
+    pub async fn propose_milestone(
+        &mut self,
+        amount: u64,
+        holder: Option<(&Keypair, Pubkey)>,
+    ) -> (Pubkey, Pubkey) {
+        self.add_milestone(amount, 0, 1, 0).await.unwrap();
+        self.propose(0, holder, &[]).await.unwrap();
+        (self.milestone(0), self.proposal(0))
+    }
+
+    pub async fn vote(
+        &mut self,
+        voter: &Keypair,
+        proposal_id: u8,
+        choice: u8,
+    ) -> Result<(), BanksClientError> {
+        let proposal = self.proposal(proposal_id);
+        let mut data = vec![5];
+        data.extend_from_slice(&(proposal_id as u64).to_le_bytes());
+        data.push(choice);
+        let accounts = vec![
+            AccountMeta::new_readonly(self.project, false),
+            AccountMeta::new(proposal, false),
+            AccountMeta::new(voter.pubkey(), true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(self.escrow(&voter.pubkey()), false),
+            AccountMeta::new(self.vote_record(proposal_id, &voter.pubkey()), false),
+        ];
+        self.send(accounts, data, &[voter]).await
+    }
+
+    pub fn veto_record(&self, proposal_id: u8, voter: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"veto", self.proposal(proposal_id).as_ref(), voter.as_ref()],
+            &self.program_id,
+        )
+        .0
+    }
+
+    // Vetoes passed proposal `proposal_id` with the voter's escrowed tokens
+    pub async fn veto(&mut self, voter: &Keypair, proposal_id: u8) -> Result<(), BanksClientError> {
+        let mut data = vec![29];
+        data.extend_from_slice(&(proposal_id as u64).to_le_bytes());
+        let accounts = vec![
+            AccountMeta::new_readonly(self.project, false),
+            AccountMeta::new(self.proposal(proposal_id), false),
+            AccountMeta::new(voter.pubkey(), true),
+            AccountMeta::new(self.escrow(&voter.pubkey()), false),
+            AccountMeta::new_readonly(self.token_mint, false),
+            AccountMeta::new(self.veto_record(proposal_id, &voter.pubkey()), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ];
+        self.send(accounts, data, &[voter]).await
+    }
+
+    // Commits the voter's escrowed weight to a hidden `choice` on proposal
+    // `proposal_id`
+    pub async fn commit_vote(
+        &mut self,
+        voter: &Keypair,
+        proposal_id: u8,
+        choice: u8,
+        salt: [u8; 32],
+    ) -> Result<(), BanksClientError> {
+        let commitment = keccak::hashv(&[&[choice], &salt, voter.pubkey().as_ref()]);
+        let mut data = vec![36];
+        data.extend_from_slice(&(proposal_id as u64).to_le_bytes());
+        data.extend_from_slice(&commitment.0);
+        let accounts = vec![
+            AccountMeta::new_readonly(self.project, false),
+            AccountMeta::new(self.proposal(proposal_id), false),
+            AccountMeta::new(voter.pubkey(), true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(self.escrow(&voter.pubkey()), false),
+            AccountMeta::new(self.vote_record(proposal_id, &voter.pubkey()), false),
+        ];
+        self.send(accounts, data, &[voter]).await
+    }
+
+    pub async fn reveal_vote(
+        &mut self,
+        voter: &Keypair,
+        proposal_id: u8,
+        choice: u8,
+        salt: [u8; 32],
+    ) -> Result<(), BanksClientError> {
+        let mut data = vec![37];
+        data.extend_from_slice(&(proposal_id as u64).to_le_bytes());
+        data.push(choice);
+        data.extend_from_slice(&salt);
+        let accounts = vec![
+            AccountMeta::new_readonly(self.project, false),
+            AccountMeta::new(self.proposal(proposal_id), false),
+            AccountMeta::new(voter.pubkey(), true),
+            AccountMeta::new(self.vote_record(proposal_id, &voter.pubkey()), false),
+        ];
+        self.send(accounts, data, &[voter]).await
+    }
+
+    // Moves the voter's vote on proposal `proposal_id` to `choice`, or
+    // withdraws it if None
+    pub async fn revise_vote(
+        &mut self,
+        voter: &Keypair,
+        proposal_id: u8,
+        choice: Option<u8>,
+    ) -> Result<(), BanksClientError> {
+        let mut data = vec![if choice.is_some() { 32 } else { 33 }];
+        data.extend_from_slice(&(proposal_id as u64).to_le_bytes());
+        data.extend(choice);
+        let accounts = vec![
+            AccountMeta::new_readonly(self.project, false),
+            AccountMeta::new(self.proposal(proposal_id), false),
+            AccountMeta::new(voter.pubkey(), true),
+            AccountMeta::new(self.vote_record(proposal_id, &voter.pubkey()), false),
+        ];
+        self.send(accounts, data, &[voter]).await
+    }
+
+    pub fn vote_record(&self, proposal_id: u8, voter: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"vote", self.proposal(proposal_id).as_ref(), voter.as_ref()],
+            &self.program_id,
+        )
+        .0
+    }
+
+    // Escrows all of the voter's tokens and votes yes on proposal
+    // `proposal_id`, then waits out the vote
+    pub async fn pass(&mut self, voter: &Keypair, token_account: Pubkey, proposal_id: u8) {
+        let tokens = self.token_balance(token_account).await;
+        if tokens > 0 {
+            self.deposit_escrow(voter, token_account, tokens)
+                .await
+                .unwrap();
+        }
+        self.vote(voter, proposal_id, VOTE_YES).await.unwrap();
+        self.warp(181).await;
+    }
+
+    // Cancels proposal `proposal_id` as the guardian, which the authority is
+    // when launched with `authority_guards`
+    pub async fn cancel(&mut self, proposal_id: u8) -> Result<(), BanksClientError> {
+        let mut data = vec![28];
+        data.extend_from_slice(&(proposal_id as u64).to_le_bytes());
+        let accounts = vec![
+            AccountMeta::new_readonly(self.project, false),
+            AccountMeta::new(self.proposal(proposal_id), false),
+            AccountMeta::new_readonly(self.ctx.payer.pubkey(), true),
+        ];
+        self.send(accounts, data, &[]).await
+    }
+
+    // Proposes choosing between `options` as the authority
+    pub async fn propose_choice(&mut self, options: &[&str]) -> Result<(), BanksClientError> {
+        let index = self.project_state().await.proposal_count;
+        let mut data = vec![34];
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(b"P0");
+        data.extend_from_slice(b"Pick");
+        data.push(options.len() as u8);
+        for option in options {
+            data.extend_from_slice(&(option.len() as u32).to_le_bytes());
+            data.extend_from_slice(option.as_bytes());
+        }
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(self.proposal(index), false),
+            AccountMeta::new(self.ctx.payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(self.token_mint, false),
+        ];
+        self.send(accounts, data, &[]).await
+    }
+
+    // Proposes `action` as the authority
+    pub async fn propose_action(&mut self, action: ProposalAction) -> Result<(), BanksClientError> {
+        let index = self.project_state().await.proposal_count;
+        let mut data = vec![38];
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(b"P0");
+        data.extend_from_slice(b"Act!");
+        let mut action_data = [0; ProposalAction::LEN];
+        action.pack(&mut action_data);
+        data.extend_from_slice(&action_data);
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(self.proposal(index), false),
+            AccountMeta::new(self.ctx.payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(self.token_mint, false),
+        ];
+        self.send(accounts, data, &[]).await
+    }
+
+    // Executes passed action proposal `proposal_id` with the accounts its
+    // action needs
+    pub async fn execute(
+        &mut self,
+        proposal_id: u8,
+        action_accounts: &[AccountMeta],
+    ) -> Result<(), BanksClientError> {
+        let mut data = vec![35];
+        data.extend_from_slice(&(proposal_id as u64).to_le_bytes());
+        let mut accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(self.proposal(proposal_id), false),
+        ];
+        accounts.extend_from_slice(action_accounts);
+        self.send(accounts, data, &[]).await
+    }
+
+    // Dissolves the project on `voter`'s vote with all of their tokens, which
+    // are handed back afterwards
+    pub async fn dissolve(&mut self, voter: &Keypair, token_account: Pubkey) {
+        let index = self.project_state().await.proposal_count;
+        let tokens = self.token_balance(token_account).await;
+        self.propose_action(ProposalAction::Dissolve).await.unwrap();
+        self.pass(voter, token_account, index).await;
+        let accounts = [
+            AccountMeta::new_readonly(self.vault, false),
+            AccountMeta::new_readonly(self.token_mint, false),
+        ];
+        self.execute(index, &accounts).await.unwrap();
+        self.withdraw_escrow(voter, token_account, tokens)
+            .await
+            .unwrap();
+    }
+
+    // Burns `amount` of a dissolved project's tokens for USDC
+    pub async fn redeem(
+        &mut self,
+        holder: &Keypair,
+        usdc_account: Pubkey,
+        token_account: Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut data = vec![40];
+        data.extend_from_slice(&amount.to_le_bytes());
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(holder.pubkey(), true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(self.token_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new(usdc_account, false),
+        ];
+        self.send(accounts, data, &[holder]).await
+    }
+
+    pub async fn complete(
+        &mut self,
+        milestone_id: u8,
+        proposal_id: u8,
+    ) -> Result<(), BanksClientError> {
+        let accounts = vec![
+            AccountMeta::new_readonly(self.project, false),
+            AccountMeta::new(self.milestone(milestone_id), false),
+            AccountMeta::new_readonly(self.ctx.payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(self.proposal(proposal_id), false),
+        ];
+        self.send(accounts, vec![8, milestone_id], &[]).await
+    }
+
+    // Releases the unlocked funds of proposal `proposal_id` to the authority's
+    // USDC account
+    pub async fn release(
+        &mut self,
+        proposal_id: u8,
+        recipient: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let milestone_id = self.proposal_state(proposal_id).await.milestone_id;
+        let mut data = vec![6];
+        data.extend_from_slice(&(proposal_id as u64).to_le_bytes());
+        let accounts = vec![
+            AccountMeta::new(self.project, false),
+            AccountMeta::new(self.proposal(proposal_id), false),
+            AccountMeta::new(self.ctx.payer.pubkey(), true),
+            AccountMeta::new(self.milestone(milestone_id), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ];
+        self.send(accounts, data, &[]).await
+    }
+
+    pub async fn account_data(&mut self, key: Pubkey) -> Vec<u8> {
+        self.ctx
+            .banks_client
+            .get_account(key)
+            .await
+            .unwrap()
+            .unwrap()
+            .data
+    }
+
+    pub async fn project_state(&mut self) -> Project {
+        Project::unpack(&self.account_data(self.project).await).unwrap()
+    }
+
+    // Overwrites the project account with `project`, for states that would
+    // take too many transactions to reach
+    pub async fn set_project_state(&mut self, project: &Project) {
+        let mut account = self
+            .ctx
+            .banks_client
+            .get_account(self.project)
+            .await
+            .unwrap()
+            .unwrap();
+        project.pack(&mut account.data);
+        self.ctx.set_account(&self.project, &account.into());
+    }
+
+    pub async fn milestone_state(&mut self, index: u8) -> Milestone {
+        Milestone::unpack(&self.account_data(self.milestone(index)).await).unwrap()
+    }
+
+    pub async fn evidence_state(&mut self, milestone_id: u8, version: u8) -> MilestoneEvidence {
+        let evidence = self.evidence(milestone_id, version);
+        MilestoneEvidence::unpack(&self.account_data(evidence).await).unwrap()
+    }
+
+    pub async fn proposal_state(&mut self, index: u8) -> Proposal {
+        Proposal::unpack(&self.account_data(self.proposal(index)).await).unwrap()
+    }
+
+    pub async fn warp(&mut self, seconds: i64) {
+        let mut clock: Clock = self.ctx.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    pub async fn token_balance(&mut self, account: Pubkey) -> u64 {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(account)
+            .await
+            .unwrap()
+            .unwrap();
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+}
+
+pub async fn send(
+    ctx: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = ctx.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&ctx.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&ctx.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    ctx.banks_client.process_transaction(transaction).await
+}
+
+pub async fn create_mint(
+    ctx: &mut ProgramTestContext,
+    mint: &Keypair,
+    authority: &Pubkey,
+    decimals: u8,
+) {
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &ctx.payer.pubkey(),
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_mint2(
+            &spl_token::id(),
+            &mint.pubkey(),
+            authority,
+            None,
+            decimals,
+        )
+        .unwrap(),
+    ];
+    send(ctx, &instructions, &[mint]).await.unwrap();
+}
+
+pub async fn create_token_account(
+    ctx: &mut ProgramTestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Pubkey {
+    let account = Keypair::new();
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &ctx.payer.pubkey(),
+            &account.pubkey(),
+            rent.minimum_balance(spl_token::state::Account::LEN),
+            spl_token::state::Account::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_account3(
+            &spl_token::id(),
+            &account.pubkey(),
+            mint,
+            owner,
+        )
+        .unwrap(),
+    ];
+    send(ctx, &instructions, &[&account]).await.unwrap();
+    account.pubkey()
+}
+
+// Asserts the transaction failed with `error` from the program
+pub fn assert_error(result: Result<(), BanksClientError>, error: UnicornFactoryError) {
+    let code = error as u32;
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(found),
+        ))) => assert_eq!(found, code, "expected program error {}", code),
+        other => panic!("expected program error {}, got {:?}", code, other),
+    }
+}
@@ -0,0 +1,198 @@
+// Program tests for dissolving a project and paying its treasury out to
+// holders and to buyers still owed their tokens.
+
+mod common;
+
+use common::{assert_error, Env, Launch, FUNDING_GOAL};
+use solana_sdk::instruction::AccountMeta;
+use unicorn_factory::{ProposalAction, UnicornFactoryError};
+
+#[tokio::test]
+async fn dissolved_treasury_redeems_at_the_snapshot_rate() {
+    let mut env = Env::new(0).await;
+    let (holder, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    let (other, other_usdc, other_tokens) = env.wallet(FUNDING_GOAL).await;
+
+    env.contribute(&holder, usdc_account, token_account, 600_000)
+        .await
+        .unwrap();
+    let held = env.token_balance(token_account).await;
+    env.propose_action(ProposalAction::Dissolve).await.unwrap();
+    env.pass(&holder, token_account, 0).await;
+
+    // Until the raise succeeds its buyers are owed refunds instead
+    let accounts = [
+        AccountMeta::new_readonly(env.vault, false),
+        AccountMeta::new_readonly(env.token_mint, false),
+    ];
+    assert_error(
+        env.execute(0, &accounts).await,
+        UnicornFactoryError::RaiseNotSucceeded,
+    );
+
+    env.contribute(&other, other_usdc, other_tokens, 400_000)
+        .await
+        .unwrap();
+    env.execute(0, &accounts).await.unwrap();
+    env.withdraw_escrow(&holder, token_account, held)
+        .await
+        .unwrap();
+    let other_held = env.token_balance(other_tokens).await;
+    let supply = held + other_held;
+
+    // Each holder burns their tokens for their share of the snapshot
+    for (wallet, usdc, tokens, amount) in [
+        (&holder, usdc_account, token_account, held),
+        (&other, other_usdc, other_tokens, other_held),
+    ] {
+        let before = env.token_balance(usdc).await;
+        env.redeem(wallet, usdc, tokens, amount).await.unwrap();
+        assert_eq!(
+            env.token_balance(usdc).await - before,
+            amount * FUNDING_GOAL / supply
+        );
+    }
+    assert!(env.token_balance(env.vault).await <= 1);
+
+    // Nothing can be bought into a dissolved project
+    assert!(env
+        .buy(&other, other_usdc, other_tokens, 1_000, &[])
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn locked_up_buyers_are_paid_for_unclaimed_tokens() {
+    let mut env = Env::launch(Launch {
+        lockup: true,
+        ..Launch::default()
+    })
+    .await;
+    let (holder, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    let (other, other_usdc, other_tokens) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&holder, usdc_account, token_account, 600_000)
+        .await
+        .unwrap();
+    env.contribute(&other, other_usdc, other_tokens, 400_000)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(token_account).await, 0);
+    env.claim_tokens(&holder, usdc_account, token_account)
+        .await
+        .unwrap();
+    let held = env.token_balance(token_account).await;
+    let owed = env.project_state().await.unclaimed_tokens;
+
+    // The unclaimed tokens count towards the supply the treasury is split by
+    env.dissolve(&holder, token_account).await;
+    let project = env.project_state().await;
+    assert_eq!(project.dissolved_supply, held + owed);
+    assert_eq!(project.dissolved_treasury, FUNDING_GOAL);
+
+    // and are paid for in USDC rather than minted
+    env.claim_tokens(&other, other_usdc, other_tokens)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(other_tokens).await, 0);
+    assert_eq!(
+        env.token_balance(other_usdc).await,
+        FUNDING_GOAL - 400_000 + owed * FUNDING_GOAL / (held + owed)
+    );
+    assert!(env
+        .claim_tokens(&other, other_usdc, other_tokens)
+        .await
+        .is_err());
+
+    env.redeem(&holder, usdc_account, token_account, held)
+        .await
+        .unwrap();
+    assert!(env.token_balance(env.vault).await <= 1);
+}
+
+#[tokio::test]
+async fn unsettled_bids_get_their_refund_and_their_share() {
+    let mut env = Env::launch(Launch {
+        dutch_auction: Some((10, 2, 100, 200_000)),
+        ..Launch::default()
+    })
+    .await;
+    let (early, early_usdc, early_tokens) = env.wallet(FUNDING_GOAL).await;
+    let (late, late_usdc, late_tokens) = env.wallet(FUNDING_GOAL).await;
+
+    // The early bid locks in 100k tokens at 10 and the late one 90k at 6
+    env.contribute(&early, early_usdc, early_tokens, FUNDING_GOAL)
+        .await
+        .unwrap();
+    env.warp(50).await;
+    env.contribute(&late, late_usdc, late_tokens, 540_000)
+        .await
+        .unwrap();
+    env.warp(100).await;
+    env.finalize().await.unwrap();
+    env.settle_bid(&late, late_usdc, late_tokens).await.unwrap();
+    assert_eq!(env.token_balance(late_tokens).await, 90_000);
+
+    // The early bid is owed 400k back beside its tokens, which stays out of
+    // the treasury the supply shares
+    env.dissolve(&late, late_tokens).await;
+    let project = env.project_state().await;
+    assert_eq!(project.dissolved_supply, 190_000);
+    assert_eq!(project.dissolved_treasury, 1_140_000);
+
+    env.settle_bid(&early, early_usdc, early_tokens)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(early_tokens).await, 0);
+    assert_eq!(env.token_balance(early_usdc).await, FUNDING_GOAL);
+
+    env.redeem(&late, late_usdc, late_tokens, 90_000)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(late_usdc).await, FUNDING_GOAL);
+    assert_eq!(env.token_balance(env.vault).await, 0);
+}
+
+#[tokio::test]
+async fn unclaimed_commitments_are_paid_at_the_redemption_rate() {
+    let mut env = Env::launch(Launch {
+        batch_auction: Some((10, 100, 100_000)),
+        ..Launch::default()
+    })
+    .await;
+    let (first, first_usdc, first_tokens) = env.wallet(FUNDING_GOAL).await;
+    let (second, second_usdc, second_tokens) = env.wallet(FUNDING_GOAL).await;
+    env.commit(&first, first_usdc, 600_005).await.unwrap();
+    env.commit(&second, second_usdc, 500_003).await.unwrap();
+    env.warp(101).await;
+    env.settle().await.unwrap();
+
+    // 1_100_008 clears at 12; the first commitment gets 50k tokens and 5 back
+    env.claim_commitment(&first, first_usdc, first_tokens)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(first_tokens).await, 50_000);
+
+    // The second counts as the 41_666.9 tokens its 500_003 buys at 12
+    env.dissolve(&first, first_tokens).await;
+    let project = env.project_state().await;
+    assert_eq!(project.dissolved_supply, 91_667);
+    assert_eq!(project.dissolved_treasury, 1_100_003);
+
+    env.claim_commitment(&second, second_usdc, second_tokens)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(second_tokens).await, 0);
+    assert_eq!(
+        env.token_balance(second_usdc).await,
+        FUNDING_GOAL - 500_003 + 500_002
+    );
+
+    env.redeem(&first, first_usdc, first_tokens, 50_000)
+        .await
+        .unwrap();
+    assert_eq!(
+        env.token_balance(first_usdc).await,
+        FUNDING_GOAL - 600_000 + 599_999
+    );
+    assert_eq!(env.token_balance(env.vault).await, 2);
+}
@@ -0,0 +1,455 @@
+// Program tests for proposals, voting and the timelock between a vote and its
+// execution.
+
+mod common;
+
+use common::{assert_error, Env, Launch, FUNDING_GOAL};
+use solana_sdk::instruction::AccountMeta;
+use unicorn_factory::{
+    MilestoneStatus, ProposalAction, UnicornFactoryError, AUTHORITY_PROPOSAL_SLOTS, VOTE_ABSTAIN,
+    VOTE_NO, VOTE_YES,
+};
+
+#[tokio::test]
+async fn holders_propose_milestones_and_lose_the_deposit_if_cancelled() {
+    let mut env = Env::launch(Launch {
+        authority_guards: true,
+        proposal_threshold: 100_000,
+        proposal_deposit: 50_000,
+        ..Launch::default()
+    })
+    .await;
+    let (holder, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&holder, usdc_account, token_account, 500_000)
+        .await
+        .unwrap();
+    env.deposit_escrow(&holder, token_account, 100_000)
+        .await
+        .unwrap();
+
+    let (_, proposal) = env
+        .propose_milestone(200_000, Some((&holder, usdc_account)))
+        .await;
+    assert_eq!(env.token_balance(usdc_account).await, 450_000);
+
+    // The guardian stops it; with no quorum rule it still "reached quorum"
+    env.cancel(0).await.unwrap();
+
+    let mut data = vec![39];
+    data.extend_from_slice(&0u64.to_le_bytes());
+    let settle = vec![
+        AccountMeta::new(env.project, false),
+        AccountMeta::new(proposal, false),
+        AccountMeta::new(env.vault, false),
+        AccountMeta::new(usdc_account, false),
+    ];
+    env.warp(181).await;
+    env.send(settle, data, &[]).await.unwrap();
+
+    assert_eq!(env.token_balance(usdc_account).await, 450_000);
+    assert_eq!(env.token_balance(env.vault).await, 550_000);
+}
+
+#[tokio::test]
+async fn holders_keep_one_live_proposal_and_leave_the_last_slots_to_the_authority() {
+    let mut env = Env::launch(Launch {
+        proposal_threshold: 100_000,
+        ..Launch::default()
+    })
+    .await;
+    let (holder, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&holder, usdc_account, token_account, 500_000)
+        .await
+        .unwrap();
+    env.deposit_escrow(&holder, token_account, 100_000)
+        .await
+        .unwrap();
+    for _ in 0..3 {
+        env.add_milestone(100_000, 0, 1, 0).await.unwrap();
+    }
+
+    // A second proposal waits for the first one's tally, which also locks the
+    // escrow so the tokens cannot propose again from another wallet
+    env.propose(0, Some((&holder, usdc_account)), &[])
+        .await
+        .unwrap();
+    assert_error(
+        env.propose(1, Some((&holder, usdc_account)), &[]).await,
+        UnicornFactoryError::ProposalLimitReached,
+    );
+    assert_error(
+        env.withdraw_escrow(&holder, token_account, 100_000).await,
+        UnicornFactoryError::EscrowLocked,
+    );
+    env.warp(181).await;
+    env.propose(1, Some((&holder, usdc_account)), &[])
+        .await
+        .unwrap();
+
+    // Once only the authority's slots are left holders cannot take them
+    env.warp(181).await;
+    let mut project = env.project_state().await;
+    project.proposal_count = u8::MAX - AUTHORITY_PROPOSAL_SLOTS;
+    env.set_project_state(&project).await;
+    assert_error(
+        env.propose(2, Some((&holder, usdc_account)), &[]).await,
+        UnicornFactoryError::ProposalLimitReached,
+    );
+    env.propose(2, None, &[]).await.unwrap();
+
+    // and the last index is refused rather than overflowing the count
+    env.add_milestone(100_000, 0, 1, 0).await.unwrap();
+    let mut project = env.project_state().await;
+    project.proposal_count = u8::MAX;
+    env.set_project_state(&project).await;
+    assert_error(
+        env.propose(3, None, &[]).await,
+        UnicornFactoryError::ProposalLimitReached,
+    );
+}
+
+#[tokio::test]
+async fn milestones_are_proposed_again_once_their_proposal_is_dead() {
+    let mut env = Env::launch(Launch {
+        authority_guards: true,
+        ..Launch::default()
+    })
+    .await;
+    let (holder, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&holder, usdc_account, token_account, 500_000)
+        .await
+        .unwrap();
+    env.deposit_escrow(&holder, token_account, 500_000)
+        .await
+        .unwrap();
+    env.propose_milestone(200_000, None).await;
+
+    // A live proposal keeps the milestone
+    let current = [AccountMeta::new_readonly(env.proposal(0), false)];
+    assert_error(
+        env.propose(0, None, &current).await,
+        UnicornFactoryError::MilestoneAlreadyHasProposal,
+    );
+
+    // Voted down, it frees the milestone for a fresh proposal
+    env.vote(&holder, 0, VOTE_NO).await.unwrap();
+    env.warp(181).await;
+    env.propose(0, None, &current).await.unwrap();
+    let milestone = env.milestone_state(0).await;
+    assert_eq!(milestone.status, MilestoneStatus::Proposed);
+    assert_eq!(milestone.proposal_id, 1);
+
+    // as does a cancelled one, which has to be the milestone's current one
+    env.cancel(1).await.unwrap();
+    assert!(env.propose(0, None, &current).await.is_err());
+    let current = [AccountMeta::new_readonly(env.proposal(1), false)];
+    env.propose(0, None, &current).await.unwrap();
+    assert_eq!(env.milestone_state(0).await.proposal_id, 2);
+}
+
+#[tokio::test]
+async fn passed_proposals_wait_out_the_timelock_and_can_be_vetoed_in_it() {
+    let mut env = Env::launch(Launch {
+        execution_delay: 100,
+        execution_window: 50,
+        veto_threshold_bps: 100,
+        ..Launch::default()
+    })
+    .await;
+    let (holder, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    let (dissenter, dissenter_usdc, dissenter_tokens) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&holder, usdc_account, token_account, 600_000)
+        .await
+        .unwrap();
+    env.contribute(&dissenter, dissenter_usdc, dissenter_tokens, 400_000)
+        .await
+        .unwrap();
+    let held = env.token_balance(token_account).await;
+    env.deposit_escrow(&holder, token_account, held)
+        .await
+        .unwrap();
+    let dissent = env.token_balance(dissenter_tokens).await;
+    env.deposit_escrow(&dissenter, dissenter_tokens, dissent)
+        .await
+        .unwrap();
+    for milestone_id in 0..3 {
+        env.add_milestone(200_000, 0, 1, 0).await.unwrap();
+        env.propose(milestone_id, None, &[]).await.unwrap();
+        env.vote(&holder, milestone_id, VOTE_YES).await.unwrap();
+    }
+
+    // Vetoes wait for the vote to pass
+    assert_error(
+        env.veto(&dissenter, 1).await,
+        UnicornFactoryError::VetoWindowClosed,
+    );
+    env.warp(181).await;
+
+    // Passed proposals take no effect during the delay, when holders can
+    // still veto them
+    assert_error(
+        env.complete(0, 0).await,
+        UnicornFactoryError::TimelockActive,
+    );
+    env.veto(&dissenter, 1).await.unwrap();
+    assert!(env.proposal_state(1).await.is_cancelled);
+
+    env.warp(100).await;
+    assert_error(
+        env.veto(&dissenter, 0).await,
+        UnicornFactoryError::VetoWindowClosed,
+    );
+    assert_error(
+        env.complete(1, 1).await,
+        UnicornFactoryError::ProposalCancelled,
+    );
+    env.complete(0, 0).await.unwrap();
+
+    // and lapse once the execution window has passed
+    env.warp(51).await;
+    assert_error(
+        env.complete(2, 2).await,
+        UnicornFactoryError::ProposalExpired,
+    );
+}
+
+#[tokio::test]
+async fn votes_can_be_changed_or_withdrawn_while_voting_is_open() {
+    let mut env = Env::new(0).await;
+    let (holder, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    let (other, _, _) = env.wallet(0).await;
+    env.contribute(&holder, usdc_account, token_account, 500_000)
+        .await
+        .unwrap();
+    env.deposit_escrow(&holder, token_account, 500_000)
+        .await
+        .unwrap();
+    env.propose_milestone(200_000, None).await;
+
+    env.vote(&holder, 0, VOTE_YES).await.unwrap();
+    env.revise_vote(&holder, 0, Some(VOTE_NO)).await.unwrap();
+    let proposal = env.proposal_state(0).await;
+    assert_eq!((proposal.yes_votes, proposal.no_votes), (0, 500_000));
+
+    // Only recorded votes move, and only to choices the proposal has
+    assert_error(
+        env.revise_vote(&other, 0, Some(VOTE_YES)).await,
+        UnicornFactoryError::VoteNotFound,
+    );
+    assert_error(
+        env.revise_vote(&holder, 0, Some(7)).await,
+        UnicornFactoryError::InvalidVoteChoice,
+    );
+
+    // A withdrawn vote leaves the tally and can be cast again
+    env.revise_vote(&holder, 0, None).await.unwrap();
+    let proposal = env.proposal_state(0).await;
+    assert_eq!((proposal.yes_votes, proposal.no_votes), (0, 0));
+    env.vote(&holder, 0, VOTE_YES).await.unwrap();
+
+    env.warp(181).await;
+    assert_error(
+        env.revise_vote(&holder, 0, Some(VOTE_NO)).await,
+        UnicornFactoryError::VotingPeriodEnded,
+    );
+    assert!(env.proposal_state(0).await.passed());
+}
+
+#[tokio::test]
+async fn abstentions_make_quorum_and_options_need_a_single_leader() {
+    let mut env = Env::launch(Launch {
+        quorum_bps: 5_000,
+        ..Launch::default()
+    })
+    .await;
+    let (large, large_usdc, large_tokens) = env.wallet(FUNDING_GOAL).await;
+    let (small, small_usdc, small_tokens) = env.wallet(FUNDING_GOAL).await;
+    for (holder, usdc_account, token_account, amount) in [
+        (&large, large_usdc, large_tokens, 600_000),
+        (&small, small_usdc, small_tokens, 400_000),
+    ] {
+        env.contribute(holder, usdc_account, token_account, amount)
+            .await
+            .unwrap();
+        let tokens = env.token_balance(token_account).await;
+        env.deposit_escrow(holder, token_account, tokens)
+            .await
+            .unwrap();
+    }
+    for milestone_id in 0..2 {
+        env.add_milestone(200_000, 0, 1, 0).await.unwrap();
+        env.propose(milestone_id, None, &[]).await.unwrap();
+    }
+    env.propose_choice(&["Web", "Mobile", "Both"])
+        .await
+        .unwrap();
+    env.propose_choice(&["Now", "Later"]).await.unwrap();
+
+    // The small holder alone is short of half the supply, while an
+    // abstention makes up the quorum without approving anything
+    env.vote(&small, 0, VOTE_YES).await.unwrap();
+    env.vote(&large, 1, VOTE_ABSTAIN).await.unwrap();
+    env.vote(&small, 1, VOTE_YES).await.unwrap();
+
+    // Options are voted by index
+    assert_error(
+        env.vote(&large, 2, 3).await,
+        UnicornFactoryError::InvalidVoteChoice,
+    );
+    env.vote(&large, 2, 2).await.unwrap();
+    env.vote(&small, 2, 0).await.unwrap();
+    env.vote(&large, 3, VOTE_ABSTAIN).await.unwrap();
+    env.vote(&small, 3, VOTE_ABSTAIN).await.unwrap();
+    assert_error(
+        env.execute(2, &[]).await,
+        UnicornFactoryError::VotingPeriodNotEnded,
+    );
+    env.warp(181).await;
+
+    assert_error(
+        env.complete(0, 0).await,
+        UnicornFactoryError::QuorumNotReached,
+    );
+    env.complete(1, 1).await.unwrap();
+
+    env.execute(2, &[]).await.unwrap();
+    let proposal = env.proposal_state(2).await;
+    assert!(proposal.is_executed);
+    assert_eq!(proposal.winning_option, 2);
+    assert_error(
+        env.execute(3, &[]).await,
+        UnicornFactoryError::ProposalDidNotPass,
+    );
+}
+
+#[tokio::test]
+async fn secret_ballots_count_only_what_is_revealed_in_time() {
+    let mut env = Env::launch(Launch {
+        reveal_period: 100,
+        ..Launch::default()
+    })
+    .await;
+    let (large, large_usdc, large_tokens) = env.wallet(FUNDING_GOAL).await;
+    let (small, small_usdc, small_tokens) = env.wallet(FUNDING_GOAL).await;
+    let mut weights = Vec::new();
+    for (holder, usdc_account, token_account, amount) in [
+        (&large, large_usdc, large_tokens, 600_000),
+        (&small, small_usdc, small_tokens, 400_000),
+    ] {
+        env.contribute(holder, usdc_account, token_account, amount)
+            .await
+            .unwrap();
+        let tokens = env.token_balance(token_account).await;
+        env.deposit_escrow(holder, token_account, tokens)
+            .await
+            .unwrap();
+        weights.push(tokens);
+    }
+    env.propose_milestone(200_000, None).await;
+
+    // Ballots are committed, not cast in the open
+    assert_error(
+        env.vote(&large, 0, VOTE_YES).await,
+        UnicornFactoryError::InvalidVoteChoice,
+    );
+    env.commit_vote(&large, 0, VOTE_YES, [1; 32]).await.unwrap();
+    env.commit_vote(&small, 0, VOTE_NO, [2; 32]).await.unwrap();
+    let proposal = env.proposal_state(0).await;
+    assert_eq!(proposal.unrevealed_votes, weights[0] + weights[1]);
+    assert_eq!((proposal.yes_votes, proposal.no_votes), (0, 0));
+    assert_error(
+        env.reveal_vote(&large, 0, VOTE_YES, [1; 32]).await,
+        UnicornFactoryError::RevealPhaseClosed,
+    );
+
+    // and revealed once voting closes, only as committed
+    env.warp(181).await;
+    assert_error(
+        env.reveal_vote(&large, 0, VOTE_YES, [2; 32]).await,
+        UnicornFactoryError::BallotMismatch,
+    );
+    assert_error(
+        env.reveal_vote(&large, 0, VOTE_NO, [1; 32]).await,
+        UnicornFactoryError::BallotMismatch,
+    );
+    env.reveal_vote(&large, 0, VOTE_YES, [1; 32]).await.unwrap();
+    assert_error(
+        env.complete(0, 0).await,
+        UnicornFactoryError::VotingPeriodNotEnded,
+    );
+
+    // Ballots left unrevealed are reported apart from the tally
+    env.warp(100).await;
+    assert_error(
+        env.reveal_vote(&small, 0, VOTE_NO, [2; 32]).await,
+        UnicornFactoryError::RevealPhaseClosed,
+    );
+    let proposal = env.proposal_state(0).await;
+    assert_eq!(proposal.unrevealed_votes, weights[1]);
+    assert_eq!((proposal.yes_votes, proposal.no_votes), (weights[0], 0));
+    env.complete(0, 0).await.unwrap();
+}
+
+#[tokio::test]
+async fn passed_actions_take_effect_through_execute() {
+    let mut env = Env::new(0).await;
+    let (holder, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&holder, usdc_account, token_account, 600_000)
+        .await
+        .unwrap();
+    env.deposit_escrow(&holder, token_account, 600_000)
+        .await
+        .unwrap();
+    env.propose_milestone(200_000, None).await;
+    let actions = [
+        ProposalAction::CancelMilestone { milestone_id: 0 },
+        ProposalAction::TransferTreasury {
+            recipient: usdc_account,
+            amount: 1,
+        },
+        ProposalAction::SetPaused { paused: true },
+    ];
+    for (index, action) in actions.into_iter().enumerate() {
+        env.propose_action(action).await.unwrap();
+        env.vote(&holder, index as u8 + 1, VOTE_YES).await.unwrap();
+    }
+    env.vote(&holder, 0, VOTE_YES).await.unwrap();
+    env.warp(181).await;
+
+    // Milestone proposals are carried out by releasing funds instead
+    assert_error(
+        env.execute(0, &[]).await,
+        UnicornFactoryError::InvalidProposalType,
+    );
+
+    let milestone = [AccountMeta::new(env.milestone(0), false)];
+    env.execute(1, &milestone).await.unwrap();
+    assert_eq!(
+        env.milestone_state(0).await.status,
+        MilestoneStatus::Cancelled
+    );
+    assert_eq!(env.project_state().await.total_allocated, 0);
+    assert_error(
+        env.execute(1, &milestone).await,
+        UnicornFactoryError::ProposalAlreadyExecuted,
+    );
+
+    // Transfers are held to what the treasury may release, which is nothing
+    // while the raise is still open
+    let treasury = [
+        AccountMeta::new(env.vault, false),
+        AccountMeta::new(usdc_account, false),
+    ];
+    assert_error(
+        env.execute(2, &treasury).await,
+        UnicornFactoryError::InsufficientTreasury,
+    );
+
+    env.execute(3, &[]).await.unwrap();
+    assert!(env.project_state().await.is_paused);
+    assert_error(
+        env.contribute(&holder, usdc_account, token_account, 1)
+            .await,
+        UnicornFactoryError::ProjectPaused,
+    );
+}
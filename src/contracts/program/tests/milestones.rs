@@ -0,0 +1,173 @@
+// Program tests for milestone allocation, approval and payout.
+
+mod common;
+
+use common::{assert_error, create_token_account, Env, FUNDING_GOAL};
+use solana_sdk::signature::Signer;
+use unicorn_factory::{MilestoneStatus, UnicornFactoryError, VOTE_YES};
+
+#[tokio::test]
+async fn milestone_allocations_stay_within_the_funding_goal() {
+    let mut env = Env::new(0).await;
+
+    env.add_milestone(600_000, 0, 1, 0).await.unwrap();
+    assert_error(
+        env.add_milestone(FUNDING_GOAL - 600_000 + 1, 0, 1, 0).await,
+        UnicornFactoryError::MilestoneAllocationExceedsGoal,
+    );
+    env.add_milestone(FUNDING_GOAL - 600_000, 0, 1, 0)
+        .await
+        .unwrap();
+
+    let project = env.project_state().await;
+    assert_eq!(project.milestone_count, 2);
+    assert_eq!(project.total_allocated, FUNDING_GOAL);
+}
+
+#[tokio::test]
+async fn releases_are_tracked_against_the_project_and_milestone() {
+    let mut env = Env::new(0).await;
+    let authority = env.ctx.payer.pubkey();
+    let authority_usdc = create_token_account(&mut env.ctx, &env.usdc.clone(), &authority).await;
+    let (holder, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&holder, usdc_account, token_account, FUNDING_GOAL)
+        .await
+        .unwrap();
+
+    env.propose_milestone(600_000, None).await;
+    env.pass(&holder, token_account, 0).await;
+    env.complete(0, 0).await.unwrap();
+    env.release(0, authority_usdc).await.unwrap();
+
+    let project = env.project_state().await;
+    assert_eq!(project.total_released, 600_000);
+    let milestone = env.milestone_state(0).await;
+    assert_eq!(milestone.status, MilestoneStatus::Released);
+    assert_eq!(milestone.released_amount, 600_000);
+
+    // A released milestone pays out only once
+    assert!(env.release(0, authority_usdc).await.is_err());
+    assert_eq!(env.token_balance(authority_usdc).await, 600_000);
+}
+
+#[tokio::test]
+async fn missed_deadlines_need_a_fresh_vote() {
+    let mut env = Env::new(0).await;
+    let authority = env.ctx.payer.pubkey();
+    let authority_usdc = create_token_account(&mut env.ctx, &env.usdc.clone(), &authority).await;
+    let (holder, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&holder, usdc_account, token_account, FUNDING_GOAL)
+        .await
+        .unwrap();
+    let deadline = env.now().await + 100;
+    env.add_milestone(600_000, deadline, 1, 0).await.unwrap();
+    env.propose(0, None, &[]).await.unwrap();
+
+    assert_error(
+        env.check_deadline(0).await,
+        UnicornFactoryError::MilestoneNotOverdue,
+    );
+
+    // The vote passes, but only after the deadline slipped by
+    env.pass(&holder, token_account, 0).await;
+    env.check_deadline(0).await.unwrap();
+    let milestone = env.milestone_state(0).await;
+    assert_eq!(milestone.status, MilestoneStatus::Missed);
+    assert!(milestone.missed_at > deadline);
+    assert_error(
+        env.complete(0, 0).await,
+        UnicornFactoryError::MilestoneMissed,
+    );
+
+    // A fresh proposal supersedes the old one and is not missed again
+    env.propose(0, None, &[]).await.unwrap();
+    env.vote(&holder, 1, VOTE_YES).await.unwrap();
+    env.warp(181).await;
+    assert_error(
+        env.check_deadline(0).await,
+        UnicornFactoryError::MilestoneNotOverdue,
+    );
+    env.complete(0, 1).await.unwrap();
+    assert_error(
+        env.release(0, authority_usdc).await,
+        UnicornFactoryError::MilestoneMissed,
+    );
+    env.release(1, authority_usdc).await.unwrap();
+    assert_eq!(env.token_balance(authority_usdc).await, 600_000);
+}
+
+#[tokio::test]
+async fn approved_milestones_pay_out_one_tranche_at_a_time() {
+    let mut env = Env::new(0).await;
+    let authority = env.ctx.payer.pubkey();
+    let authority_usdc = create_token_account(&mut env.ctx, &env.usdc.clone(), &authority).await;
+    let (holder, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&holder, usdc_account, token_account, FUNDING_GOAL)
+        .await
+        .unwrap();
+
+    // Several tranches need time between them
+    assert_error(
+        env.add_milestone(600_000, 0, 3, 0).await,
+        UnicornFactoryError::InvalidMilestone,
+    );
+    env.add_milestone(600_000, 0, 3, 100).await.unwrap();
+    env.propose(0, None, &[]).await.unwrap();
+    env.pass(&holder, token_account, 0).await;
+    env.complete(0, 0).await.unwrap();
+
+    // The first tranche unlocks on approval and each next one an interval later
+    env.release(0, authority_usdc).await.unwrap();
+    assert_eq!(env.token_balance(authority_usdc).await, 200_000);
+    assert_eq!(env.milestone_state(0).await.released_amount, 200_000);
+    assert_error(
+        env.release(0, authority_usdc).await,
+        UnicornFactoryError::TrancheLocked,
+    );
+
+    // A late release catches up on every tranche unlocked since
+    env.warp(250).await;
+    env.release(0, authority_usdc).await.unwrap();
+    assert_eq!(env.token_balance(authority_usdc).await, 600_000);
+    let milestone = env.milestone_state(0).await;
+    assert_eq!(milestone.status, MilestoneStatus::Released);
+    assert_eq!(milestone.released_amount, 600_000);
+    assert_eq!(env.project_state().await.total_released, 600_000);
+}
+
+#[tokio::test]
+async fn evidence_is_versioned_and_proposals_keep_the_version_they_saw() {
+    let mut env = Env::new(0).await;
+    env.add_milestone(600_000, 0, 1, 0).await.unwrap();
+
+    // Evidence needs a storage URI and a content hash
+    assert_error(
+        env.submit_evidence(0, [1; 32], "ftp://example.com/report")
+            .await,
+        UnicornFactoryError::InvalidEvidence,
+    );
+    assert_error(
+        env.submit_evidence(0, [0; 32], "ipfs://bafyreport").await,
+        UnicornFactoryError::InvalidEvidence,
+    );
+    env.submit_evidence(0, [1; 32], "ipfs://bafyreport")
+        .await
+        .unwrap();
+    env.propose(0, None, &[]).await.unwrap();
+
+    // Amendments add a version and leave the earlier ones in place
+    env.submit_evidence(0, [2; 32], "https://example.com/report")
+        .await
+        .unwrap();
+    let first = env.evidence_state(0, 1).await;
+    assert_eq!(first.uri, "ipfs://bafyreport");
+    assert_eq!(first.content_hash, [1; 32]);
+    let milestone = env.milestone_state(0).await;
+    assert_eq!(milestone.evidence_version, 2);
+    assert_eq!(milestone.evidence_hash, [2; 32]);
+
+    // The proposal still points at what voters were shown
+    let proposal = env.proposal_state(0).await;
+    assert_eq!(proposal.evidence_version, 1);
+    assert_eq!(proposal.evidence_hash, [1; 32]);
+}
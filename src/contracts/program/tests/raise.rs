@@ -0,0 +1,272 @@
+// Program tests for how a raise closes and how a failed one is wound down.
+
+mod common;
+
+use common::{assert_error, create_token_account, Env, FUNDING_GOAL};
+use solana_program::{keccak, pubkey::Pubkey};
+use solana_program_test::BanksClientError;
+use solana_sdk::{
+    instruction::AccountMeta,
+    signature::{Keypair, Signer},
+};
+use unicorn_factory::{ContributorRecord, UnicornFactoryError};
+
+// Allowlist leaves and nodes hash the way the program's proofs expect
+fn leaf(wallet: &Pubkey) -> [u8; 32] {
+    keccak::hashv(&[wallet.as_ref()]).to_bytes()
+}
+
+fn node(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    keccak::hashv(&[&first, &second]).to_bytes()
+}
+
+// Contributes to the presale round with an allowlist proof
+async fn presale_contribute(
+    env: &mut Env,
+    contributor: &Keypair,
+    usdc_account: Pubkey,
+    token_account: Pubkey,
+    amount: u64,
+    proof: &[[u8; 32]],
+) -> Result<(), BanksClientError> {
+    let mut data = vec![1];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&(proof.len() as u32).to_le_bytes());
+    for sibling in proof {
+        data.extend_from_slice(sibling);
+    }
+    let sale_round =
+        Pubkey::find_program_address(&[b"sale_round", env.project.as_ref()], &env.program_id).0;
+    let accounts = vec![
+        AccountMeta::new(env.project, false),
+        AccountMeta::new(contributor.pubkey(), true),
+        AccountMeta::new(token_account, false),
+        AccountMeta::new(env.token_mint, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new(env.vault, false),
+        AccountMeta::new(env.record(&contributor.pubkey()), false),
+        AccountMeta::new(usdc_account, false),
+        AccountMeta::new(sale_round, false),
+    ];
+    env.send(accounts, data, &[contributor]).await
+}
+
+#[tokio::test]
+async fn refund_returns_usdc_after_a_failed_raise() {
+    let mut env = Env::new(100).await;
+    let (contributor, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&contributor, usdc_account, token_account, 250_000)
+        .await
+        .unwrap();
+
+    env.warp(200).await;
+    env.send(vec![AccountMeta::new(env.project, false)], vec![12], &[])
+        .await
+        .unwrap();
+
+    env.refund(&contributor, usdc_account, token_account)
+        .await
+        .unwrap();
+
+    assert_eq!(env.token_balance(usdc_account).await, FUNDING_GOAL);
+    assert_eq!(env.token_balance(env.vault).await, 0);
+
+    // A second refund is rejected
+    assert!(env
+        .refund(&contributor, usdc_account, token_account)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn refunds_after_curve_sales_share_what_the_vault_holds() {
+    let mut env = Env::new(100).await;
+    let (seller, seller_usdc, seller_tokens) = env.wallet(FUNDING_GOAL).await;
+    let (holder, holder_usdc, holder_tokens) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&seller, seller_usdc, seller_tokens, 250_000)
+        .await
+        .unwrap();
+    env.contribute(&holder, holder_usdc, holder_tokens, 250_000)
+        .await
+        .unwrap();
+    env.sell(&seller, seller_usdc, seller_tokens, 100)
+        .await
+        .unwrap();
+
+    env.warp(200).await;
+    env.send(vec![AccountMeta::new(env.project, false)], vec![12], &[])
+        .await
+        .unwrap();
+
+    // The curve is closed once the raise has failed
+    assert!(env
+        .sell(&seller, seller_usdc, seller_tokens, 100)
+        .await
+        .is_err());
+
+    env.refund(&seller, seller_usdc, seller_tokens)
+        .await
+        .unwrap();
+    env.refund(&holder, holder_usdc, holder_tokens)
+        .await
+        .unwrap();
+
+    assert_eq!(env.token_balance(seller_usdc).await, FUNDING_GOAL);
+    assert_eq!(env.token_balance(holder_usdc).await, FUNDING_GOAL);
+    assert_eq!(env.token_balance(env.vault).await, 0);
+}
+
+#[tokio::test]
+async fn refunds_take_back_the_tokens_delivered_for_them() {
+    let mut env = Env::new(100).await;
+    let (contributor, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&contributor, usdc_account, token_account, 250_000)
+        .await
+        .unwrap();
+    let delivered = env.token_balance(token_account).await;
+    assert!(delivered > 0);
+
+    env.warp(200).await;
+    env.send(vec![AccountMeta::new(env.project, false)], vec![12], &[])
+        .await
+        .unwrap();
+
+    // Tokens moved elsewhere cannot be refunded while they are kept
+    let other = Keypair::new();
+    let elsewhere =
+        create_token_account(&mut env.ctx, &env.token_mint.clone(), &other.pubkey()).await;
+    let transfer = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &token_account,
+        &elsewhere,
+        &contributor.pubkey(),
+        &[],
+        1,
+    )
+    .unwrap();
+    common::send(&mut env.ctx, &[transfer], &[&contributor])
+        .await
+        .unwrap();
+    assert!(env
+        .refund(&contributor, usdc_account, token_account)
+        .await
+        .is_err());
+    assert_eq!(
+        env.token_balance(usdc_account).await,
+        FUNDING_GOAL - 250_000
+    );
+
+    // Returning them allows the refund, which burns every delivered token
+    let transfer = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &elsewhere,
+        &token_account,
+        &other.pubkey(),
+        &[],
+        1,
+    )
+    .unwrap();
+    common::send(&mut env.ctx, &[transfer], &[&other])
+        .await
+        .unwrap();
+    env.refund(&contributor, usdc_account, token_account)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(token_account).await, 0);
+    assert_eq!(env.token_balance(usdc_account).await, FUNDING_GOAL);
+    assert_error(
+        env.refund(&contributor, usdc_account, token_account).await,
+        UnicornFactoryError::AlreadyRefunded,
+    );
+}
+
+#[tokio::test]
+async fn presale_rounds_admit_only_allowlisted_wallets() {
+    let mut env = Env::new(0).await;
+    let (first, first_usdc, first_tokens) = env.wallet(FUNDING_GOAL).await;
+    let (second, second_usdc, second_tokens) = env.wallet(FUNDING_GOAL).await;
+    let (outsider, outsider_usdc, outsider_tokens) = env.wallet(FUNDING_GOAL).await;
+    let root = node(leaf(&first.pubkey()), leaf(&second.pubkey()));
+
+    let now = env.now().await;
+    let mut data = vec![19];
+    data.extend_from_slice(&now.to_le_bytes());
+    data.extend_from_slice(&(now + 100).to_le_bytes());
+    data.extend_from_slice(&10u64.to_le_bytes()); // price
+    data.extend_from_slice(&100_000u64.to_le_bytes()); // per_wallet_cap
+    data.extend_from_slice(&root);
+    let sale_round =
+        Pubkey::find_program_address(&[b"sale_round", env.project.as_ref()], &env.program_id).0;
+    let accounts = vec![
+        AccountMeta::new(env.project, false),
+        AccountMeta::new(sale_round, false),
+        AccountMeta::new(env.ctx.payer.pubkey(), true),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ];
+    env.send(accounts, data, &[]).await.unwrap();
+
+    // Allowlisted wallets buy at the round price with their own proof
+    presale_contribute(
+        &mut env,
+        &first,
+        first_usdc,
+        first_tokens,
+        50_000,
+        &[leaf(&second.pubkey())],
+    )
+    .await
+    .unwrap();
+    presale_contribute(
+        &mut env,
+        &second,
+        second_usdc,
+        second_tokens,
+        50_000,
+        &[leaf(&first.pubkey())],
+    )
+    .await
+    .unwrap();
+    let record =
+        ContributorRecord::unpack(&env.account_data(env.record(&first.pubkey())).await).unwrap();
+    assert_eq!(record.presale_contributed, 50_000);
+    assert_eq!(env.token_balance(first_tokens).await, 5_000);
+
+    // Nobody else gets in, even with a member's proof, and members stay under the cap
+    assert_error(
+        presale_contribute(
+            &mut env,
+            &outsider,
+            outsider_usdc,
+            outsider_tokens,
+            50_000,
+            &[leaf(&second.pubkey())],
+        )
+        .await,
+        UnicornFactoryError::NotAllowlisted,
+    );
+    assert_error(
+        presale_contribute(
+            &mut env,
+            &first,
+            first_usdc,
+            first_tokens,
+            50_001,
+            &[leaf(&second.pubkey())],
+        )
+        .await,
+        UnicornFactoryError::ContributionCapExceeded,
+    );
+
+    // The public curve opens once the round ends
+    assert_error(
+        env.buy(&outsider, outsider_usdc, outsider_tokens, 50_000, &[])
+            .await,
+        UnicornFactoryError::SaleRoundActive,
+    );
+    env.warp(101).await;
+    env.buy(&outsider, outsider_usdc, outsider_tokens, 50_000, &[])
+        .await
+        .unwrap();
+}
@@ -0,0 +1,246 @@
+// Program tests for projects raising in an SPL quote token, using a locally
+// minted mock USDC in place of the real mint.
+
+mod common;
+
+use common::{create_token_account, Env, Launch, FUNDING_GOAL};
+use solana_program::pubkey::Pubkey;
+use solana_sdk::{
+    instruction::AccountMeta,
+    signature::{Keypair, Signer},
+};
+use unicorn_factory::VOTE_YES;
+
+#[tokio::test]
+async fn contribute_moves_usdc_into_the_vault() {
+    let mut env = Env::new(0).await;
+    let (contributor, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+
+    env.contribute(&contributor, usdc_account, token_account, 400_000)
+        .await
+        .unwrap();
+
+    assert_eq!(env.token_balance(env.vault).await, 400_000);
+    assert_eq!(
+        env.token_balance(usdc_account).await,
+        FUNDING_GOAL - 400_000
+    );
+    assert_eq!(env.token_balance(token_account).await, 400_000);
+}
+
+#[tokio::test]
+async fn release_pays_an_approved_milestone_in_usdc() {
+    let mut env = Env::new(0).await;
+    let authority = env.ctx.payer.pubkey();
+    let authority_usdc = create_token_account(&mut env.ctx, &env.usdc.clone(), &authority).await;
+    let (contributor, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&contributor, usdc_account, token_account, FUNDING_GOAL)
+        .await
+        .unwrap();
+
+    let (milestone, proposal) = env.propose_milestone(600_000, None).await;
+
+    // The contributor escrows their tokens and votes yes with them
+    env.deposit_escrow(&contributor, token_account, FUNDING_GOAL)
+        .await
+        .unwrap();
+    env.vote(&contributor, 0, VOTE_YES).await.unwrap();
+
+    env.warp(181).await;
+
+    let mut release = vec![6];
+    release.extend_from_slice(&0u64.to_le_bytes());
+    let release_accounts = vec![
+        AccountMeta::new(env.project, false),
+        AccountMeta::new(proposal, false),
+        AccountMeta::new(authority, true),
+        AccountMeta::new(milestone, false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new(env.vault, false),
+        AccountMeta::new(authority_usdc, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    // Passing the vote alone releases nothing
+    assert!(env
+        .send(release_accounts.clone(), release.clone(), &[])
+        .await
+        .is_err());
+
+    // Complete, then release to the authority's USDC account
+    let accounts = vec![
+        AccountMeta::new_readonly(env.project, false),
+        AccountMeta::new(milestone, false),
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(proposal, false),
+    ];
+    env.send(accounts, vec![8, 0], &[]).await.unwrap();
+    env.send(release_accounts, release, &[]).await.unwrap();
+
+    assert_eq!(env.token_balance(authority_usdc).await, 600_000);
+    assert_eq!(env.token_balance(env.vault).await, FUNDING_GOAL - 600_000);
+}
+
+#[tokio::test]
+async fn escrowed_votes_stay_locked_until_the_tally_closes() {
+    let mut env = Env::new(0).await;
+    let (holder, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&holder, usdc_account, token_account, FUNDING_GOAL)
+        .await
+        .unwrap();
+    env.propose_milestone(600_000, None).await;
+
+    env.deposit_escrow(&holder, token_account, FUNDING_GOAL)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(token_account).await, 0);
+    assert_eq!(env.token_balance(env.escrow_vault()).await, FUNDING_GOAL);
+
+    // Before voting the escrow can move freely
+    env.withdraw_escrow(&holder, token_account, 1)
+        .await
+        .unwrap();
+    env.deposit_escrow(&holder, token_account, 1).await.unwrap();
+
+    // Once counted, the tokens cannot leave to vote again from another wallet
+    env.vote(&holder, 0, VOTE_YES).await.unwrap();
+    assert!(env
+        .withdraw_escrow(&holder, token_account, FUNDING_GOAL)
+        .await
+        .is_err());
+
+    env.warp(181).await;
+    env.withdraw_escrow(&holder, token_account, FUNDING_GOAL)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(token_account).await, FUNDING_GOAL);
+}
+
+#[tokio::test]
+async fn buys_count_toward_the_wallet_cap() {
+    let mut env = Env::launch(Launch {
+        max_contribution_per_wallet: 300_000,
+        ..Launch::default()
+    })
+    .await;
+    let (buyer, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&buyer, usdc_account, token_account, 200_000)
+        .await
+        .unwrap();
+
+    // Buying is held to what the wallet has already contributed
+    assert!(env
+        .buy(&buyer, usdc_account, token_account, 200_000, &[])
+        .await
+        .is_err());
+    env.buy(&buyer, usdc_account, token_account, 100_000, &[])
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(env.vault).await, 300_000);
+}
+
+#[tokio::test]
+async fn funding_rounds_are_scheduled_up_front_and_price_buys() {
+    let mut env = Env::new(0).await;
+    let now = env.now().await;
+    env.add_round(400_000, 4, now + 10, now + 100)
+        .await
+        .unwrap();
+
+    // Rounds may not overlap, start in the past or outgrow the funding goal
+    assert!(env
+        .add_round(100_000, 5, now + 50, now + 150)
+        .await
+        .is_err());
+    assert!(env.add_round(100_000, 5, now - 10, now + 5).await.is_err());
+    assert!(env
+        .add_round(700_000, 5, now + 200, now + 300)
+        .await
+        .is_err());
+    env.add_round(600_000, 5, now + 200, now + 300)
+        .await
+        .unwrap();
+
+    let (buyer, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    let rounds = [env.round(0), env.round(1)];
+    env.warp(20).await;
+    assert!(env.add_round(1, 5, now + 400, now + 500).await.is_err());
+
+    // Buys pay the round's price, not the curve's
+    env.buy(&buyer, usdc_account, token_account, 100_000, &rounds)
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(token_account).await, 25_000);
+
+    // Nothing is sold once every round has closed
+    env.warp(400).await;
+    assert!(env
+        .buy(&buyer, usdc_account, token_account, 100_000, &rounds)
+        .await
+        .is_err());
+}
+
+// CreateCouncil and UpdateCouncil data: threshold, member count, treasury, members
+fn council_data(tag: u8, threshold: u8, members: &[Pubkey]) -> Vec<u8> {
+    let mut data = vec![tag, threshold, members.len() as u8];
+    data.extend_from_slice(Pubkey::new_unique().as_ref()); // treasury
+    for member in members {
+        data.extend_from_slice(member.as_ref());
+    }
+    data
+}
+
+#[tokio::test]
+async fn council_updates_need_the_current_quorum() {
+    let mut env = Env::new(0).await;
+    let (first, second, third) = (Keypair::new(), Keypair::new(), Keypair::new());
+    let (council, _) =
+        Pubkey::find_program_address(&[b"council", env.project.as_ref()], &env.program_id);
+    let accounts = vec![
+        AccountMeta::new(env.project, false),
+        AccountMeta::new(council, false),
+        AccountMeta::new(env.ctx.payer.pubkey(), true),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ];
+    let members = [first.pubkey(), second.pubkey()];
+    env.send(accounts, council_data(25, 2, &members), &[])
+        .await
+        .unwrap();
+
+    let project = env.project;
+    let update = |signers: &[&Keypair]| {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(project, false),
+            AccountMeta::new(council, false),
+        ];
+        for signer in signers {
+            accounts.push(AccountMeta::new_readonly(signer.pubkey(), true));
+        }
+        accounts
+    };
+    let rotated = [first.pubkey(), third.pubkey()];
+
+    // One of two members falls short of the threshold
+    let accounts = update(&[&first]);
+    assert!(env
+        .send(accounts, council_data(44, 2, &rotated), &[&first])
+        .await
+        .is_err());
+
+    let accounts = update(&[&first, &second]);
+    env.send(accounts, council_data(44, 2, &rotated), &[&first, &second])
+        .await
+        .unwrap();
+
+    // The replaced member no longer counts towards the quorum
+    let accounts = update(&[&first, &second]);
+    assert!(env
+        .send(accounts, council_data(44, 1, &members), &[&first, &second])
+        .await
+        .is_err());
+    let accounts = update(&[&first, &third]);
+    env.send(accounts, council_data(44, 1, &members), &[&first, &third])
+        .await
+        .unwrap();
+}
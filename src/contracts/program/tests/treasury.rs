@@ -0,0 +1,94 @@
+// Program tests for the project vault holding raised funds and for the
+// treasury's wind-down.
+
+mod common;
+
+use common::{assert_error, create_token_account, Env, FUNDING_GOAL};
+use solana_program::program_pack::Pack;
+use solana_sdk::{instruction::AccountMeta, signature::Signer};
+use unicorn_factory::UnicornFactoryError;
+
+#[tokio::test]
+async fn the_vault_is_a_token_account_held_by_the_project() {
+    let mut env = Env::new(0).await;
+    let (contributor, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&contributor, usdc_account, token_account, 250_000)
+        .await
+        .unwrap();
+
+    let vault = spl_token::state::Account::unpack(&env.account_data(env.vault).await).unwrap();
+    assert_eq!(vault.owner, env.project);
+    assert_eq!(vault.mint, env.usdc);
+    assert_eq!(vault.amount, 250_000);
+}
+
+#[tokio::test]
+async fn funds_only_move_through_the_project_vault() {
+    let mut env = Env::new(0).await;
+    let authority = env.ctx.payer.pubkey();
+    let authority_usdc = create_token_account(&mut env.ctx, &env.usdc.clone(), &authority).await;
+    let (contributor, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+
+    // Contributions cannot be routed to any other token account
+    let decoy = create_token_account(&mut env.ctx, &env.usdc.clone(), &authority).await;
+    let real_vault = env.vault;
+    env.vault = decoy;
+    assert_error(
+        env.contribute(&contributor, usdc_account, token_account, 250_000)
+            .await,
+        UnicornFactoryError::InvalidVaultAccount,
+    );
+    env.vault = real_vault;
+    env.contribute(&contributor, usdc_account, token_account, FUNDING_GOAL)
+        .await
+        .unwrap();
+
+    // Nor can a release be drawn from one
+    env.propose_milestone(600_000, None).await;
+    env.pass(&contributor, token_account, 0).await;
+    env.complete(0, 0).await.unwrap();
+    let mut data = vec![6];
+    data.extend_from_slice(&0u64.to_le_bytes());
+    let accounts = vec![
+        AccountMeta::new(env.project, false),
+        AccountMeta::new(env.proposal(0), false),
+        AccountMeta::new(authority, true),
+        AccountMeta::new(env.milestone(0), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new(decoy, false),
+        AccountMeta::new(authority_usdc, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    assert_error(
+        env.send(accounts, data, &[]).await,
+        UnicornFactoryError::InvalidVaultAccount,
+    );
+    env.release(0, authority_usdc).await.unwrap();
+    assert_eq!(env.token_balance(authority_usdc).await, 600_000);
+}
+
+#[tokio::test]
+async fn projects_are_only_read_from_program_owned_accounts() {
+    let mut env = Env::new(0).await;
+    let (contributor, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+
+    // A copy of the project under another owner is not a project
+    let mut forged = env
+        .ctx
+        .banks_client
+        .get_account(env.project)
+        .await
+        .unwrap()
+        .unwrap();
+    forged.owner = solana_program::system_program::id();
+    let forged_key = solana_sdk::pubkey::Pubkey::new_unique();
+    env.ctx.set_account(&forged_key, &forged.into());
+    let project = env.project;
+    env.project = forged_key;
+    assert_error(
+        env.contribute(&contributor, usdc_account, token_account, 250_000)
+            .await,
+        UnicornFactoryError::InvalidProjectAccount,
+    );
+    env.project = project;
+}
@@ -0,0 +1,85 @@
+// Program tests for team vesting schedules drawn from the vesting reserve.
+
+mod common;
+
+use common::{assert_error, Env, Launch, FUNDING_GOAL};
+use solana_sdk::signature::Signer;
+use unicorn_factory::UnicornFactoryError;
+
+const TOTAL_SUPPLY: u64 = 10_000_000_000;
+
+#[tokio::test]
+async fn vested_tokens_wait_for_a_successful_raise() {
+    let mut env = Env::launch(Launch {
+        total_supply: TOTAL_SUPPLY,
+        vesting_bps: 2_000,
+        ..Launch::default()
+    })
+    .await;
+    let (insider, insider_usdc, insider_tokens) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&insider, insider_usdc, insider_tokens, 1_000)
+        .await
+        .unwrap();
+    let held = env.token_balance(insider_tokens).await;
+
+    // A schedule cannot start in the past
+    let now = env.now().await;
+    let reserve = TOTAL_SUPPLY / 5;
+    assert_error(
+        env.create_vesting(&insider.pubkey(), reserve, now - 100, now - 100, now - 1)
+            .await,
+        UnicornFactoryError::InvalidVestingSchedule,
+    );
+
+    // A schedule that has fully vested still cannot be claimed, and so sold
+    // into the curve, while the raise is open
+    env.create_vesting(&insider.pubkey(), reserve, now, now, now + 10)
+        .await
+        .unwrap();
+    env.warp(20).await;
+    assert_error(
+        env.claim_vested(&insider, insider_tokens).await,
+        UnicornFactoryError::RaiseNotSucceeded,
+    );
+    assert_eq!(env.token_balance(insider_tokens).await, held);
+
+    let (contributor, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&contributor, usdc_account, token_account, FUNDING_GOAL)
+        .await
+        .unwrap();
+    env.claim_vested(&insider, insider_tokens).await.unwrap();
+    assert_eq!(env.token_balance(insider_tokens).await, held + reserve);
+    assert_eq!(env.project_state().await.vesting_allocated, reserve);
+}
+
+#[tokio::test]
+async fn revoking_returns_the_unvested_share_to_the_reserve() {
+    let mut env = Env::launch(Launch {
+        total_supply: TOTAL_SUPPLY,
+        vesting_bps: 2_000,
+        ..Launch::default()
+    })
+    .await;
+    let (contributor, usdc_account, token_account) = env.wallet(FUNDING_GOAL).await;
+    env.contribute(&contributor, usdc_account, token_account, FUNDING_GOAL)
+        .await
+        .unwrap();
+
+    let now = env.now().await;
+    env.create_vesting(&contributor.pubkey(), 1_000, now, now, now + 100)
+        .await
+        .unwrap();
+    env.warp(25).await;
+    env.revoke_vesting(&contributor.pubkey()).await.unwrap();
+    assert_error(
+        env.revoke_vesting(&contributor.pubkey()).await,
+        UnicornFactoryError::VestingAlreadyRevoked,
+    );
+    let vested = env.project_state().await.vesting_allocated;
+    assert!((250..1_000).contains(&vested));
+
+    let held = env.token_balance(token_account).await;
+    env.warp(1_000).await;
+    env.claim_vested(&contributor, token_account).await.unwrap();
+    assert_eq!(env.token_balance(token_account).await, held + vested);
+}
@@ -2,8 +2,12 @@ import { AnchorProvider } from '@project-serum/anchor';
 import { PublicKey, SystemProgram, TransactionInstruction, Keypair, Transaction, ComputeBudgetProgram } from '@solana/web3.js';
 import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress, createInitializeMintInstruction, MINT_SIZE, createAssociatedTokenAccountInstruction, ASSOCIATED_TOKEN_PROGRAM_ID } from '@solana/spl-token';
 
-// Account sizes (based on Rust program)
-const PROJECT_ACCOUNT_SIZE = 32 + // authority
+// Account layout version written by the program (PROJECT_LAYOUT_VERSION)
+const PROJECT_LAYOUT_VERSION = 1;
+
+// Projects created before the layout was versioned end after proposal_count
+// and must be migrated with migrateProject before the program accepts them
+const LEGACY_PROJECT_ACCOUNT_SIZE = 32 + // authority
   32 + // name (max 32 bytes)
   8 + // symbol (max 8 bytes)
   8 + // funding_goal
@@ -15,6 +19,59 @@ const PROJECT_ACCOUNT_SIZE = 32 + // authority
   1 + // milestone_count
   1; // proposal_count
 
+// Account sizes (based on Rust program)
+const PROJECT_ACCOUNT_SIZE = LEGACY_PROJECT_ACCOUNT_SIZE +
+  1 + // layout_version
+  8 + // total_allocated
+  8 + // total_released
+  1 + // vault_bump
+  32 + // quote_mint
+  8 + // vesting_reserve
+  8 + // vesting_allocated
+  1 + // lockup_enabled
+  8 + // raise_deadline
+  1 + // raise_state
+  2 + // graduation_bps
+  8 + // graduation_token_reserve
+  1 + // is_graduated
+  8 + // sale_round_end
+  8 + // min_contribution
+  8 + // max_contribution_per_wallet
+  1 + // round_count
+  1 + // current_round
+  1 + // launch_mode
+  8 + // auction_start_price
+  8 + // auction_floor_price
+  8 + // auction_start_time
+  8 + // auction_end_time
+  8 + // auction_supply
+  8 + // auction_tokens_sold
+  8 + // clearing_price
+  32 + // creator
+  32 + // pending_authority
+  8 + // governance.execution_delay
+  8 + // governance.execution_window
+  32 + // governance.guardian
+  2 + // governance.veto_threshold_bps
+  2 + // governance.quorum_bps
+  8 + // governance.reveal_period
+  8 + // governance.proposal_threshold
+  8 + // governance.proposal_deposit
+  8 + // curve_base_price
+  8 + // curve_slope
+  1 + // is_paused
+  8 + // held_deposits
+  8 + // slashed_deposits
+  1 + // is_dissolved
+  8 + // total_supply
+  8 + // total_contributed
+  8 + // dissolved_supply
+  8 + // dissolved_treasury
+  8 + // unclaimed_tokens
+  8; // unsettled_deposits
+
+const MAX_PROPOSAL_OPTIONS = 8;
+
 const PROPOSAL_ACCOUNT_SIZE = 32 + // creator
   32 + // title (max 32 bytes)
   256 + // description (max 256 bytes)
@@ -23,13 +80,115 @@ const PROPOSAL_ACCOUNT_SIZE = 32 + // creator
   8 + // no_votes
   1 + // is_executed
   8 + // created_at
-  8; // voting_end
+  8 + // voting_end
+  1 + // is_cancelled
+  8 + // veto_votes
+  8 + // abstain_votes
+  8 + // quorum_votes
+  1 + // option count
+  32 * MAX_PROPOSAL_OPTIONS + // option_labels
+  8 * MAX_PROPOSAL_OPTIONS + // option_votes
+  1 + // winning_option
+  8 + // reveal_end
+  8 + // unrevealed_votes
+  49 + // action
+  8 + // deposit
+  1 + // evidence_version
+  32; // evidence_hash
 
 const MILESTONE_ACCOUNT_SIZE = 32 + // title (max 32 bytes)
   256 + // description (max 256 bytes)
   8 + // amount
-  1 + // is_completed
-  8; // completed_at
+  1 + // status
+  8 + // released_at
+  1 + // proposal_id
+  8 + // deadline
+  8 + // missed_at
+  1 + // tranche_count
+  8 + // tranche_interval
+  8 + // approved_at
+  8 + // released_amount
+  1 + // evidence_version
+  32; // evidence_hash
+
+// Mirrors RaiseState in the Rust program
+export enum RaiseState {
+  Open = 0,
+  Succeeded = 1,
+  Failed = 2,
+}
+
+// Mirrors LaunchMode in the Rust program
+export enum LaunchMode {
+  BondingCurve = 0,
+  DutchAuction = 1,
+  BatchAuction = 2,
+}
+
+// Mirrors MilestoneStatus in the Rust program
+export enum MilestoneStatus {
+  Pending = 0,
+  Proposed = 1,
+  Approved = 2,
+  Released = 3,
+  Missed = 4,
+  Cancelled = 5,
+}
+
+// Launch options passed to initializeProject; omitted fields keep the
+// program's defaults (native SOL, no lockup, deadline, caps or governance rules)
+export interface ProjectConfig {
+  quoteMint: PublicKey;
+  // Token supply the vesting share, auction supply and graduation pool come from
+  totalSupply: number;
+  vestingBps: number;
+  lockupEnabled: boolean;
+  raiseDeadline: number;
+  graduationBps: number;
+  graduationTokenReserve: number;
+  minContribution: number;
+  maxContributionPerWallet: number;
+  launchMode: LaunchMode;
+  auctionStartPrice: number;
+  auctionFloorPrice: number;
+  auctionStartTime: number;
+  auctionEndTime: number;
+  auctionSupply: number;
+  executionDelay: number;
+  executionWindow: number;
+  guardian: PublicKey;
+  vetoThresholdBps: number;
+  quorumBps: number;
+  revealPeriod: number;
+  proposalThreshold: number;
+  proposalDeposit: number;
+}
+
+const DEFAULT_PROJECT_CONFIG: ProjectConfig = {
+  quoteMint: PublicKey.default,
+  totalSupply: 0,
+  vestingBps: 0,
+  lockupEnabled: false,
+  raiseDeadline: 0,
+  graduationBps: 0,
+  graduationTokenReserve: 0,
+  minContribution: 0,
+  maxContributionPerWallet: 0,
+  launchMode: LaunchMode.BondingCurve,
+  auctionStartPrice: 0,
+  auctionFloorPrice: 0,
+  auctionStartTime: 0,
+  auctionEndTime: 0,
+  auctionSupply: 0,
+  executionDelay: 0,
+  executionWindow: 0,
+  guardian: PublicKey.default,
+  vetoThresholdBps: 0,
+  quorumBps: 0,
+  revealPeriod: 0,
+  proposalThreshold: 0,
+  proposalDeposit: 0,
+};
 
 // Temporary interface until we generate the IDL
 interface Project {
@@ -43,6 +202,24 @@ interface Project {
   tokenMintAddress: PublicKey;
   proposalCount: number;
   milestoneCount: number;
+  // 0 for legacy accounts that still need migrateProject
+  layoutVersion: number;
+  totalAllocated: number;
+  totalReleased: number;
+  quoteMint: PublicKey;
+  lockupEnabled: boolean;
+  raiseDeadline: number;
+  raiseState: RaiseState;
+  isGraduated: boolean;
+  minContribution: number;
+  maxContributionPerWallet: number;
+  roundCount: number;
+  currentRound: number;
+  launchMode: LaunchMode;
+  creator: PublicKey;
+  isPaused: boolean;
+  isDissolved: boolean;
+  totalSupply: number;
 }
 
 export interface Proposal {
@@ -55,15 +232,32 @@ export interface Proposal {
   isExecuted: boolean;
   createdAt: number;
   votingEnd: number;
+  isCancelled: boolean;
+  vetoVotes: number;
+  abstainVotes: number;
+  quorumVotes: number;
+  revealEnd: number;
+  deposit: number;
+  evidenceVersion: number;
 }
 
 export interface Milestone {
   title: string;
   description: string;
   amount: number;
+  status: MilestoneStatus;
+  // Approved or released, kept for callers written against the boolean flags
   isCompleted: boolean;
   completedAt: number;
+  // Waiting on its latest proposal
   hasProposal: boolean;
+  releasedAt: number;
+  proposalId: number;
+  deadline: number;
+  trancheCount: number;
+  trancheInterval: number;
+  releasedAmount: number;
+  evidenceVersion: number;
 }
 
 export class UnicornFactoryClient {
@@ -78,8 +272,10 @@ export class UnicornFactoryClient {
   async initializeProject(
     name: string,
     symbol: string,
-    fundingGoal: number
+    fundingGoal: number,
+    options: Partial<ProjectConfig> = {}
   ): Promise<string> {
+    const config: ProjectConfig = { ...DEFAULT_PROJECT_CONFIG, ...options };
     console.log('Starting project initialization...');
     console.log('Provider wallet:', this.provider.wallet.publicKey.toString());
     
@@ -110,8 +306,8 @@ export class UnicornFactoryClient {
       fundingGoal
     });
   
-    // Create instruction data buffer
-    const data = Buffer.alloc(8 + nameBytes.length + symbolBytes.length + 8);
+    // Create instruction data buffer: lengths, strings, funding goal, then config
+    const data = Buffer.alloc(8 + nameBytes.length + symbolBytes.length + 8 + 194);
     let offset = 0;
   
     // Write name length (u32)
@@ -134,6 +330,54 @@ export class UnicornFactoryClient {
     const fundingGoalBuffer = Buffer.alloc(8);
     fundingGoalBuffer.writeBigUInt64LE(BigInt(fundingGoal));
     data.set(fundingGoalBuffer, offset);
+    offset += 8;
+
+    // Write launch config in the order the program reads it
+    data.set(config.quoteMint.toBuffer(), offset);
+    offset += 32;
+    data.writeBigUInt64LE(BigInt(config.totalSupply), offset);
+    offset += 8;
+    data.writeUInt16LE(config.vestingBps, offset);
+    offset += 2;
+    data.writeUInt8(config.lockupEnabled ? 1 : 0, offset);
+    offset += 1;
+    data.writeBigInt64LE(BigInt(config.raiseDeadline), offset);
+    offset += 8;
+    data.writeUInt16LE(config.graduationBps, offset);
+    offset += 2;
+    data.writeBigUInt64LE(BigInt(config.graduationTokenReserve), offset);
+    offset += 8;
+    data.writeBigUInt64LE(BigInt(config.minContribution), offset);
+    offset += 8;
+    data.writeBigUInt64LE(BigInt(config.maxContributionPerWallet), offset);
+    offset += 8;
+    data.writeUInt8(config.launchMode, offset);
+    offset += 1;
+    data.writeBigUInt64LE(BigInt(config.auctionStartPrice), offset);
+    offset += 8;
+    data.writeBigUInt64LE(BigInt(config.auctionFloorPrice), offset);
+    offset += 8;
+    data.writeBigInt64LE(BigInt(config.auctionStartTime), offset);
+    offset += 8;
+    data.writeBigInt64LE(BigInt(config.auctionEndTime), offset);
+    offset += 8;
+    data.writeBigUInt64LE(BigInt(config.auctionSupply), offset);
+    offset += 8;
+    data.writeBigInt64LE(BigInt(config.executionDelay), offset);
+    offset += 8;
+    data.writeBigInt64LE(BigInt(config.executionWindow), offset);
+    offset += 8;
+    data.set(config.guardian.toBuffer(), offset);
+    offset += 32;
+    data.writeUInt16LE(config.vetoThresholdBps, offset);
+    offset += 2;
+    data.writeUInt16LE(config.quorumBps, offset);
+    offset += 2;
+    data.writeBigInt64LE(BigInt(config.revealPeriod), offset);
+    offset += 8;
+    data.writeBigUInt64LE(BigInt(config.proposalThreshold), offset);
+    offset += 8;
+    data.writeBigUInt64LE(BigInt(config.proposalDeposit), offset);
   
     console.log('Instruction data prepared');
     console.log('Sending transaction...');
@@ -168,16 +412,24 @@ export class UnicornFactoryClient {
         null, // freeze authority
       );
   
+      // The vault holds raised funds; SPL raises also pass the quote mint
+      const vaultPda = await this.getVaultPda(projectPda);
+      const keys = [
+        { pubkey: projectPda, isSigner: false, isWritable: true },
+        { pubkey: this.provider.wallet.publicKey, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: tokenMint.publicKey, isSigner: false, isWritable: false },
+        { pubkey: vaultPda, isSigner: false, isWritable: true },
+      ];
+      if (!config.quoteMint.equals(PublicKey.default)) {
+        keys.push({ pubkey: config.quoteMint, isSigner: false, isWritable: false });
+      }
+
       // Create the project initialization instruction
       const initializeProjectInstruction = new TransactionInstruction({
         programId: this.programId,
-        keys: [
-          { pubkey: projectPda, isSigner: false, isWritable: true },
-          { pubkey: this.provider.wallet.publicKey, isSigner: true, isWritable: true },
-          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-          { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-          { pubkey: tokenMint.publicKey, isSigner: false, isWritable: false },
-        ],
+        keys,
         data: Buffer.from([0, ...data]),
       });
   
@@ -206,27 +458,48 @@ export class UnicornFactoryClient {
     }
   }
 
+  // `proof` is the allowlist proof for presale and allowlisted rounds, and
+  // `roundAccounts` the sale round or funding round accounts the raise is in
   async contribute(
     projectPda: PublicKey,
     amount: number,
     contributorTokenAccount: PublicKey,
-    projectToken: PublicKey
+    projectToken: PublicKey,
+    proof: Buffer[] = [],
+    roundAccounts: PublicKey[] = []
   ): Promise<string> {
-    const instruction = {
+    const project = await this.getProject(projectPda);
+    const contributor = this.provider.wallet.publicKey;
+
+    const keys = [
+      { pubkey: projectPda, isSigner: false, isWritable: true },
+      { pubkey: contributor, isSigner: true, isWritable: true },
+      { pubkey: contributorTokenAccount, isSigner: false, isWritable: true },
+      { pubkey: projectToken, isSigner: false, isWritable: true },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: await this.getVaultPda(projectPda), isSigner: false, isWritable: true },
+      { pubkey: await this.getContributorRecordPda(projectPda, contributor), isSigner: false, isWritable: true },
+    ];
+    const quoteAccount = await this.getQuoteAccount(project, contributor);
+    if (quoteAccount) {
+      keys.push({ pubkey: quoteAccount, isSigner: false, isWritable: true });
+    }
+    for (const roundAccount of roundAccounts) {
+      keys.push({ pubkey: roundAccount, isSigner: false, isWritable: true });
+    }
+
+    // Amount (u64), then the proof length (u32) and 32-byte proof nodes
+    const data = Buffer.alloc(8 + 4 + 32 * proof.length);
+    data.writeBigUInt64LE(BigInt(amount), 0);
+    data.writeUInt32LE(proof.length, 8);
+    proof.forEach((node, i) => data.set(node, 12 + 32 * i));
+
+    const instruction = new TransactionInstruction({
       programId: this.programId,
-      keys: [
-        { pubkey: projectPda, isSigner: false, isWritable: true },
-        { pubkey: this.provider.wallet.publicKey, isSigner: true, isWritable: true },
-        { pubkey: contributorTokenAccount, isSigner: false, isWritable: true },
-        { pubkey: projectToken, isSigner: false, isWritable: true },
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-      ],
-      data: Buffer.from([
-        1, // instruction index for contribute
-        ...new Uint8Array(new BigInt64Array([BigInt(amount)]).buffer),
-      ]),
-    };
+      keys,
+      data: Buffer.from([1, ...data]), // 1 is the instruction index for contribute
+    });
 
     const tx = await this.provider.sendAndConfirm(
       new Transaction().add(instruction)
@@ -242,83 +515,26 @@ export class UnicornFactoryClient {
       throw new Error('Project not found');
     }
 
-    // Parse account data according to our Rust struct layout (based on Project::LEN)
-    const data = accountInfo.data;
-    
-    let offset = 0;
-
-    // Parse authority (32 bytes)
-    const authority = new PublicKey(data.slice(offset, offset + 32));
-    offset += 32;
-    
-    // Parse name (32 bytes, potentially zero-padded)
-    const nameBytes = data.slice(offset, offset + 32);
-    const name = new TextDecoder().decode(nameBytes).replace(/\0/g, ''); // Remove null characters
-    offset += 32;
-    
-    // Parse symbol (8 bytes, potentially zero-padded)
-    const symbolBytes = data.slice(offset, offset + 8);
-    const symbol = new TextDecoder().decode(symbolBytes).replace(/\0/g, ''); // Remove null characters
-    offset += 8;
-    
-    // Parse funding goal (8 bytes)
-    const fundingGoal = Number(data.readBigUInt64LE(offset));
-    offset += 8;
-    
-    // Parse total raised (8 bytes)
-    const totalRaised = Number(data.readBigUInt64LE(offset));
-    offset += 8;
-    
-    // Parse token price (8 bytes)
-    const tokenPrice = Number(data.readBigUInt64LE(offset));
-    offset += 8;
-    
-    // Parse is_active (1 byte)
-    const isActive = Boolean(data[offset]);
-    offset += 1;
-    
-    // Parse bump (1 byte)
-    const bump = data[offset];
-    offset += 1;
-
-    // Parse token_mint (32 bytes)
-    const tokenMintAddress = new PublicKey(data.slice(offset, offset + 32));
-    offset += 32;
-
-    // Parse milestone_count (1 byte)
-    const milestoneCount = data[offset];
-    offset += 1;
-
-    // Parse proposal_count (1 byte)
-    const proposalCount = data[offset];
-    offset += 1;
+    const project = this.unpackProject(accountInfo.data);
+    if (project.layoutVersion !== PROJECT_LAYOUT_VERSION) {
+      console.warn(`Project ${projectPda.toString()} uses layout ${project.layoutVersion}; run migrateProject`);
+    }
 
     console.log('Parsed project:', {
-      authority: authority.toString(),
-      name,
-      symbol,
-      fundingGoal,
-      totalRaised,
-      tokenPrice,
-      isActive,
-      bump,
-      tokenMintAddress: tokenMintAddress.toString(),
-      milestoneCount,
-      proposalCount,
+      authority: project.authority.toString(),
+      name: project.name,
+      symbol: project.symbol,
+      fundingGoal: project.fundingGoal,
+      totalRaised: project.totalRaised,
+      tokenPrice: project.tokenPrice,
+      isActive: project.isActive,
+      tokenMintAddress: project.tokenMintAddress.toString(),
+      milestoneCount: project.milestoneCount,
+      proposalCount: project.proposalCount,
+      layoutVersion: project.layoutVersion,
     });
 
-    return {
-      authority,
-      name,
-      symbol,
-      fundingGoal,
-      totalRaised,
-      tokenPrice,
-      isActive,
-      tokenMintAddress,
-      proposalCount,
-      milestoneCount,
-    };
+    return project;
   }
 
   async getProjectPda(authority: PublicKey): Promise<PublicKey> {
@@ -329,6 +545,135 @@ export class UnicornFactoryClient {
     return pda;
   }
 
+  async getVaultPda(projectPda: PublicKey): Promise<PublicKey> {
+    const [pda] = await PublicKey.findProgramAddress(
+      [Buffer.from('vault'), projectPda.toBuffer()],
+      this.programId
+    );
+    return pda;
+  }
+
+  async getContributorRecordPda(projectPda: PublicKey, contributor: PublicKey): Promise<PublicKey> {
+    const [pda] = await PublicKey.findProgramAddress(
+      [Buffer.from('contributor'), projectPda.toBuffer(), contributor.toBuffer()],
+      this.programId
+    );
+    return pda;
+  }
+
+  async getVoteRecordPda(proposalPda: PublicKey, voter: PublicKey): Promise<PublicKey> {
+    const [pda] = await PublicKey.findProgramAddress(
+      [Buffer.from('vote'), proposalPda.toBuffer(), voter.toBuffer()],
+      this.programId
+    );
+    return pda;
+  }
+
+  async getVoterEscrowPda(projectPda: PublicKey, holder: PublicKey): Promise<PublicKey> {
+    const [pda] = await PublicKey.findProgramAddress(
+      [Buffer.from('escrow'), projectPda.toBuffer(), holder.toBuffer()],
+      this.programId
+    );
+    return pda;
+  }
+
+  async getEscrowVaultPda(projectPda: PublicKey): Promise<PublicKey> {
+    const [pda] = await PublicKey.findProgramAddress(
+      [Buffer.from('escrow_vault'), projectPda.toBuffer()],
+      this.programId
+    );
+    return pda;
+  }
+
+  // Quote token account of `owner` for projects raising in an SPL token, or
+  // null for native SOL projects where the wallet itself pays and receives
+  private async getQuoteAccount(project: Project, owner: PublicKey): Promise<PublicKey | null> {
+    if (project.quoteMint.equals(PublicKey.default)) {
+      return null;
+    }
+    return getAssociatedTokenAddress(project.quoteMint, owner, true);
+  }
+
+  // Moves a project created before the layout was versioned onto the current
+  // layout. Anyone can pay for the migration.
+  async migrateProject(projectPda: PublicKey): Promise<string> {
+    const instruction = new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: projectPda, isSigner: false, isWritable: true },
+        { pubkey: this.provider.wallet.publicKey, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: await this.getVaultPda(projectPda), isSigner: false, isWritable: true },
+      ],
+      data: Buffer.from([43]), // 43 is the instruction index for migrate project
+    });
+
+    const tx = await this.provider.sendAndConfirm(
+      new Transaction().add(instruction)
+    );
+
+    return tx;
+  }
+
+  // Escrows project tokens so they count towards votes, vetoes and proposal
+  // thresholds. Escrowed tokens stay locked until the tallies they were
+  // counted in have closed.
+  async depositEscrow(projectPda: PublicKey, amount: number): Promise<string> {
+    const project = await this.getProject(projectPda);
+    const holder = this.provider.wallet.publicKey;
+
+    const data = Buffer.alloc(8);
+    data.writeBigUInt64LE(BigInt(amount), 0);
+
+    const instruction = new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: projectPda, isSigner: false, isWritable: false },
+        { pubkey: holder, isSigner: true, isWritable: true },
+        { pubkey: await getAssociatedTokenAddress(project.tokenMintAddress, holder), isSigner: false, isWritable: true },
+        { pubkey: await this.getVoterEscrowPda(projectPda, holder), isSigner: false, isWritable: true },
+        { pubkey: await this.getEscrowVaultPda(projectPda), isSigner: false, isWritable: true },
+        { pubkey: project.tokenMintAddress, isSigner: false, isWritable: false },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.from([45, ...data]), // 45 is the instruction index for deposit escrow
+    });
+
+    const tx = await this.provider.sendAndConfirm(
+      new Transaction().add(instruction)
+    );
+
+    return tx;
+  }
+
+  async withdrawEscrow(projectPda: PublicKey, amount: number): Promise<string> {
+    const project = await this.getProject(projectPda);
+    const holder = this.provider.wallet.publicKey;
+
+    const data = Buffer.alloc(8);
+    data.writeBigUInt64LE(BigInt(amount), 0);
+
+    const instruction = new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: projectPda, isSigner: false, isWritable: false },
+        { pubkey: holder, isSigner: true, isWritable: false },
+        { pubkey: await getAssociatedTokenAddress(project.tokenMintAddress, holder), isSigner: false, isWritable: true },
+        { pubkey: await this.getVoterEscrowPda(projectPda, holder), isSigner: false, isWritable: true },
+        { pubkey: await this.getEscrowVaultPda(projectPda), isSigner: false, isWritable: true },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.from([46, ...data]), // 46 is the instruction index for withdraw escrow
+    });
+
+    const tx = await this.provider.sendAndConfirm(
+      new Transaction().add(instruction)
+    );
+
+    return tx;
+  }
+
   async getAllProjects(): Promise<Project[]> {
     console.log('Fetching all projects...');
     try {
@@ -350,7 +695,8 @@ export class UnicornFactoryClient {
             // We can try to unpack the account as a Project and validate if it looks like one.
             // A more robust method would involve an account type discriminant byte in the Rust program,
             // but for now, we'll rely on unpacking and structural checks.
-            if (account.account.data.length >= PROJECT_ACCOUNT_SIZE) { // Check minimum size
+            const dataLength = account.account.data.length;
+            if (dataLength === PROJECT_ACCOUNT_SIZE || dataLength === LEGACY_PROJECT_ACCOUNT_SIZE) {
                 try {
                     const potentialProject = this.unpackProject(account.account.data);
                     
@@ -366,18 +712,7 @@ export class UnicornFactoryClient {
                         // This requires adding bump to the unpackProject helper
                         // For simplicity now, we just rely on PDA derivation match
 
-                        projects.push({
-                            authority: potentialProject.authority,
-                            name: potentialProject.name,
-                            symbol: potentialProject.symbol,
-                            fundingGoal: potentialProject.fundingGoal,
-                            totalRaised: potentialProject.totalRaised,
-                            tokenPrice: potentialProject.tokenPrice,
-                            isActive: potentialProject.isActive,
-                            tokenMintAddress: potentialProject.tokenMintAddress,
-                            proposalCount: potentialProject.proposalCount,
-                            milestoneCount: potentialProject.milestoneCount,
-                        });
+                        projects.push(potentialProject);
                     } else {
                        // console.log(`Account ${account.pubkey.toString()} is not a project PDA.`);
                     }
@@ -398,7 +733,8 @@ export class UnicornFactoryClient {
     }
   }
 
-  // Helper function to unpack Project data
+  // Helper function to unpack Project data. Legacy accounts only carry the
+  // fields up to proposal_count and report layout version 0.
   private unpackProject(data: Buffer): Project {
         let offset = 0;
 
@@ -446,9 +782,9 @@ export class UnicornFactoryClient {
 
         // Unpack proposal_count (1 byte)
         const proposalCount = data[offset];
-        offset += 1; // Ensure offset matches total size
+        offset += 1;
 
-        return {
+        const project: Project = {
             authority,
             name,
             symbol,
@@ -459,10 +795,120 @@ export class UnicornFactoryClient {
             tokenMintAddress,
             proposalCount,
             milestoneCount,
+            layoutVersion: 0,
+            totalAllocated: 0,
+            totalReleased: 0,
+            quoteMint: PublicKey.default,
+            lockupEnabled: false,
+            raiseDeadline: 0,
+            raiseState: isActive ? RaiseState.Open : RaiseState.Succeeded,
+            isGraduated: false,
+            minContribution: 0,
+            maxContributionPerWallet: 0,
+            roundCount: 0,
+            currentRound: 0,
+            launchMode: LaunchMode.BondingCurve,
+            creator: authority,
+            isPaused: false,
+            isDissolved: false,
+            totalSupply: 0,
         };
+        if (data.length < PROJECT_ACCOUNT_SIZE) {
+            return project;
+        }
+
+        // Unpack layout_version (1 byte)
+        project.layoutVersion = data[offset];
+        offset += 1;
+
+        // Unpack total_allocated and total_released (8 bytes each)
+        project.totalAllocated = Number(data.readBigUInt64LE(offset));
+        offset += 8;
+        project.totalReleased = Number(data.readBigUInt64LE(offset));
+        offset += 8;
+
+        // Skip vault_bump (1 byte)
+        offset += 1;
+
+        // Unpack quote_mint (32 bytes)
+        project.quoteMint = new PublicKey(data.slice(offset, offset + 32));
+        offset += 32;
+
+        // Skip vesting_reserve and vesting_allocated (8 bytes each)
+        offset += 16;
+
+        // Unpack lockup_enabled (1 byte)
+        project.lockupEnabled = Boolean(data[offset]);
+        offset += 1;
+
+        // Unpack raise_deadline (8 bytes)
+        project.raiseDeadline = Number(data.readBigInt64LE(offset));
+        offset += 8;
+
+        // Unpack raise_state (1 byte)
+        project.raiseState = data[offset] as RaiseState;
+        offset += 1;
+
+        // Skip graduation_bps (2 bytes) and graduation_token_reserve (8 bytes)
+        offset += 10;
+
+        // Unpack is_graduated (1 byte)
+        project.isGraduated = Boolean(data[offset]);
+        offset += 1;
+
+        // Skip sale_round_end (8 bytes)
+        offset += 8;
+
+        // Unpack min_contribution and max_contribution_per_wallet (8 bytes each)
+        project.minContribution = Number(data.readBigUInt64LE(offset));
+        offset += 8;
+        project.maxContributionPerWallet = Number(data.readBigUInt64LE(offset));
+        offset += 8;
+
+        // Unpack round_count and current_round (1 byte each)
+        project.roundCount = data[offset];
+        offset += 1;
+        project.currentRound = data[offset];
+        offset += 1;
+
+        // Unpack launch_mode (1 byte)
+        project.launchMode = data[offset] as LaunchMode;
+        offset += 1;
+
+        // Skip the auction fields (7 x 8 bytes)
+        offset += 56;
+
+        // Unpack creator (32 bytes)
+        project.creator = new PublicKey(data.slice(offset, offset + 32));
+        offset += 32;
+
+        // Skip pending_authority (32 bytes), governance (76 bytes) and curve params (16 bytes)
+        offset += 32 + 76 + 16;
+
+        // Unpack is_paused (1 byte)
+        project.isPaused = Boolean(data[offset]);
+        offset += 1;
+
+        // Skip held_deposits and slashed_deposits (8 bytes each)
+        offset += 16;
+
+        // Unpack is_dissolved (1 byte)
+        project.isDissolved = Boolean(data[offset]);
+        offset += 1;
+
+        // Unpack total_supply (8 bytes)
+        project.totalSupply = Number(data.readBigUInt64LE(offset));
+        offset += 8;
+
+        // Skip total_contributed (8 bytes)
+        offset += 8; // Ensure offset matches total size
+
+        return project;
   }
 
-  async buyTokens(projectPda: PublicKey, amount: number): Promise<string> {
+  // Buys are recorded like contributions; `roundAccounts` the funding round
+  // accounts the raise is in
+  async buyTokens(projectPda: PublicKey, amount: number, roundAccounts: PublicKey[] = []): Promise<string> {
     console.log('Starting buy tokens...');
     console.log('Buyer wallet:', this.provider.wallet.publicKey.toString());
     console.log('Project PDA:', projectPda.toString());
@@ -499,17 +945,28 @@ export class UnicornFactoryClient {
         console.log('Added create ATA instruction');
     }
 
+    const keys = [
+      { pubkey: projectPda, isSigner: false, isWritable: true }, // Project account (PDA)
+      { pubkey: this.provider.wallet.publicKey, isSigner: true, isWritable: true }, // Buyer account
+      { pubkey: buyerTokenAccountAddress, isSigner: false, isWritable: true }, // Buyer token account
+      { pubkey: projectTokenMintAddress, isSigner: false, isWritable: true }, // Project token mint account
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // SPL Token program
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // System program
+      { pubkey: await this.getVaultPda(projectPda), isSigner: false, isWritable: true }, // Project vault
+      { pubkey: await this.getContributorRecordPda(projectPda, this.provider.wallet.publicKey), isSigner: false, isWritable: true }, // Buyer contribution record
+    ];
+    const buyerQuoteAccount = await this.getQuoteAccount(project, this.provider.wallet.publicKey);
+    if (buyerQuoteAccount) {
+      keys.push({ pubkey: buyerQuoteAccount, isSigner: false, isWritable: true }); // Buyer quote token account
+    }
+    for (const roundAccount of roundAccounts) {
+      keys.push({ pubkey: roundAccount, isSigner: false, isWritable: true }); // Funding round
+    }
+
     // Create the buy instruction
     const buyInstruction = new TransactionInstruction({
       programId: this.programId,
-      keys: [
-        { pubkey: projectPda, isSigner: false, isWritable: true }, // Project account (PDA)
-        { pubkey: this.provider.wallet.publicKey, isSigner: true, isWritable: true }, // Buyer account
-        { pubkey: buyerTokenAccountAddress, isSigner: false, isWritable: true }, // Buyer token account
-        { pubkey: projectTokenMintAddress, isSigner: false, isWritable: true }, // Project token mint account
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // SPL Token program
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // System program
-      ],
+      keys,
       data: Buffer.from([
         2, // instruction index for buy tokens
         ...new Uint8Array(new BigInt64Array([BigInt(amount)]).buffer),
@@ -558,16 +1015,24 @@ export class UnicornFactoryClient {
 
     console.log('Instruction data:', Array.from(data));
 
+    const keys = [
+      { pubkey: projectPda, isSigner: false, isWritable: true }, // Project account (PDA)
+      { pubkey: this.provider.wallet.publicKey, isSigner: true, isWritable: true }, // Seller account (signer and writable to receive SOL)
+      { pubkey: sellerTokenAccountAddress, isSigner: false, isWritable: true }, // Seller token account
+      { pubkey: projectTokenMintAddress, isSigner: false, isWritable: true }, // Project token mint account
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // SPL Token program
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // System program
+      { pubkey: await this.getVaultPda(projectPda), isSigner: false, isWritable: true }, // Project vault paying out
+      { pubkey: await this.getContributorRecordPda(projectPda, this.provider.wallet.publicKey), isSigner: false, isWritable: true }, // Seller contribution record, if any
+    ];
+    const sellerQuoteAccount = await this.getQuoteAccount(project, this.provider.wallet.publicKey);
+    if (sellerQuoteAccount) {
+      keys.push({ pubkey: sellerQuoteAccount, isSigner: false, isWritable: true }); // Seller quote token account
+    }
+
     const instruction = new TransactionInstruction({
       programId: this.programId,
-      keys: [
-        { pubkey: projectPda, isSigner: false, isWritable: true }, // Project account (PDA)
-        { pubkey: this.provider.wallet.publicKey, isSigner: true, isWritable: true }, // Seller account (signer and writable to receive SOL)
-        { pubkey: sellerTokenAccountAddress, isSigner: false, isWritable: true }, // Seller token account
-        { pubkey: projectTokenMintAddress, isSigner: false, isWritable: true }, // Project token mint account
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // SPL Token program
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // System program
-      ],
+      keys,
       data,
     });
 
@@ -685,15 +1150,45 @@ export class UnicornFactoryClient {
     // Write milestone_id (u8)
     data.writeUInt8(milestoneId, offset);
 
+    const proposer = this.provider.wallet.publicKey;
+    const keys = [
+      { pubkey: projectPda, isSigner: false, isWritable: true },
+      { pubkey: proposalAccountPda, isSigner: false, isWritable: true },
+      { pubkey: proposer, isSigner: true, isWritable: true },
+      { pubkey: milestonePda, isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: project.tokenMintAddress, isSigner: false, isWritable: false }, // Project token mint (quorum snapshot)
+    ];
+
+    // Holders other than the authority prove their escrow and post the deposit
+    if (!proposer.equals(project.authority)) {
+      keys.push(
+        { pubkey: await this.getVoterEscrowPda(projectPda, proposer), isSigner: false, isWritable: true },
+        { pubkey: await this.getVaultPda(projectPda), isSigner: false, isWritable: true },
+      );
+      const quoteAccount = await this.getQuoteAccount(project, proposer);
+      if (quoteAccount) {
+        keys.push(
+          { pubkey: quoteAccount, isSigner: false, isWritable: true },
+          { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }, // Invoked for the deposit
+        );
+      }
+    }
+
+    // Re-proposing a milestone whose proposal failed, lapsed or was cancelled
+    // shows that proposal
+    const milestone = await this.getMilestone(projectPda, milestoneId);
+    if (milestone.status === MilestoneStatus.Proposed) {
+      const [currentProposalPda] = await PublicKey.findProgramAddress(
+        [Buffer.from('proposal'), projectPda.toBuffer(), Buffer.from([milestone.proposalId])],
+        this.programId
+      );
+      keys.push({ pubkey: currentProposalPda, isSigner: false, isWritable: false });
+    }
+
     const instruction = new TransactionInstruction({
       programId: this.programId,
-      keys: [
-        { pubkey: projectPda, isSigner: false, isWritable: true },
-        { pubkey: proposalAccountPda, isSigner: false, isWritable: true },
-        { pubkey: this.provider.wallet.publicKey, isSigner: true, isWritable: true },
-        { pubkey: milestonePda, isSigner: false, isWritable: true },
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-      ],
+      keys,
       data: Buffer.from([4, ...data]), // 4 is the instruction index
     });
 
@@ -739,17 +1234,24 @@ export class UnicornFactoryClient {
     );
     console.log('Proposal PDA:', proposalAccountPda.toString());
 
-    const data = Buffer.alloc(9); // 8 bytes for proposal ID + 1 byte for vote
+    const data = Buffer.alloc(9); // 8 bytes for proposal ID + 1 byte for the choice
     data.writeBigUInt64LE(BigInt(proposalId), 0);
-    data.writeUInt8(vote ? 1 : 0, 8);
+    data.writeUInt8(vote ? 1 : 0, 8); // VOTE_YES or VOTE_NO
+
+    // Votes are weighted by the voter's escrowed project tokens and recorded
+    // in a per-voter vote record so they cannot be cast twice
+    const voter = this.provider.wallet.publicKey;
+    const voterEscrow = await this.getVoterEscrowPda(projectPda, voter);
 
     const instruction = new TransactionInstruction({
       programId: this.programId,
       keys: [
         { pubkey: projectPda, isSigner: false, isWritable: false }, // Project account (Readonly)
         { pubkey: proposalAccountPda, isSigner: false, isWritable: true }, // Proposal account (PDA - writable to update votes)
-        { pubkey: this.provider.wallet.publicKey, isSigner: true, isWritable: false }, // Voter account (Signer, Readonly)
+        { pubkey: voter, isSigner: true, isWritable: true }, // Voter account (Signer, pays for the vote record)
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // System program
+        { pubkey: voterEscrow, isSigner: false, isWritable: true }, // Voter escrow (PDA, locked until the tally closes)
+        { pubkey: await this.getVoteRecordPda(proposalAccountPda, voter), isSigner: false, isWritable: true }, // Vote record (PDA)
       ],
       data: Buffer.from([5, ...data]), // 5 is the instruction index for vote
     });
@@ -802,15 +1304,24 @@ export class UnicornFactoryClient {
     const data = Buffer.alloc(8);
     data.writeBigUInt64LE(BigInt(proposalId), 0);
 
+    const project = await this.getProject(projectPda);
+    const keys = [
+      { pubkey: projectPda, isSigner: false, isWritable: true }, // Project account (PDA - writable to track released funds)
+      { pubkey: proposalAccountPda, isSigner: false, isWritable: true }, // Proposal account (PDA - writable to mark as executed)
+      { pubkey: this.provider.wallet.publicKey, isSigner: true, isWritable: true }, // Authority account (Signer, writable to receive SOL)
+      { pubkey: milestonePda, isSigner: false, isWritable: true }, // Milestone account (PDA - writable to update its status)
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // System program
+      { pubkey: await this.getVaultPda(projectPda), isSigner: false, isWritable: true }, // Project vault paying out
+    ];
+    const authorityQuoteAccount = await this.getQuoteAccount(project, this.provider.wallet.publicKey);
+    if (authorityQuoteAccount) {
+      keys.push({ pubkey: authorityQuoteAccount, isSigner: false, isWritable: true }); // Authority quote token account
+      keys.push({ pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }); // SPL Token program
+    }
+
     const instruction = new TransactionInstruction({
       programId: this.programId,
-      keys: [
-        { pubkey: projectPda, isSigner: false, isWritable: true }, // Project account (PDA - writable to send SOL)
-        { pubkey: proposalAccountPda, isSigner: false, isWritable: true }, // Proposal account (PDA - writable to mark as executed)
-        { pubkey: this.provider.wallet.publicKey, isSigner: true, isWritable: true }, // Authority account (Signer, writable to receive SOL)
-        { pubkey: milestonePda, isSigner: false, isWritable: true }, // Milestone account (PDA - writable to update has_proposal flag)
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // System program
-      ],
+      keys,
       data: Buffer.from([6, ...data]), // 6 is the instruction index for release funds
     });
 
@@ -851,7 +1362,10 @@ export class UnicornFactoryClient {
     milestoneIndex: number,
     title: string,
     description: string,
-    amount: number
+    amount: number,
+    deadline = 0, // Unix time the milestone must be approved by, 0 for none
+    trancheCount = 1, // Equal payouts, the first on approval
+    trancheInterval = 0 // Seconds between tranches
   ): Promise<string> {
     console.log('Adding milestone...');
 
@@ -867,7 +1381,7 @@ export class UnicornFactoryClient {
     const descriptionBytes = new TextEncoder().encode(description);
 
     // Create instruction data buffer
-    const data = Buffer.alloc(4 + 4 + 24 + titleBytes.length + descriptionBytes.length + 8);
+    const data = Buffer.alloc(4 + 4 + 24 + titleBytes.length + descriptionBytes.length + 8 + 8 + 1 + 8);
     let offset = 0;
 
     // Write title length (u32)
@@ -893,6 +1407,14 @@ export class UnicornFactoryClient {
     const amountBuffer = Buffer.alloc(8);
     amountBuffer.writeBigUInt64LE(BigInt(amount));
     data.set(amountBuffer, offset);
+    offset += 8;
+
+    // Write deadline (i64) and tranche schedule (u8 count, i64 interval)
+    data.writeBigInt64LE(BigInt(deadline), offset);
+    offset += 8;
+    data.writeUInt8(trancheCount, offset);
+    offset += 1;
+    data.writeBigInt64LE(BigInt(trancheInterval), offset);
 
     const instruction = new TransactionInstruction({
       programId: this.programId,
@@ -947,6 +1469,13 @@ export class UnicornFactoryClient {
     const data = Buffer.alloc(1);
     data.writeUInt8(milestoneId, 0);
 
+    // Completion is approved by the milestone's latest proposal
+    const milestone = await this.getMilestone(projectPda, milestoneId);
+    const [proposalAccountPda] = await PublicKey.findProgramAddress(
+        [Buffer.from('proposal'), projectPda.toBuffer(), Buffer.from([milestone.proposalId])],
+        this.programId
+    );
+
     const instruction = new TransactionInstruction({
       programId: this.programId,
       keys: [
@@ -954,6 +1483,7 @@ export class UnicornFactoryClient {
         { pubkey: milestoneAccountPda, isSigner: false, isWritable: true }, // Milestone account (PDA)
         { pubkey: this.provider.wallet.publicKey, isSigner: true, isWritable: false }, // Authority account (Signer, not modified)
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // System program
+        { pubkey: proposalAccountPda, isSigner: false, isWritable: false }, // Approving proposal (PDA)
       ],
       data: Buffer.from([8, ...data]), // 8 is the instruction index for complete milestone
     });
@@ -1004,75 +1534,20 @@ export class UnicornFactoryClient {
     }
     console.log('Proposal Account Data Length:', accountInfo.data.length);
 
-    // Parse account data according to the Proposal struct layout
-    const data = accountInfo.data;
-    let offset = 0;
-
-    // Parse creator (32 bytes)
-    const creator = new PublicKey(data.slice(offset, offset + 32));
-    offset += 32;
-    
-    // Parse title (32 bytes)
-    const title = new TextDecoder().decode(data.slice(offset, offset + 32)).replace(/\0/g, '');
-    offset += 32;
-    
-    // Parse description (256 bytes)
-    const description = new TextDecoder().decode(data.slice(offset, offset + 256)).replace(/\0/g, '');
-    offset += 256;
-    
-    // Parse milestone ID (1 byte)
-    const milestoneId = data[offset];
-    offset += 1;
-    
-    // Parse yes votes (8 bytes)
-    const yesVotes = Number(data.readBigUInt64LE(offset));
-    offset += 8;
-    
-    // Parse no votes (8 bytes)
-    const noVotes = Number(data.readBigUInt64LE(offset));
-    offset += 8;
-    
-    // Parse is executed (1 byte)
-    const isExecuted = Boolean(data[offset]);
-    offset += 1;
-    
-    // Parse created at (8 bytes)
-    const createdAt = Number(data.readBigInt64LE(offset));
-    offset += 8;
-    
-    console.log(`Debug: Before votingEnd check - offset: ${offset}, data.length: ${data.length}`);
-
-    // Conditionally unpack votingEnd if buffer has enough bytes
-    let votingEnd: number;
-    if (offset + 8 <= data.length) {
-        votingEnd = Number(data.readBigInt64LE(offset));
-    } else {
-        votingEnd = 0; // Default value if votingEnd field is missing
-    }
+    const proposal = this.unpackProposal(accountInfo.data);
 
     console.log('Parsed proposal:', {
-        creator: creator.toString(),
-        title,
-        description,
-        milestoneId,
-        yesVotes,
-        noVotes,
-        isExecuted,
-        createdAt,
-        votingEnd,
+        creator: proposal.creator.toString(),
+        title: proposal.title,
+        milestoneId: proposal.milestoneId,
+        yesVotes: proposal.yesVotes,
+        noVotes: proposal.noVotes,
+        isExecuted: proposal.isExecuted,
+        isCancelled: proposal.isCancelled,
+        votingEnd: proposal.votingEnd,
     });
 
-    return {
-      creator,
-      title,
-      description,
-      milestoneId,
-      yesVotes,
-      noVotes,
-      isExecuted,
-      createdAt,
-      votingEnd,
-    };
+    return proposal;
   }
 
   async getMilestone(projectPda: PublicKey, milestoneId: number): Promise<Milestone> {
@@ -1093,50 +1568,17 @@ export class UnicornFactoryClient {
       throw new Error('Milestone not found');
     }
 
-    // Parse account data according to the Milestone struct layout
-    const data = accountInfo.data;
-    let offset = 0;
-
-    // Parse title (32 bytes)
-    const title = new TextDecoder().decode(data.slice(offset, offset + 32)).replace(/\0/g, '');
-    offset += 32;
-    
-    // Parse description (256 bytes)
-    const description = new TextDecoder().decode(data.slice(offset, offset + 256)).replace(/\0/g, '');
-    offset += 256;
-    
-    // Parse amount (8 bytes)
-    const amount = Number(data.readBigUInt64LE(offset));
-    offset += 8;
-    
-    // Parse is completed (1 byte)
-    const isCompleted = Boolean(data[offset]);
-    offset += 1;
-    
-    // Parse completed at (8 bytes)
-    const completedAt = Number(data.readBigInt64LE(offset));
-    offset += 8;
-
-    // Parse has proposal (1 byte)
-    const hasProposal = Boolean(data[offset]);
+    const milestone = this.unpackMilestone(accountInfo.data);
 
     console.log('Parsed milestone:', {
-        title,
-        description,
-        amount,
-        isCompleted,
-        completedAt,
-        hasProposal,
+        title: milestone.title,
+        amount: milestone.amount,
+        status: MilestoneStatus[milestone.status],
+        releasedAmount: milestone.releasedAmount,
+        proposalId: milestone.proposalId,
     });
 
-    return {
-      title,
-      description,
-      amount,
-      isCompleted,
-      completedAt,
-      hasProposal,
-    };
+    return milestone;
   }
 
   async getAllProposals(): Promise<Proposal[]> {
@@ -1211,13 +1653,34 @@ export class UnicornFactoryClient {
         const createdAt = Number(data.readBigInt64LE(offset));
         offset += 8;
 
-        // Conditionally unpack votingEnd if buffer has enough bytes
-        let votingEnd: number;
-        if (offset + 8 <= data.length) {
-            votingEnd = Number(data.readBigInt64LE(offset));
-        } else {
-            votingEnd = 0; // Default value if votingEnd field is missing
-        }
+        const votingEnd = Number(data.readBigInt64LE(offset));
+        offset += 8;
+
+        const isCancelled = Boolean(data[offset]);
+        offset += 1;
+
+        const vetoVotes = Number(data.readBigUInt64LE(offset));
+        offset += 8;
+
+        const abstainVotes = Number(data.readBigUInt64LE(offset));
+        offset += 8;
+
+        const quorumVotes = Number(data.readBigUInt64LE(offset));
+        offset += 8;
+
+        // Skip option count, labels, votes and the winning option
+        offset += 1 + 32 * MAX_PROPOSAL_OPTIONS + 8 * MAX_PROPOSAL_OPTIONS + 1;
+
+        const revealEnd = Number(data.readBigInt64LE(offset));
+        offset += 8;
+
+        // Skip unrevealed votes and the action
+        offset += 8 + 49;
+
+        const deposit = Number(data.readBigUInt64LE(offset));
+        offset += 8;
+
+        const evidenceVersion = data[offset];
 
         return {
             creator,
@@ -1229,6 +1692,13 @@ export class UnicornFactoryClient {
             isExecuted,
             createdAt,
             votingEnd,
+            isCancelled,
+            vetoVotes,
+            abstainVotes,
+            quorumVotes,
+            revealEnd,
+            deposit,
+            evidenceVersion,
         };
   }
 
@@ -1245,21 +1715,50 @@ export class UnicornFactoryClient {
         const amount = Number(data.readBigUInt64LE(offset));
         offset += 8;
 
-        const isCompleted = Boolean(data[offset]);
+        const status = data[offset] as MilestoneStatus;
         offset += 1;
 
-        const completedAt = Number(data.readBigInt64LE(offset));
+        const releasedAt = Number(data.readBigInt64LE(offset));
+        offset += 8;
+
+        const proposalId = data[offset];
+        offset += 1;
+
+        const deadline = Number(data.readBigInt64LE(offset));
+        offset += 8;
+
+        // Skip missed_at
+        offset += 8;
+
+        const trancheCount = data[offset];
+        offset += 1;
+
+        const trancheInterval = Number(data.readBigInt64LE(offset));
+        offset += 8;
+
+        const approvedAt = Number(data.readBigInt64LE(offset));
+        offset += 8;
+
+        const releasedAmount = Number(data.readBigUInt64LE(offset));
         offset += 8;
 
-        const hasProposal = Boolean(data[offset]);
+        const evidenceVersion = data[offset];
 
         return {
             title,
             description,
             amount,
-            isCompleted,
-            completedAt,
-            hasProposal,
+            status,
+            isCompleted: status === MilestoneStatus.Approved || status === MilestoneStatus.Released,
+            completedAt: approvedAt,
+            hasProposal: status === MilestoneStatus.Proposed,
+            releasedAt,
+            proposalId,
+            deadline,
+            trancheCount,
+            trancheInterval,
+            releasedAmount,
+            evidenceVersion,
         };
   }
 }
\ No newline at end of file